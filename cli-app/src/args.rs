@@ -0,0 +1,150 @@
+//! Flags of the command line. They may come before or after the mode and its arguments.
+
+use bot_against_humanity_core::i18n::Message;
+
+use crate::{assets::CardSource, input::CountBase};
+
+/// The flags that take a value.
+const FLAGS: [&str; 7] = [
+    "--questions",
+    "--answers",
+    "--pack",
+    "--hand-size",
+    "--win-target",
+    "--count-base",
+    "--seed",
+];
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Args {
+    /// The mode and its arguments, like `host Alice`.
+    pub words: Vec<String>,
+    /// In the order given. The built-in cards are used only if there are none.
+    pub cards: Vec<CardSource>,
+    pub hand_size: Option<usize>,
+    pub win_target: Option<usize>,
+    pub count_base: Option<CountBase>,
+    pub seed: Option<u64>,
+    pub help: bool,
+}
+
+impl Args {
+    /// Valid flags, each value either after a space or after `=`:
+    /// - --questions `path`
+    /// - --answers `path`
+    /// - --pack `path`
+    /// - --hand-size `usize >= 1`
+    /// - --win-target `usize >= 1`
+    /// - --count-base `0 or 1`
+    /// - --seed `u64`
+    /// - --help, -h
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, Message> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if arg == "--help" || arg == "-h" {
+                parsed.help = true;
+                continue;
+            }
+            if !arg.starts_with("--") {
+                parsed.words.push(arg);
+                continue;
+            }
+
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_owned(), Some(value.to_owned())),
+                None => (arg, None),
+            };
+            if !FLAGS.contains(&flag.as_str()) {
+                return Err(Message::new("cli.unknownFlag").arg("flag", flag));
+            }
+            let value = match value.or_else(|| args.next()) {
+                Some(value) => value,
+                None => return Err(Message::new("cli.flagNeedsValue").arg("flag", flag)),
+            };
+            let bad_value = || {
+                Message::new("cli.badFlagValue")
+                    .arg("flag", flag.as_str())
+                    .arg("value", value.as_str())
+            };
+
+            match flag.as_str() {
+                "--questions" => parsed.cards.push(CardSource::Questions(value.into())),
+                "--answers" => parsed.cards.push(CardSource::Answers(value.into())),
+                "--pack" => parsed.cards.push(CardSource::Pack(value.into())),
+                "--hand-size" => {
+                    parsed.hand_size = Some(at_least_one(&value).ok_or_else(bad_value)?)
+                }
+                "--win-target" => {
+                    parsed.win_target = Some(at_least_one(&value).ok_or_else(bad_value)?)
+                }
+                "--count-base" => {
+                    parsed.count_base = Some(match value.as_str() {
+                        "0" => CountBase::ZeroBased,
+                        "1" => CountBase::OneBased,
+                        _ => return Err(bad_value()),
+                    })
+                }
+                "--seed" => parsed.seed = Some(value.parse().map_err(|_| bad_value())?),
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+fn at_least_one(value: &str) -> Option<usize> {
+    value.parse().ok().filter(|&number| number >= 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, Message> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parse_flags_around_the_mode() {
+        assert_eq!(
+            parse(&[
+                "--pack",
+                "packs",
+                "host",
+                "--hand-size=7",
+                "Alice",
+                "--questions",
+                "q.txt",
+                "--count-base",
+                "0",
+            ]),
+            Ok(Args {
+                words: vec!["host".into(), "Alice".into()],
+                cards: vec![
+                    CardSource::Pack("packs".into()),
+                    CardSource::Questions("q.txt".into())
+                ],
+                hand_size: Some(7),
+                count_base: Some(CountBase::ZeroBased),
+                ..Args::default()
+            })
+        );
+        assert!(parse(&["-h"]).unwrap().help);
+    }
+
+    #[test]
+    fn reject_bad_flags() {
+        assert_eq!(
+            parse(&["--win-target", "0"]).unwrap_err().key,
+            "cli.badFlagValue"
+        );
+        assert_eq!(parse(&["--answers"]).unwrap_err().key, "cli.flagNeedsValue");
+        assert_eq!(
+            parse(&["--colour", "red"]).unwrap_err().key,
+            "cli.unknownFlag"
+        );
+    }
+}
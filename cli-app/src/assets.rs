@@ -1,3 +1,174 @@
+//! The cards the game is played with: a few built in, or loaded from files.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use bot_against_humanity_core::decks::{
+    parse_csv, parse_json_against_humanity, parse_tsv, DeckAnswer, DeckImport, DeckQuestion,
+};
+
+type Error = String;
+
+/// Where to load cards from, on the command line or in the preparation stage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum CardSource {
+    Questions(PathBuf),
+    Answers(PathBuf),
+    Pack(PathBuf),
+}
+
+impl CardSource {
+    pub fn path(&self) -> &Path {
+        match self {
+            CardSource::Questions(path) | CardSource::Answers(path) | CardSource::Pack(path) => {
+                path
+            }
+        }
+    }
+
+    /// The cards, and what was skipped or looks wrong in them, located by file.
+    pub fn load(&self) -> Result<DeckImport, Error> {
+        let mut import = DeckImport::default();
+
+        match self {
+            CardSource::Questions(path) => add_questions(&mut import, read_cards(path)?),
+            CardSource::Answers(path) => add_answers(&mut import, read_cards(path)?),
+            CardSource::Pack(path) if path.is_file() => import_deck(path, &mut import)?,
+            CardSource::Pack(path) => read_pack(path, &mut import)?,
+        }
+
+        Ok(import)
+    }
+}
+
+fn add_questions(import: &mut DeckImport, texts: Vec<String>) {
+    import.deck.questions.extend(texts.into_iter().map(|text| {
+        let mut question = DeckQuestion {
+            text,
+            pick: 1,
+            pack: None,
+            tags: vec![],
+        };
+        question.pick = question.num_blanks().max(1);
+        question
+    }));
+}
+
+fn add_answers(import: &mut DeckImport, texts: Vec<String>) {
+    import
+        .deck
+        .answers
+        .extend(texts.into_iter().map(|text| DeckAnswer {
+            text,
+            pack: None,
+            tags: vec![],
+        }));
+}
+
+/// Whether `path` is a deck the core can import: JSON Against Humanity, CSV or TSV.
+fn is_deck(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| ["json", "csv", "tsv"].contains(&extension))
+}
+
+/// Add a deck file to `import`, choosing the importer by the extension.
+fn import_deck(path: &Path, import: &mut DeckImport) -> Result<(), Error> {
+    let text = fs::read_to_string(path).map_err(|err| describe(path, err))?;
+    let parsed = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => parse_json_against_humanity(&text),
+        Some("csv") => parse_csv(&text),
+        Some("tsv") => parse_tsv(&text),
+        _ => return Err(describe(path, "not a .json, .csv or .tsv deck")),
+    }
+    .map_err(|err| describe(path, err.to_string()))?;
+
+    import.deck.questions.extend(parsed.deck.questions);
+    import.deck.answers.extend(parsed.deck.answers);
+    import
+        .issues
+        .extend(parsed.issues.into_iter().map(|mut issue| {
+            issue.location = describe(path, &issue.location);
+            issue
+        }));
+
+    Ok(())
+}
+
+/// Cards of a file, or of every file in a directory in the order of their names. A `.json`
+/// file is an array of cards, like the library files of the interface; any other file has one
+/// card per line. Blank cards are skipped.
+fn read_cards(path: &Path) -> Result<Vec<String>, Error> {
+    if path.is_dir() {
+        let mut cards = vec![];
+        for entry in sorted_entries(path)? {
+            if entry.is_file() {
+                cards.extend(read_cards(&entry)?);
+            }
+        }
+        return Ok(cards);
+    }
+
+    let text = fs::read_to_string(path).map_err(|err| describe(path, err))?;
+    let cards = if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        serde_json::from_str::<Vec<String>>(&text).map_err(|err| describe(path, err))?
+    } else {
+        text.lines().map(str::to_owned).collect()
+    };
+
+    Ok(cards
+        .into_iter()
+        .map(|card| card.trim().to_owned())
+        .filter(|card| !card.is_empty())
+        .collect())
+}
+
+/// A pack is a deck file, or a directory with `questions` and `answers`, each a file or a
+/// directory of files, such as `questions.txt` or `answers/base.json`. Its other `.json`, `.csv`
+/// and `.tsv` files are decks, and its other directories are packs in turn.
+fn read_pack(path: &Path, import: &mut DeckImport) -> Result<(), Error> {
+    for entry in sorted_entries(path)? {
+        match entry.file_stem().and_then(|stem| stem.to_str()) {
+            Some("questions") => add_questions(import, read_cards(&entry)?),
+            Some("answers") => add_answers(import, read_cards(&entry)?),
+            _ if entry.is_dir() => read_pack(&entry, import)?,
+            _ if is_deck(&entry) => import_deck(&entry, import)?,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// The paths in a directory by name, without hidden ones.
+fn sorted_entries(path: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut entries = fs::read_dir(path)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<Vec<_>>>()
+        })
+        .map_err(|err| describe(path, err))?;
+    entries.retain(|entry| {
+        !entry
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'))
+    });
+    entries.sort();
+
+    Ok(entries)
+}
+
+fn describe(path: &Path, err: impl ToString) -> Error {
+    format!("{}: {}", path.display(), err.to_string())
+}
+
 pub(crate) fn questions() -> Vec<String> {
     vec![
         "Who is the smartest person alive?",
@@ -70,3 +241,54 @@ pub(crate) fn answers() -> Vec<String> {
     .map(|p| p.to_owned())
     .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_a_directory_of_packs() {
+        let root = std::env::temp_dir().join(format!("cli-app-packs-{}", std::process::id()));
+        let library = root.join("library");
+        fs::create_dir_all(library.join("questions")).unwrap();
+        fs::create_dir_all(library.join("answers")).unwrap();
+        fs::create_dir_all(root.join("mine")).unwrap();
+        fs::write(library.join("questions/b.json"), r#"["Why _?", "  "]"#).unwrap();
+        fs::write(library.join("questions/a.txt"), "Who _?\n\nWhat _?\n").unwrap();
+        fs::write(library.join("answers/base.json"), r#"["a cat"]"#).unwrap();
+        fs::write(root.join("mine/answers.txt"), "a dog\r\n").unwrap();
+        fs::write(
+            root.join("mine/extra.csv"),
+            "type,text\nquestion,How _?\njoker,?\nanswer,a fox\n",
+        )
+        .unwrap();
+        fs::write(root.join("README.md"), "Not cards").unwrap();
+
+        let import = CardSource::Pack(root.clone()).load().unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            import.deck.question_texts(),
+            vec!["Who _?", "What _?", "Why _?", "How _?"]
+        );
+        assert_eq!(import.deck.answer_texts(), vec!["a cat", "a dog", "a fox"]);
+        assert_eq!(import.issues.len(), 1);
+        assert!(import.issues[0].location.ends_with("extra.csv: line 3"));
+    }
+
+    #[test]
+    fn load_a_deck_file() {
+        let path = std::env::temp_dir().join(format!("cli-app-deck-{}.json", std::process::id()));
+        fs::write(
+            &path,
+            r#"{"black": [{"text": "Why _?", "pick": 1}], "white": ["a cat"], "packs": []}"#,
+        )
+        .unwrap();
+
+        let import = CardSource::Pack(path.clone()).load().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(import.deck.question_texts(), vec!["Why _?"]);
+        assert_eq!(import.deck.answer_texts(), vec!["a cat"]);
+    }
+}
@@ -0,0 +1,197 @@
+//! Commands that can be typed at every prompt of a game, instead of card indices.
+
+use bot_against_humanity_core::{
+    drivers::{generic::GenericDriver, GameCoreDriver},
+    i18n::{Locale, Message},
+    GameCoreError,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{color_player_name, input::CountBase, input::GameInput, ranking_lines};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum GameCommand {
+    Scores,
+    Redraw,
+    Hand,
+    Question,
+    /// The ID of the player to remove.
+    Kick(String),
+    Quit,
+    Help,
+}
+
+/// Why a command ended the game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Ending {
+    Quit,
+    /// The player is to be removed before the next game, like with /remove.
+    Kick(String),
+}
+
+impl GameCommand {
+    /// What is typed after the "/", in the order of the help.
+    pub const NAMES: [&'static str; 7] = [
+        "scores", "redraw", "hand", "question", "kick", "quit", "help",
+    ];
+
+    /// Read a line like "/scores" or "/kick Alex", ignoring the case of the command and the
+    /// surrounding spaces.
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim().strip_prefix('/')?;
+        let (name, argument) = match line.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, argument.trim()),
+            None => (line, ""),
+        };
+
+        let command = match name.to_lowercase().as_str() {
+            "kick" if !argument.is_empty() => return Some(GameCommand::Kick(argument.to_owned())),
+            "scores" => GameCommand::Scores,
+            "redraw" => GameCommand::Redraw,
+            "hand" => GameCommand::Hand,
+            "question" => GameCommand::Question,
+            "quit" => GameCommand::Quit,
+            "help" => GameCommand::Help,
+            _ => return None,
+        };
+
+        argument.is_empty().then_some(command)
+    }
+}
+
+/// Run `command` for `player`. Return the lines to show them, or why the game ends.
+pub(crate) fn run(
+    command: GameCommand,
+    driver: &mut GenericDriver,
+    player: &str,
+    locale: Locale,
+    count_base: CountBase,
+) -> Result<Vec<String>, Ending> {
+    let lines = match command {
+        GameCommand::Scores => ranking_lines(&driver.scores()),
+        GameCommand::Redraw => match driver.redraw_hands([player]) {
+            Ok(()) => hand_lines(driver, player, locale, count_base),
+            Err(err) => vec![err.localize(locale)],
+        },
+        GameCommand::Hand => hand_lines(driver, player, locale, count_base),
+        GameCommand::Question => vec![Message::new("cli.answerThis")
+            .arg("question", driver.current_question().unwrap_or_default())
+            .localize(locale)],
+        GameCommand::Kick(kicked) => {
+            if driver.ordered_players().contains(&kicked) {
+                return Err(Ending::Kick(kicked));
+            }
+            vec![GameCoreError::PlayerDoesNotExist {
+                display_name: kicked.clone(),
+                name: kicked,
+            }
+            .localize(locale)]
+        }
+        GameCommand::Quit => return Err(Ending::Quit),
+        GameCommand::Help => GameCommand::NAMES
+            .iter()
+            .map(|name| Message::new(format!("cli.command.{}", name)).localize(locale))
+            .collect(),
+    };
+
+    Ok(lines)
+}
+
+/// Ask with `read` until `player` answers, running the commands they type before that.
+/// Return why the game ends instead, if it does.
+pub(crate) fn read_answer<T>(
+    driver: &mut GenericDriver,
+    player: &str,
+    locale: Locale,
+    count_base: CountBase,
+    read: impl Fn() -> GameInput<T>,
+) -> Result<T, Ending> {
+    loop {
+        match read() {
+            GameInput::Answer(answer) => return Ok(answer),
+            GameInput::Command(command) => {
+                for line in run(command, driver, player, locale, count_base)? {
+                    println!("{}", line);
+                }
+            }
+        }
+    }
+}
+
+fn hand_lines(
+    driver: &GenericDriver,
+    player: &str,
+    locale: Locale,
+    count_base: CountBase,
+) -> Vec<String> {
+    let view = match driver.view_for(player) {
+        Ok(view) => view,
+        Err(err) => return vec![err.localize(locale)],
+    };
+
+    let title = Message::new("cli.yourHand")
+        .arg("player", color_player_name(player).to_string())
+        .localize(locale);
+    let cards = view
+        .hand
+        .iter()
+        .enumerate()
+        .map(|(i, card)| format!("{} - {}", i + count_base.first_index(), card.content));
+
+    std::iter::once(title).chain(cards).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bot_against_humanity_core::drivers::generic::GenericDriverBuilder;
+
+    use super::*;
+
+    #[test]
+    fn parse_commands() {
+        for name in GameCommand::NAMES {
+            let line = match name {
+                "kick" => "/kick Alex".to_owned(),
+                name => format!("/{}", name),
+            };
+            assert!(GameCommand::parse(&line).is_some(), "{}", line);
+        }
+        assert_eq!(
+            GameCommand::parse("  /Scores \n"),
+            Some(GameCommand::Scores)
+        );
+        assert_eq!(
+            GameCommand::parse("/KICK  Mary Ann "),
+            Some(GameCommand::Kick("Mary Ann".to_owned()))
+        );
+        assert_eq!(GameCommand::parse("/kick"), None);
+        assert_eq!(GameCommand::parse("/scores all"), None);
+        assert_eq!(GameCommand::parse("scores"), None);
+    }
+
+    #[test]
+    fn kick_only_players_in_the_game() {
+        let mut builder = GenericDriverBuilder::new();
+        for player in ["Ann", "Bob", "Cat"] {
+            builder.add_player(player).unwrap();
+        }
+        builder.add_new_questions(["Why _?"]);
+        builder.add_new_answers((0..40).map(|answer| answer.to_string()));
+        builder.set_seed(Some(0));
+        let mut driver = builder.build().unwrap();
+        let mut kick = |player: &str| {
+            let command = GameCommand::Kick(player.to_owned());
+            run(
+                command,
+                &mut driver,
+                "Ann",
+                Locale::English,
+                CountBase::OneBased,
+            )
+        };
+
+        assert_eq!(kick("Bob"), Err(Ending::Kick("Bob".to_owned())));
+        assert!(kick("Dan").is_ok());
+    }
+}
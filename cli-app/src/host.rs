@@ -0,0 +1,631 @@
+//! `host`: run the game and play in this terminal while the others `join` over TCP. Each
+//! player sees their own hand in their own terminal; everything else is shown to everyone.
+
+use std::{
+    collections::BTreeMap,
+    io,
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use bot_against_humanity_core::{
+    drivers::{
+        generic::{GenericDriver, GenericDriverBuilder},
+        GameCoreDriver,
+    },
+    i18n::{Locale, Message},
+};
+
+use crate::{
+    color_player_name,
+    commands::{self, Ending, GameCommand},
+    error_handler, find_non_judge_players, hand_of, handle_preparation_input,
+    input::{CountBase, InputManager, PreparationInput},
+    network::{Connection, GuestMessage, HostMessage},
+    print_hand, print_preparation_help, ranking_lines, say, sleep, Config,
+};
+
+/// Shared with the thread that lets players in.
+struct Lobby {
+    builder: GenericDriverBuilder,
+    guests: BTreeMap<String, Guest>,
+    /// The host's language.
+    locale: Locale,
+    is_playing: bool,
+}
+
+struct Guest {
+    connection: Connection,
+    locale: Locale,
+}
+
+/// Why a game stopped before its end.
+enum Interruption {
+    /// A guest hung up, so the game cannot go on.
+    Disconnected(String),
+    /// The host typed /quit.
+    EndedBy(String),
+    /// A guest typed /quit, and leaves the lobby too.
+    Left(String),
+    /// The host typed /kick to remove this one, who must leave the lobby too.
+    Kicked(String),
+}
+
+impl Interruption {
+    /// `player` typed the command that ended the game.
+    fn from_ending(ending: Ending, player: &str) -> Self {
+        match ending {
+            Ending::Quit => Interruption::EndedBy(player.to_owned()),
+            Ending::Kick(kicked) => Interruption::Kicked(kicked),
+        }
+    }
+}
+
+pub(crate) fn host(
+    address: &str,
+    player: &str,
+    mut builder: GenericDriverBuilder,
+    mut config: Config,
+) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    let address = listener.local_addr()?;
+
+    if let Err(err) = builder.add_player(player) {
+        error_handler(err.localize(config.locale));
+        return Ok(());
+    }
+    say(
+        config.locale,
+        Message::new("cli.hosting").arg("address", address.to_string()),
+    );
+
+    let lobby = Arc::new(Mutex::new(Lobby {
+        builder,
+        guests: BTreeMap::new(),
+        locale: config.locale,
+        is_playing: false,
+    }));
+    {
+        let lobby = lobby.clone();
+        thread::spawn(move || accept_guests(listener, lobby));
+    }
+
+    loop {
+        print_preparation_help(config.locale);
+
+        let driver = loop {
+            let input = InputManager::preparation_input(config.locale);
+            let mut lobby = lobby.lock().unwrap();
+
+            if let PreparationInput::RemovePlayer(name) = &input {
+                if let Some(mut guest) = lobby.guests.remove(name) {
+                    let _ = guest.connection.send(&HostMessage::Bye);
+                }
+            }
+            let driver = match input {
+                PreparationInput::AddPlayer(_) => {
+                    error_handler(
+                        Message::new("cli.joinOverNetwork")
+                            .arg("address", address.to_string())
+                            .localize(config.locale),
+                    );
+                    None
+                }
+                input => handle_preparation_input(input, &mut lobby.builder, &mut config),
+            };
+            lobby.locale = config.locale;
+
+            if let Some(driver) = driver {
+                lobby.is_playing = true;
+                break driver;
+            }
+        };
+
+        let mut table = Table {
+            host: player.to_owned(),
+            locale: config.locale,
+            count_base: config.count_base,
+            delays: config.delays,
+            guests: std::mem::take(&mut lobby.lock().unwrap().guests),
+        };
+        let result = table.play(driver);
+
+        let mut lobby = lobby.lock().unwrap();
+        match result {
+            Ok(()) => {}
+            // A guest who hung up or typed /quit; the game cannot go on without them.
+            Err(Interruption::Disconnected(player)) | Err(Interruption::Left(player)) => {
+                if let Some(mut guest) = table.guests.remove(&player) {
+                    let _ = guest.connection.send(&HostMessage::Bye);
+                }
+                let _ = lobby.builder.remove_player(&player);
+                let _ = table.print_to_all(|locale| {
+                    Message::new("cli.playerDisconnected")
+                        .arg("player", color_player_name(&player).to_string())
+                        .localize(locale)
+                });
+            }
+            Err(Interruption::EndedBy(player)) => {
+                let _ = table.print_to_all(|locale| {
+                    Message::new("cli.gameEndedBy")
+                        .arg("player", color_player_name(&player).to_string())
+                        .localize(locale)
+                });
+            }
+            // The host cannot leave their own game.
+            Err(Interruption::Kicked(player)) if player == table.host => {
+                let _ = table.print_to_all(|locale| Message::new("cli.gameEnded").localize(locale));
+            }
+            Err(Interruption::Kicked(player)) => {
+                let _ = table.print_to_all(|locale| {
+                    Message::new("cli.playerLeft")
+                        .arg("player", color_player_name(&player).to_string())
+                        .localize(locale)
+                });
+                if let Some(mut guest) = table.guests.remove(&player) {
+                    let _ = guest.connection.send(&HostMessage::Bye);
+                }
+                let _ = lobby.builder.remove_player(&player);
+            }
+        }
+        let _ = table.print_to_guests(|locale| Message::new("cli.waitingForHost").localize(locale));
+        lobby.guests = table.guests;
+        lobby.is_playing = false;
+    }
+}
+
+/// Let players in on their own threads, so one that never says who they are blocks no one.
+fn accept_guests(listener: TcpListener, lobby: Arc<Mutex<Lobby>>) {
+    for stream in listener.incoming().flatten() {
+        let lobby = lobby.clone();
+        thread::spawn(move || welcome(stream, &lobby));
+    }
+}
+
+fn welcome(stream: TcpStream, lobby: &Mutex<Lobby>) -> io::Result<()> {
+    let mut connection = Connection::new(stream)?;
+    let (player, locale) = match connection.receive()? {
+        GuestMessage::Join { player, locale } => {
+            (player, Locale::from_code(&locale).unwrap_or_default())
+        }
+        _ => return Ok(()),
+    };
+
+    let mut lobby = lobby.lock().unwrap();
+    let joined = if lobby.is_playing {
+        Err(Message::new("cli.gameInProgress").localize(locale))
+    } else {
+        lobby
+            .builder
+            .add_player(&player)
+            .map_err(|err| err.localize(locale))
+    };
+
+    match joined {
+        Ok(()) => {
+            say(
+                lobby.locale,
+                Message::new("cli.playerJoined")
+                    .arg("player", color_player_name(&player).to_string()),
+            );
+            connection.send(&HostMessage::Say {
+                text: Message::new("cli.waitingForHost").localize(locale),
+            })?;
+            lobby.guests.insert(player, Guest { connection, locale });
+        }
+        Err(text) => {
+            connection.send(&HostMessage::Error { text })?;
+            connection.send(&HostMessage::Bye)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Everyone in a game: the host in this terminal and the guests over the network.
+struct Table {
+    host: String,
+    locale: Locale,
+    count_base: CountBase,
+    /// Pause while the answers are revealed.
+    delays: bool,
+    guests: BTreeMap<String, Guest>,
+}
+
+impl Table {
+    fn play(&mut self, mut driver: GenericDriver) -> Result<(), Interruption> {
+        let result = self.play_rounds(&mut driver);
+        driver.end_game();
+
+        result
+    }
+
+    fn play_rounds(&mut self, driver: &mut GenericDriver) -> Result<(), Interruption> {
+        let ordered_players = driver.ordered_players();
+        let first_index = self.count_base.first_index();
+
+        loop {
+            let round_information = driver.start_round();
+            let judge = &round_information.judge;
+            let question = &round_information.question;
+            let mut non_judge_players = find_non_judge_players(&ordered_players, judge);
+
+            self.print_to_all(|locale| {
+                Message::new("cli.judgeForRound")
+                    .arg("judge", color_player_name(judge).to_string())
+                    .localize(locale)
+            })?;
+
+            // The guests get their hands at once; the host answers first, while they think.
+            non_judge_players.sort_by_key(|&player| *player != self.host);
+            for &player in non_judge_players.iter() {
+                self.ask_for_answers(driver, player, question)?;
+            }
+
+            let mut submitted_answers = vec![];
+            for player in non_judge_players {
+                loop {
+                    let answers = self.answers_from(driver, player, question)?;
+                    match driver.submit_answers(player, answers) {
+                        Ok(all_submitted) => {
+                            submitted_answers = all_submitted.unwrap_or_default();
+                            self.print_to_guest(player, |locale| {
+                                Message::new("cli.waitingForOthers").localize(locale)
+                            })?;
+                            break;
+                        }
+                        Err(err) if driver.is_game_over() => {
+                            self.error_to(player, |locale| err.localize(locale))?;
+                            return self.print_to_all(|locale| {
+                                Message::new("cli.gameEnded").localize(locale)
+                            });
+                        }
+                        Err(err) => {
+                            self.error_to(player, |locale| err.localize(locale))?;
+                            self.ask_for_answers(driver, player, question)?;
+                        }
+                    }
+                }
+            }
+
+            // Display the submitted answers to everyone
+            self.print_to_all(|locale| Message::new("cli.answersCollected").localize(locale))?;
+            for (i, (_, answer)) in submitted_answers.iter().enumerate() {
+                if self.delays {
+                    sleep(1);
+                }
+                self.print_to_all(|_| format!("{} - {}", i + first_index, answer))?;
+            }
+            self.print_to_all(|locale| {
+                Message::new("cli.chooseFavorite")
+                    .arg("judge", color_player_name(judge).to_string())
+                    .localize(locale)
+            })?;
+
+            let ranking = loop {
+                let chosen_index = self.favorite_from(driver, judge)?;
+
+                let chosen_player = if let Some((player, _)) = submitted_answers.get(chosen_index) {
+                    player
+                } else {
+                    self.error_to(judge, |locale| {
+                        Message::new("cli.invalidInput").localize(locale)
+                    })?;
+                    continue;
+                };
+
+                match driver.end_round(chosen_player) {
+                    Ok(round_end_info) => break round_end_info,
+                    Err(err) => self.error_to(judge, |locale| err.localize(locale))?,
+                }
+            };
+            let highest = ranking[0].clone();
+
+            for line in ranking_lines(&ranking) {
+                self.print_to_all(|_| line.clone())?;
+            }
+
+            if highest.1 >= driver.config().win_target as i32 {
+                return self.print_to_all(|locale| {
+                    Message::new("cli.winner")
+                        .arg("player", color_player_name(&highest.0).to_string())
+                        .localize(locale)
+                });
+            }
+
+            if driver.is_game_over() {
+                return self.print_to_all(|locale| {
+                    Message::new("cli.deckRanOut")
+                        .arg("player", color_player_name(&highest.0).to_string())
+                        .arg("points", highest.1)
+                        .localize(locale)
+                });
+            }
+        }
+    }
+
+    /// Send a guest their hand as it is now. The host sees theirs when asked in
+    /// `answers_from()`.
+    fn ask_for_answers(
+        &mut self,
+        driver: &GenericDriver,
+        player: &str,
+        question: &str,
+    ) -> Result<(), Interruption> {
+        let first_index = self.count_base.first_index();
+        let hand = hand_of(driver, player);
+
+        self.send_to_guest(player, |_| HostMessage::Submit {
+            hand: hand.clone(),
+            question: question.to_owned(),
+            first_index,
+        })
+    }
+
+    fn answers_from(
+        &mut self,
+        driver: &mut GenericDriver,
+        player: &str,
+        question: &str,
+    ) -> Result<Vec<usize>, Interruption> {
+        let (locale, count_base) = (self.locale, self.count_base);
+
+        if player == self.host {
+            let hand = hand_of(driver, player);
+            print_hand(locale, count_base, player, &hand, question);
+            return commands::read_answer(driver, player, locale, count_base, || {
+                InputManager::submit_answers(count_base, locale)
+            })
+            .map_err(|ending| Interruption::from_ending(ending, player));
+        }
+
+        loop {
+            match self.receive_from(player)? {
+                GuestMessage::Answers { indices } => return Ok(indices),
+                GuestMessage::Command { command } => self.run_command(driver, player, command)?,
+                _ => return Err(Interruption::Disconnected(player.to_owned())),
+            }
+        }
+    }
+
+    fn favorite_from(
+        &mut self,
+        driver: &mut GenericDriver,
+        judge: &str,
+    ) -> Result<usize, Interruption> {
+        let (locale, count_base) = (self.locale, self.count_base);
+
+        if judge == self.host {
+            return commands::read_answer(driver, judge, locale, count_base, || {
+                InputManager::choose_favorite(count_base, locale)
+            })
+            .map_err(|ending| Interruption::from_ending(ending, judge));
+        }
+
+        let first_index = count_base.first_index();
+        self.send_to_guest(judge, |_| HostMessage::Choose { first_index })?;
+        loop {
+            match self.receive_from(judge)? {
+                GuestMessage::Favorite { index } => return Ok(index),
+                GuestMessage::Command { command } => self.run_command(driver, judge, command)?,
+                _ => return Err(Interruption::Disconnected(judge.to_owned())),
+            }
+        }
+    }
+
+    /// Run a command a guest typed and send them the reply. Only the host ends the game for
+    /// everyone: a guest cannot /kick, and leaves with /quit.
+    fn run_command(
+        &mut self,
+        driver: &mut GenericDriver,
+        player: &str,
+        command: GameCommand,
+    ) -> Result<(), Interruption> {
+        let locale = self
+            .guests
+            .get(player)
+            .map_or(self.locale, |guest| guest.locale);
+
+        match command {
+            GameCommand::Kick(_) => {
+                return self.send_to_guest(player, |locale| HostMessage::Reply {
+                    lines: vec![Message::new("cli.hostOnly").localize(locale)],
+                })
+            }
+            GameCommand::Quit => return Err(Interruption::Left(player.to_owned())),
+            _ => {}
+        }
+
+        match commands::run(command, driver, player, locale, self.count_base) {
+            Ok(lines) => self.send_to_guest(player, |_| HostMessage::Reply {
+                lines: lines.clone(),
+            }),
+            Err(ending) => Err(Interruption::from_ending(ending, player)),
+        }
+    }
+
+    fn print_to_all(&mut self, text: impl Fn(Locale) -> String) -> Result<(), Interruption> {
+        println!("{}", text(self.locale));
+        self.print_to_guests(text)
+    }
+
+    fn print_to_guests(&mut self, text: impl Fn(Locale) -> String) -> Result<(), Interruption> {
+        let guests: Vec<String> = self.guests.keys().cloned().collect();
+        for guest in guests {
+            self.print_to_guest(&guest, &text)?;
+        }
+
+        Ok(())
+    }
+
+    fn print_to_guest(
+        &mut self,
+        player: &str,
+        text: impl Fn(Locale) -> String,
+    ) -> Result<(), Interruption> {
+        self.send_to_guest(player, |locale| HostMessage::Say { text: text(locale) })
+    }
+
+    fn error_to(
+        &mut self,
+        player: &str,
+        text: impl Fn(Locale) -> String,
+    ) -> Result<(), Interruption> {
+        if player == self.host {
+            error_handler(text(self.locale));
+            return Ok(());
+        }
+
+        self.send_to_guest(player, |locale| HostMessage::Error { text: text(locale) })
+    }
+
+    /// Does nothing for the host.
+    fn send_to_guest(
+        &mut self,
+        player: &str,
+        message: impl Fn(Locale) -> HostMessage,
+    ) -> Result<(), Interruption> {
+        match self.guests.get_mut(player) {
+            Some(guest) => guest
+                .connection
+                .send(&message(guest.locale))
+                .map_err(|_| Interruption::Disconnected(player.to_owned())),
+            None => Ok(()),
+        }
+    }
+
+    fn receive_from(&mut self, player: &str) -> Result<GuestMessage, Interruption> {
+        self.guests
+            .get_mut(player)
+            .and_then(|guest| guest.connection.receive().ok())
+            .ok_or_else(|| Interruption::Disconnected(player.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, net::SocketAddr, thread::JoinHandle};
+
+    use super::*;
+
+    /// What a guest does when first asked for anything.
+    enum Act {
+        Play,
+        HangUp,
+        /// Type the command, then play.
+        Type(GameCommand),
+    }
+
+    /// Join as `player` and play the first card and the first answer, after acting out `act`.
+    /// Return what the host said.
+    fn guest(address: SocketAddr, player: &str, mut act: Act) -> JoinHandle<Vec<String>> {
+        let mut connection = Connection::connect(address).unwrap();
+        connection
+            .send(&GuestMessage::Join {
+                player: player.to_owned(),
+                locale: "en".to_owned(),
+            })
+            .unwrap();
+
+        thread::spawn(move || {
+            let mut said = vec![];
+            while let Ok(message) = connection.receive() {
+                let reply = match message {
+                    HostMessage::Say { text } | HostMessage::Error { text } => {
+                        said.push(text);
+                        continue;
+                    }
+                    HostMessage::Reply { lines } => {
+                        said.extend(lines);
+                        continue;
+                    }
+                    HostMessage::Bye => break,
+                    _ if matches!(act, Act::HangUp) => break,
+                    HostMessage::Submit { .. } => GuestMessage::Answers { indices: vec![0] },
+                    HostMessage::Choose { .. } => GuestMessage::Favorite { index: 0 },
+                };
+                if let Act::Type(command) = std::mem::replace(&mut act, Act::Play) {
+                    connection.send(&GuestMessage::Command { command }).unwrap();
+                }
+                connection.send(&reply).unwrap();
+            }
+
+            said
+        })
+    }
+
+    /// Ann hosts; Bob and Cat join over 127.0.0.1, Cat acting out `cat`.
+    fn set_up_table(cat: Act) -> (Table, GenericDriver, Vec<JoinHandle<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let mut builder = GenericDriverBuilder::new();
+        builder.add_player("Ann").unwrap();
+        builder.add_new_questions(["Why _?"]);
+        builder.add_new_answers((0..40).map(|answer| answer.to_string()));
+        builder.set_win_target(1).unwrap();
+        builder.set_seed(Some(0));
+        let lobby = Mutex::new(Lobby {
+            builder,
+            guests: BTreeMap::new(),
+            locale: Locale::English,
+            is_playing: false,
+        });
+
+        let guests = [("Bob", Act::Play), ("Cat", cat)]
+            .into_iter()
+            .map(|(player, act)| {
+                let guest = guest(address, player, act);
+                welcome(listener.accept().unwrap().0, &lobby).unwrap();
+                guest
+            })
+            .collect();
+
+        let lobby = lobby.into_inner().unwrap();
+        let table = Table {
+            host: "Ann".to_owned(),
+            locale: Locale::English,
+            count_base: CountBase::OneBased,
+            delays: false,
+            guests: lobby.guests,
+        };
+
+        (table, lobby.builder.build().unwrap(), guests)
+    }
+
+    #[test]
+    fn play_with_guests_until_one_leaves() {
+        // Whether Ann answers or judges, the first card is fine. Each game asks her at most once.
+        InputManager::use_script(Cursor::new("1\n1\n1\n1\n"));
+
+        let (mut table, driver, guests) = set_up_table(Act::Play);
+        assert!(table.play(driver).is_ok());
+        drop(table);
+        for guest in guests {
+            let said = guest.join().unwrap();
+            assert!(said.iter().any(|text| text.contains("You Have Won")));
+        }
+
+        let (mut table, driver, _) = set_up_table(Act::HangUp);
+        assert!(matches!(
+            table.play(driver),
+            Err(Interruption::Disconnected(player)) if player == "Cat"
+        ));
+
+        // Only the host ends the game for everyone.
+        let (mut table, driver, guests) =
+            set_up_table(Act::Type(GameCommand::Kick("Ann".to_owned())));
+        assert!(table.play(driver).is_ok());
+        drop(table);
+        let said = guests.into_iter().last().unwrap().join().unwrap();
+        assert!(said.contains(&"Only the host can kick players.".to_owned()));
+        assert!(said.iter().any(|text| text.contains("You Have Won")));
+
+        let (mut table, driver, _) = set_up_table(Act::Type(GameCommand::Quit));
+        assert!(matches!(
+            table.play(driver),
+            Err(Interruption::Left(player)) if player == "Cat"
+        ));
+    }
+}
@@ -1,39 +1,106 @@
-use std::{io::Write, str::FromStr};
+use std::{
+    io::{self, BufRead, Write},
+    process,
+    str::FromStr,
+    sync::Mutex,
+};
 
-use crate::error_handler;
+use bot_against_humanity_core::i18n::{Locale, Message};
+
+use crate::{assets::CardSource, commands::GameCommand, error_handler};
 
 pub(crate) struct InputManager;
 
+/// Read instead of stdin in the scripted mode.
+static SCRIPT: Mutex<Option<Box<dyn BufRead + Send>>> = Mutex::new(None);
+
 pub(crate) enum PreparationInput {
     Start,
+    Validate,
     AddPlayer(String),
     RemovePlayer(String),
     SetHandSize(usize),  // Must be >= 1
     SetWinTarget(usize), // Must be >= 1
     SetCountBaseOne,
     SetCountBaseZero,
+    SetPrivacy(bool),
+    SetLocale(String), // Language code
+    UsePreset(String),
+    LoadConfig(String), // Path to a JSON file
+    SaveConfig(String), // Path to a JSON file
+    LoadCards(CardSource),
+    ClearCards,
+}
+
+/// Where a line is read. The input may only run out at the preparation prompt.
+#[derive(Clone, Copy)]
+enum Prompt {
+    Preparation,
+    Game,
 }
 
-#[derive(Clone, Copy, Debug)]
+/// What a player types at a prompt of the game.
+pub(crate) enum GameInput<T> {
+    Answer(T),
+    Command(GameCommand),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum CountBase {
     ZeroBased,
     OneBased,
 }
 
+impl CountBase {
+    pub fn first_index(self) -> usize {
+        match self {
+            CountBase::ZeroBased => 0,
+            CountBase::OneBased => 1,
+        }
+    }
+
+    pub fn from_first_index(first_index: usize) -> Self {
+        if first_index == 0 {
+            CountBase::ZeroBased
+        } else {
+            CountBase::OneBased
+        }
+    }
+}
+
 type Error = String;
 
 impl InputManager {
     /// Valid input:
     /// - /start
+    /// - /validate
     /// - /add `Player Name`
     /// - /remove `Player Name`
     /// - /handsize `usize >= 1`
     /// - /wintarget `usize >= 1`
     /// - /countbase `0 or 1`
-    pub fn preparation_input() -> PreparationInput {
-        let tokens = Self::read_line_as_tokens_until_no_error(|tokens: &Vec<String>| {
-            tokens[0] == "/start"
-                || ((tokens[0] == "/add" || tokens[0] == "/remove") && tokens.len() >= 2)
+    /// - /privacy `on or off`
+    /// - /preset `Preset Name`
+    /// - /loadconfig `path`
+    /// - /saveconfig `path`
+    /// - /questions `path`
+    /// - /answers `path`
+    /// - /pack `path`
+    /// - /clearcards
+    /// - /locale `code`
+    pub fn preparation_input(locale: Locale) -> PreparationInput {
+        let tokens = Self::read_line_as_tokens_until_no_error(locale, |tokens: &Vec<String>| {
+            ((tokens[0] == "/start" || tokens[0] == "/validate" || tokens[0] == "/clearcards")
+                && tokens.len() == 1)
+                || ((tokens[0] == "/add"
+                    || tokens[0] == "/remove"
+                    || tokens[0] == "/preset"
+                    || tokens[0] == "/loadconfig"
+                    || tokens[0] == "/saveconfig"
+                    || tokens[0] == "/questions"
+                    || tokens[0] == "/answers"
+                    || tokens[0] == "/pack")
+                    && tokens.len() >= 2)
                 || ((tokens[0] == "/handsize" || tokens[0] == "/wintarget")
                     && tokens.len() == 2
                     && if let Ok(handsize) = tokens[1].parse::<usize>() {
@@ -41,51 +108,69 @@ impl InputManager {
                     } else {
                         false
                     })
+                || (tokens[0] == "/locale" && tokens.len() == 2)
                 || (tokens[0] == "/countbase"
                     && tokens.len() == 2
                     && (tokens[1] == "0" || tokens[1] == "1"))
+                || (tokens[0] == "/privacy"
+                    && tokens.len() == 2
+                    && (tokens[1] == "on" || tokens[1] == "off"))
         });
 
-        if tokens.len() == 1 {
-            PreparationInput::Start
-        } else {
-            match tokens[0].as_str() {
-                "/add" => PreparationInput::AddPlayer(tokens[1..].join(" ")),
-                "/remove" => PreparationInput::RemovePlayer(tokens[1..].join("")),
-                "/handsize" => PreparationInput::SetHandSize(tokens[1].parse::<usize>().unwrap()),
-                "/wintarget" => PreparationInput::SetWinTarget(tokens[1].parse::<usize>().unwrap()),
-                "/countbase" => match tokens[1].as_str() {
-                    "0" => PreparationInput::SetCountBaseZero,
-                    "1" => PreparationInput::SetCountBaseOne,
-                    _ => unreachable!(),
-                },
+        match tokens[0].as_str() {
+            "/start" => PreparationInput::Start,
+            "/validate" => PreparationInput::Validate,
+            "/add" => PreparationInput::AddPlayer(tokens[1..].join(" ")),
+            "/remove" => PreparationInput::RemovePlayer(tokens[1..].join("")),
+            "/handsize" => PreparationInput::SetHandSize(tokens[1].parse::<usize>().unwrap()),
+            "/wintarget" => PreparationInput::SetWinTarget(tokens[1].parse::<usize>().unwrap()),
+            "/countbase" => match tokens[1].as_str() {
+                "0" => PreparationInput::SetCountBaseZero,
+                "1" => PreparationInput::SetCountBaseOne,
                 _ => unreachable!(),
+            },
+            "/privacy" => PreparationInput::SetPrivacy(tokens[1] == "on"),
+            "/locale" => PreparationInput::SetLocale(tokens[1].clone()),
+            "/preset" => PreparationInput::UsePreset(tokens[1..].join(" ")),
+            "/loadconfig" => PreparationInput::LoadConfig(tokens[1..].join(" ")),
+            "/saveconfig" => PreparationInput::SaveConfig(tokens[1..].join(" ")),
+            "/questions" => {
+                PreparationInput::LoadCards(CardSource::Questions(tokens[1..].join(" ").into()))
+            }
+            "/answers" => {
+                PreparationInput::LoadCards(CardSource::Answers(tokens[1..].join(" ").into()))
             }
+            "/pack" => PreparationInput::LoadCards(CardSource::Pack(tokens[1..].join(" ").into())),
+            "/clearcards" => PreparationInput::ClearCards,
+            _ => unreachable!(),
         }
     }
 
-    /// Return a vector of indices representing the submitted answers.
+    /// Return a vector of indices representing the submitted answers, or a command.
     /// `count_base` is used to validate the indices; the offset is applied before returning if necessary.
-    pub fn submit_answers(count_base: CountBase) -> Vec<usize> {
-        let indices = Self::read_line_as_tokens_until_no_error(|indices: &Vec<usize>| {
+    pub fn submit_answers(count_base: CountBase, locale: Locale) -> GameInput<Vec<usize>> {
+        let indices = match Self::read_game_input_until_no_error(locale, |indices: &Vec<usize>| {
             if let CountBase::OneBased = count_base {
                 !indices.iter().any(|&ind| ind == 0)
             } else {
                 true
             }
-        });
+        }) {
+            GameInput::Answer(indices) => indices,
+            GameInput::Command(command) => return GameInput::Command(command),
+        };
 
-        if let CountBase::OneBased = count_base {
+        GameInput::Answer(if let CountBase::OneBased = count_base {
             indices.into_iter().map(|ind| ind - 1).collect()
         } else {
             indices
-        }
+        })
     }
 
-    /// Return an index representing the chosen player.
+    /// Return an index representing the chosen player, or a command.
     /// `count_base` is used to validate the indices; the offset is applied before returning if necessary.
-    pub fn choose_favorite(count_base: CountBase) -> usize {
-        let index = Self::read_line_as_tokens_until_no_error(|indices: &Vec<usize>| {
+    pub fn choose_favorite(count_base: CountBase, locale: Locale) -> GameInput<usize> {
+        let index = match Self::read_game_input_until_no_error(locale, |indices: &Vec<usize>| {
             indices.len() == 1 && {
                 if let CountBase::OneBased = count_base {
                     indices[0] != 0
@@ -93,27 +178,77 @@ impl InputManager {
                     true
                 }
             }
-        })[0];
+        }) {
+            GameInput::Answer(indices) => indices[0],
+            GameInput::Command(command) => return GameInput::Command(command),
+        };
 
-        if let CountBase::OneBased = count_base {
+        GameInput::Answer(if let CountBase::OneBased = count_base {
             index - 1
         } else {
             index
+        })
+    }
+
+    /// Read every line from `script` instead of stdin, and echo it so the transcript shows what
+    /// was typed. Lines starting with `#` are comments.
+    pub fn use_script(script: impl BufRead + Send + 'static) {
+        *SCRIPT.lock().unwrap() = Some(Box::new(script));
+    }
+
+    /// Wait until Enter is pressed, whatever was typed before it.
+    pub fn wait_for_enter(locale: Locale) {
+        Self::next_line(Prompt::Game, locale).ok();
+    }
+
+    // Like `read_line_as_tokens_until_no_error()`, but a line starting with "/" is a command.
+    fn read_game_input_until_no_error<T>(
+        locale: Locale,
+        predicate: impl Fn(&Vec<T>) -> bool,
+    ) -> GameInput<Vec<T>>
+    where
+        T: FromStr,
+    {
+        loop {
+            let line = match Self::read_line(Prompt::Game, locale) {
+                Ok(line) => line,
+                Err(err) => {
+                    error_handler(err);
+                    continue;
+                }
+            };
+
+            if line.trim_start().starts_with('/') {
+                match GameCommand::parse(&line) {
+                    Some(command) => return GameInput::Command(command),
+                    None => error_handler(Message::new("cli.unknownCommand").localize(locale)),
+                }
+                continue;
+            }
+
+            match Self::parse_tokens::<T>(&line, locale) {
+                Ok(tokens) if predicate(&tokens) => return GameInput::Answer(tokens),
+                Ok(_) => error_handler(Message::new("cli.invalidInput").localize(locale)),
+                Err(err) => error_handler(err),
+            }
         }
     }
 
     // The input must also pass the predicate in order to be returned.
-    fn read_line_as_tokens_until_no_error<T>(predicate: impl Fn(&Vec<T>) -> bool) -> Vec<T>
+    fn read_line_as_tokens_until_no_error<T>(
+        locale: Locale,
+        predicate: impl Fn(&Vec<T>) -> bool,
+    ) -> Vec<T>
     where
         T: FromStr,
     {
         loop {
-            match Self::read_line_as_tokens::<T>() {
+            match Self::read_line_as_tokens::<T>(locale) {
                 Ok(tokens) => {
                     if predicate(&tokens) {
                         return tokens;
                     }
-                    error_handler("Invalid input");
+                    error_handler(Message::new("cli.invalidInput").localize(locale));
                 }
                 Err(err) => {
                     error_handler(err);
@@ -122,12 +257,19 @@ impl InputManager {
         }
     }
 
-    fn read_line_as_tokens<T>() -> Result<Vec<T>, Error>
+    fn read_line_as_tokens<T>(locale: Locale) -> Result<Vec<T>, Error>
     where
         T: FromStr,
     {
-        let line = Self::read_line()?;
+        let line = Self::read_line(Prompt::Preparation, locale)?;
+
+        Self::parse_tokens(&line, locale)
+    }
 
+    fn parse_tokens<T>(line: &str, locale: Locale) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+    {
         let result = line
             .split_whitespace()
             .into_iter()
@@ -136,29 +278,59 @@ impl InputManager {
 
         if let Ok(tokens) = result {
             if tokens.is_empty() {
-                return Err("Empty input".to_owned());
+                return Err(Message::new("cli.emptyInput").localize(locale));
             }
 
             Ok(tokens)
         } else {
-            Err("Invalid input".to_owned())
+            Err(Message::new("cli.invalidInput").localize(locale))
         }
     }
 
-    fn read_line() -> Result<String, Error> {
-        let mut input = String::new();
-
+    fn read_line(prompt: Prompt, locale: Locale) -> Result<String, Error> {
         print!("\n> ");
         std::io::stdout().flush().unwrap();
 
-        std::io::stdin()
-            .read_line(&mut input)
-            .map_err(|e| e.to_string())?;
+        let input = Self::next_line(prompt, locale).map_err(|e| e.to_string())?;
 
         if input.is_empty() {
-            Err("Empty input".to_owned())
+            Err(Message::new("cli.emptyInput").localize(locale))
         } else {
             Ok(input)
         }
     }
+
+    /// The next line from the script or stdin. Exit at the end of the input, as nothing more
+    /// can be typed: successfully at the preparation prompt, with an error in the middle of a
+    /// game, since it could not be finished.
+    fn next_line(prompt: Prompt, locale: Locale) -> io::Result<String> {
+        let mut input = String::new();
+
+        match SCRIPT.lock().unwrap().as_mut() {
+            Some(script) => loop {
+                if script.read_line(&mut input)? == 0 {
+                    break;
+                }
+                if !input.trim_start().starts_with('#') {
+                    println!("{}", input.trim_end());
+                    break;
+                }
+                input.clear();
+            },
+            None => {
+                io::stdin().read_line(&mut input)?;
+            }
+        }
+
+        if input.is_empty() {
+            println!();
+            if let Prompt::Game = prompt {
+                error_handler(Message::new("cli.inputEnded").localize(locale));
+                process::exit(1);
+            }
+            process::exit(0);
+        }
+
+        Ok(input)
+    }
 }
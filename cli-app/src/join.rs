@@ -0,0 +1,103 @@
+//! `join`: play a game hosted elsewhere, seeing only your own hand.
+
+use std::io;
+
+use bot_against_humanity_core::i18n::{Locale, Message};
+
+use crate::{
+    commands::GameCommand,
+    error_handler,
+    input::{CountBase, GameInput, InputManager},
+    network::{Connection, GuestMessage, HostMessage},
+    print_hand, say,
+};
+
+pub(crate) fn join(address: &str, player: &str, locale: Locale) -> io::Result<()> {
+    let mut connection = Connection::connect(address)?;
+    connection.send(&GuestMessage::Join {
+        player: player.to_owned(),
+        locale: locale.code().to_owned(),
+    })?;
+
+    // A message that arrived while waiting for the reply to a command.
+    let mut pending = None;
+
+    loop {
+        let message = match pending.take() {
+            Some(message) => message,
+            None => match connection.receive() {
+                Ok(message) => message,
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                    say(locale, Message::new("cli.hostLeft"));
+                    return Ok(());
+                }
+                Err(err) => return Err(err),
+            },
+        };
+
+        match message {
+            HostMessage::Say { text } => println!("{}", text),
+            HostMessage::Error { text } => error_handler(text),
+            HostMessage::Submit {
+                hand,
+                question,
+                first_index,
+            } => {
+                let count_base = CountBase::from_first_index(first_index);
+                print_hand(locale, count_base, player, &hand, &question);
+
+                loop {
+                    match InputManager::submit_answers(count_base, locale) {
+                        GameInput::Answer(indices) => {
+                            connection.send(&GuestMessage::Answers { indices })?;
+                            break;
+                        }
+                        GameInput::Command(command) => {
+                            pending = run_command(&mut connection, command)?;
+                            if pending.is_some() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            HostMessage::Choose { first_index } => {
+                let count_base = CountBase::from_first_index(first_index);
+
+                loop {
+                    match InputManager::choose_favorite(count_base, locale) {
+                        GameInput::Answer(index) => {
+                            connection.send(&GuestMessage::Favorite { index })?;
+                            break;
+                        }
+                        GameInput::Command(command) => {
+                            pending = run_command(&mut connection, command)?;
+                            if pending.is_some() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            HostMessage::Reply { lines } => lines.iter().for_each(|line| println!("{}", line)),
+            HostMessage::Bye => return Ok(()),
+        }
+    }
+}
+
+/// Send a command to the host and print its reply. Return whatever came instead of a
+/// reply, such as the end of the game.
+fn run_command(
+    connection: &mut Connection,
+    command: GameCommand,
+) -> io::Result<Option<HostMessage>> {
+    connection.send(&GuestMessage::Command { command })?;
+
+    match connection.receive()? {
+        HostMessage::Reply { lines } => {
+            lines.iter().for_each(|line| println!("{}", line));
+            Ok(None)
+        }
+        message => Ok(Some(message)),
+    }
+}
@@ -1,221 +1,679 @@
+mod args;
 mod assets;
+mod commands;
+mod host;
 mod input;
+mod join;
+mod network;
+mod terminal;
+mod tui;
 
-use std::{io::Write, thread, time::Duration};
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufReader, Write},
+    thread,
+    time::Duration,
+};
 
-use assets::{answers, questions};
-use bot_against_humanity_core::drivers::{generic::GenericDriverBuilder, GameCoreDriver};
+use args::Args;
+use assets::{answers, questions, CardSource};
+use bot_against_humanity_core::{
+    config::{GameConfig, PRESET_NAMES},
+    drivers::{
+        generic::{GenericDriver, GenericDriverBuilder},
+        GameCoreDriver,
+    },
+    game_state::{Severity, ValidationReport},
+    i18n::{Locale, Message},
+};
 use colored::{ColoredString, Colorize};
 use input::CountBase;
 
-use crate::input::{InputManager, PreparationInput};
+use crate::{
+    commands::Ending,
+    input::{InputManager, PreparationInput},
+};
 
 type Error = String;
 
+/// Lower than the Classic preset's, for a game around one terminal.
+const DEFAULT_WIN_TARGET: usize = 3;
+
 pub(crate) fn error_handler(err: impl Into<Error>) {
     eprintln!("{}", err.into());
 }
 
-struct Config {
-    win_target: usize,
+/// Print a message from the catalogue in the chosen language.
+pub(crate) fn say(locale: Locale, message: Message) {
+    println!("{}", message.localize(locale));
+}
+
+/// Settings of the command line app itself. Game settings live in the builder's `GameConfig`.
+pub(crate) struct Config {
     count_base: CountBase,
+    locale: Locale,
+    /// Play the game in the full-screen mode instead of line by line.
+    full_screen: bool,
+    /// In the hot seat, hide each hand until its player has the device.
+    privacy: bool,
+    /// Pause while the answers are revealed. Off in the scripted mode.
+    delays: bool,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            win_target: 3,
             count_base: CountBase::OneBased,
+            locale: locale_from_env(),
+            full_screen: false,
+            privacy: false,
+            delays: true,
         }
     }
 }
 
+/// The language of `LC_ALL`, `LC_MESSAGES` or `LANG`, in this order, or English.
+fn locale_from_env() -> Locale {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| Locale::from_code(&value))
+        .unwrap_or_default()
+}
+
 fn main() {
     let mut builder = GenericDriverBuilder::new();
+    builder.set_win_target(DEFAULT_WIN_TARGET).unwrap();
+    let mut config = Config::default();
 
-    builder.add_new_questions(questions());
-    builder.add_new_answers(answers());
+    let mut args = match Args::parse(env::args().skip(1)) {
+        Ok(args) if !args.help => args,
+        Ok(_) => return print_usage(config.locale),
+        Err(message) => {
+            error_handler(message.localize(config.locale));
+            return print_usage(config.locale);
+        }
+    };
+    if args.words.first().map(String::as_str) == Some("script") {
+        // The same transcript wherever it is played.
+        config.locale = Locale::default();
+        config.delays = false;
+        args.seed.get_or_insert(0);
+        colored::control::set_override(false);
+    }
+    let locale = config.locale;
+    if !apply_args(&args, &mut builder, &mut config) {
+        return;
+    }
+    let words = &args.words;
 
-    let mut config = Config::default();
+    let result = match words.first().map(String::as_str) {
+        None => {
+            play_hot_seat(builder, config);
+            Ok(())
+        }
+        Some("tui") if words.len() == 1 => {
+            config.full_screen = terminal::is_supported();
+            if !config.full_screen {
+                say(locale, Message::new("cli.tui.unsupported"));
+            }
+            play_hot_seat(builder, config);
+            Ok(())
+        }
+        Some("script") if words.len() <= 2 => {
+            let opened = match words.get(1).filter(|path| *path != "-") {
+                Some(path) => {
+                    File::open(path).map(|file| InputManager::use_script(BufReader::new(file)))
+                }
+                None => {
+                    InputManager::use_script(BufReader::new(io::stdin()));
+                    Ok(())
+                }
+            };
+            opened.map(|()| play_hot_seat(builder, config))
+        }
+        Some("host") if words.len() == 2 || words.len() == 3 => {
+            let address = words
+                .get(2)
+                .map_or(network::DEFAULT_ADDRESS, String::as_str);
+            host::host(address, &words[1], builder, config)
+        }
+        Some("join") if words.len() >= 3 => join::join(&words[1], &words[2..].join(" "), locale),
+        _ => {
+            print_usage(locale);
+            Ok(())
+        }
+    };
 
+    if let Err(err) = result {
+        error_handler(err.to_string());
+    }
+}
+
+fn print_usage(locale: Locale) {
+    for message in [
+        Message::new("cli.usage").arg("address", network::DEFAULT_ADDRESS),
+        Message::new("cli.flags"),
+        Message::new("cli.flag.questions"),
+        Message::new("cli.flag.answers"),
+        Message::new("cli.flag.pack"),
+        Message::new("cli.flag.handSize"),
+        Message::new("cli.flag.winTarget"),
+        Message::new("cli.flag.countBase"),
+        Message::new("cli.flag.seed"),
+        Message::new("cli.flag.help"),
+    ] {
+        say(locale, message);
+    }
+}
+
+/// Load the cards and apply the settings given as flags. Return false if one of them failed.
+fn apply_args(args: &Args, builder: &mut GenericDriverBuilder, config: &mut Config) -> bool {
+    let locale = config.locale;
+
+    if args.cards.is_empty() {
+        builder.add_new_questions(questions());
+        builder.add_new_answers(answers());
+    }
+    for source in &args.cards {
+        if !load_cards(builder, source, locale) {
+            return false;
+        }
+    }
+    if !args.cards.is_empty() {
+        print_validation_report(&builder.validate(), locale);
+    }
+
+    let settings = args
+        .hand_size
+        .map_or(Ok(()), |hand_size| builder.set_hand_size(hand_size))
+        .and_then(|()| {
+            args.win_target
+                .map_or(Ok(()), |win_target| builder.set_win_target(win_target))
+        });
+    if let Err(err) = settings {
+        error_handler(err.localize(locale));
+        return false;
+    }
+    if let Some(count_base) = args.count_base {
+        config.count_base = count_base;
+    }
+    builder.set_seed(args.seed);
+
+    true
+}
+
+/// Add the cards of `source` to the decks of the next game. Return false if they can't be read.
+fn load_cards(builder: &mut GenericDriverBuilder, source: &CardSource, locale: Locale) -> bool {
+    match source.load() {
+        Ok(import) => {
+            for issue in import.issues.iter() {
+                println!(
+                    "{}",
+                    format!("{}: {}", issue.location, issue.reason).yellow()
+                );
+            }
+            say(
+                locale,
+                Message::new("cli.cardsLoaded")
+                    .arg("questions", import.deck.questions.len())
+                    .arg("answers", import.deck.answers.len())
+                    .arg("path", source.path().display().to_string()),
+            );
+            builder.add_deck(&import.deck);
+            true
+        }
+        Err(reason) => {
+            error_handler(
+                Message::new("cli.cardsNotLoaded")
+                    .arg("reason", reason)
+                    .localize(locale),
+            );
+            false
+        }
+    }
+}
+
+/// Everyone plays in this terminal, taking turns.
+fn play_hot_seat(mut builder: GenericDriverBuilder, mut config: Config) {
     loop {
         // Preparation
-        println!("Preparation stage!");
-        println!("Add a Player: /add `Player Name`");
-        println!("Start the game: /start");
-        println!("Set hand size: /handsize `Number >= 1`");
-        println!("Set win target: /wintarget `Number >= 1`");
-        println!("Set count base: /countbase `0 or 1`");
-
-        let mut driver = loop {
-            match InputManager::preparation_input() {
-                PreparationInput::Start => match builder.build() {
-                    Ok(driver) => break driver,
-                    Err(err) => error_handler(err),
-                },
-                PreparationInput::AddPlayer(player_name) => {
-                    if let Err(err) = builder.add_player(&player_name) {
-                        error_handler(err)
-                    } else {
-                        println!("{} joins the game!", color_player_name(player_name));
-                    }
-                }
-                PreparationInput::RemovePlayer(player_name) => {
-                    if let Err(err) = builder.remove_player(&player_name) {
-                        error_handler(err)
-                    } else {
-                        println!("{} leaves the game!", color_player_name(player_name));
-                    }
-                }
-                PreparationInput::SetHandSize(hand_size) => {
-                    if let Err(err) = builder.set_hand_size(hand_size) {
-                        error_handler(err)
-                    }
-                }
-                PreparationInput::SetWinTarget(win_target) => config.win_target = win_target,
-                PreparationInput::SetCountBaseOne => config.count_base = CountBase::OneBased,
-                PreparationInput::SetCountBaseZero => config.count_base = CountBase::ZeroBased,
+        print_preparation_help(config.locale);
+
+        let driver = loop {
+            let input = InputManager::preparation_input(config.locale);
+
+            if let Some(driver) = handle_preparation_input(input, &mut builder, &mut config) {
+                break driver;
             }
         };
-        let ordered_players = driver.ordered_players();
+        let kicked = if config.full_screen {
+            tui::play(driver, &config).unwrap_or_else(|err| {
+                error_handler(err.to_string());
+                None
+            })
+        } else {
+            play_in_lines(driver, &config)
+        };
+        if let Some(player) = kicked {
+            handle_preparation_input(
+                PreparationInput::RemovePlayer(player),
+                &mut builder,
+                &mut config,
+            );
+        }
+    }
+}
+
+/// Play the game with one line of output after another. Return the player kicked out, if any.
+fn play_in_lines(mut driver: GenericDriver, config: &Config) -> Option<String> {
+    let locale = config.locale;
+    let ordered_players = driver.ordered_players();
+    say(locale, Message::new("cli.commandsHint"));
 
-        loop {
-            let round_information = driver.start_round();
-            let non_judge_players =
-                find_non_judge_players(&ordered_players, &round_information.judge);
+    loop {
+        let round_information = driver.start_round();
+        let non_judge_players = find_non_judge_players(&ordered_players, &round_information.judge);
 
-            // Display round information to users
-            println!(
-                "The Judge for this round is {}!",
-                color_player_name(&round_information.judge)
+        // Display round information to users
+        say(
+            locale,
+            Message::new("cli.judgeForRound").arg(
+                "judge",
+                color_player_name(&round_information.judge).to_string(),
+            ),
+        );
+        let mut submitted_answers = vec![];
+        for player in non_judge_players {
+            if config.privacy {
+                pass_device_to(player, locale);
+            }
+            println!("======================================================");
+            print_hand(
+                locale,
+                config.count_base,
+                player,
+                &hand_of(&driver, player),
+                &round_information.question,
             );
-            let submitted_answers = non_judge_players.into_iter().fold(vec![], |_, player| {
-                println!("======================================================");
-                println!("{}, Your hand is:", color_player_name(player));
-                for (i, card_content) in round_information
-                    .player_hands
-                    .get(player)
-                    .unwrap()
-                    .iter()
-                    .enumerate()
-                {
-                    println!(
-                        "{} - {}",
-                        if let CountBase::ZeroBased = config.count_base {
-                            i
-                        } else {
-                            i + 1
-                        },
-                        card_content
-                    );
-                }
-                println!("\nAnswer this: {}", round_information.question);
-                println!("Please submit your answers, {}", color_player_name(player));
-                let submitted_answers = loop {
-                    let answers = InputManager::submit_answers(config.count_base);
-                    match driver.submit_answers(player, answers) {
-                        Ok(submitted_answers) => {
-                            if let Some(submitted_answers) = submitted_answers {
-                                break submitted_answers;
-                            } else {
-                                break vec![];
-                            }
+            loop {
+                let answers = match commands::read_answer(
+                    &mut driver,
+                    player,
+                    locale,
+                    config.count_base,
+                    || InputManager::submit_answers(config.count_base, locale),
+                ) {
+                    Ok(answers) => answers,
+                    Err(ending) => return end_game_early(driver, locale, ending),
+                };
+                match driver.submit_answers(player, answers) {
+                    Ok(all_submitted) => {
+                        submitted_answers = all_submitted.unwrap_or_default();
+                        break;
+                    }
+                    Err(err) => {
+                        error_handler(err.localize(locale));
+                        // The deck cannot refill the hands, so no round can finish.
+                        if driver.is_game_over() {
+                            return end_game_early(driver, locale, Ending::Quit);
                         }
-                        Err(err) => error_handler(err),
                     }
-                };
-                println!("======================================================");
+                }
+            }
+            println!("======================================================");
+            if config.privacy {
+                terminal::clear_screen();
+            }
+        }
 
-                submitted_answers
-            });
+        // Display the submitted answers to everyone
+        {
+            say(locale, Message::new("cli.answersCollected"));
+            for (i, (_, answer)) in submitted_answers.iter().enumerate() {
+                println!(
+                    "{} - {}",
+                    if let CountBase::ZeroBased = config.count_base {
+                        i
+                    } else {
+                        i + 1
+                    },
+                    answer
+                );
 
-            // Display the submitted answers to everyone
-            {
-                println!("The following creative answers were collected:");
-                for (i, (_, answer)) in submitted_answers.iter().enumerate() {
-                    println!(
-                        "{} - {}",
-                        if let CountBase::ZeroBased = config.count_base {
-                            i
-                        } else {
-                            i + 1
-                        },
-                        answer
-                    );
-
-                    for _ in 0..3 {
-                        print!(".");
-                        std::io::stdout().flush().unwrap();
+                for _ in 0..3 {
+                    print!(".");
+                    std::io::stdout().flush().unwrap();
+                    if config.delays {
                         sleep(1);
                     }
-                    println!();
                 }
-                println!(
-                    "Choose your favorite, Judge {}!",
-                    color_player_name(round_information.judge)
-                );
+                println!();
             }
+            say(
+                locale,
+                Message::new("cli.chooseFavorite").arg(
+                    "judge",
+                    color_player_name(&round_information.judge).to_string(),
+                ),
+            );
+        }
 
-            let ranking = loop {
-                let chosen_index = InputManager::choose_favorite(config.count_base);
-
-                let chosen_player = if let Some((player, _)) = submitted_answers.get(chosen_index) {
-                    player
-                } else {
-                    error_handler("Invalid input");
-                    continue;
-                };
+        let ranking = loop {
+            let chosen_index = match commands::read_answer(
+                &mut driver,
+                &round_information.judge,
+                locale,
+                config.count_base,
+                || InputManager::choose_favorite(config.count_base, locale),
+            ) {
+                Ok(chosen_index) => chosen_index,
+                Err(ending) => return end_game_early(driver, locale, ending),
+            };
 
-                match driver.end_round(chosen_player) {
-                    Ok(round_end_info) => break round_end_info,
-                    Err(err) => error_handler(err),
-                }
+            let chosen_player = if let Some((player, _)) = submitted_answers.get(chosen_index) {
+                player
+            } else {
+                error_handler(Message::new("cli.invalidInput").localize(locale));
+                continue;
             };
-            let highest = ranking[0].clone();
 
-            // Display the ranking
-            {
-                let mut rank_tracker = 0;
-                let mut last_points = highest.1 + 1;
-                println!("======================================================");
-                for (name, points) in ranking {
-                    if points != last_points {
-                        rank_tracker += 1
-                    }
-                    println!(
-                        "{} {} - {}",
-                        {
-                            if rank_tracker == 1 {
-                                rank_tracker.to_string().red().bold()
-                            } else {
-                                rank_tracker.to_string().as_str().into()
-                            }
-                        },
-                        color_player_name(name),
-                        points
-                    );
-                    last_points = points;
-                }
-                println!("======================================================");
+            match driver.end_round(chosen_player) {
+                Ok(round_end_info) => break round_end_info,
+                Err(err) => error_handler(err.localize(locale)),
             }
+        };
+        let highest = ranking[0].clone();
 
-            if highest.1 >= config.win_target as i32 {
-                println!(
-                    "🎉 Congratulations, {}, You Have Won! 🎉\n",
-                    color_player_name(highest.0)
+        // Display the ranking
+        for line in ranking_lines(&ranking) {
+            println!("{}", line);
+        }
+
+        if highest.1 >= driver.config().win_target as i32 {
+            say(
+                locale,
+                Message::new("cli.winner").arg("player", color_player_name(highest.0).to_string()),
+            );
+            println!();
+            driver.end_game();
+            return None;
+        }
+
+        if driver.is_game_over() {
+            say(
+                locale,
+                Message::new("cli.deckRanOut")
+                    .arg("player", color_player_name(highest.0).to_string())
+                    .arg("points", highest.1),
+            );
+            println!();
+            driver.end_game();
+            return None;
+        }
+    }
+}
+
+/// Return the player kicked out, if any.
+fn end_game_early(driver: GenericDriver, locale: Locale, ending: Ending) -> Option<String> {
+    say(locale, Message::new("cli.gameEnded"));
+    println!();
+    driver.end_game();
+
+    match ending {
+        Ending::Quit => None,
+        Ending::Kick(player) => Some(player),
+    }
+}
+
+/// Apply a command of the preparation stage. Return the driver once the game starts.
+pub(crate) fn handle_preparation_input(
+    input: PreparationInput,
+    builder: &mut GenericDriverBuilder,
+    config: &mut Config,
+) -> Option<GenericDriver> {
+    let locale = config.locale;
+
+    match input {
+        PreparationInput::Start => match builder.build() {
+            Ok(driver) => return Some(driver),
+            Err(_) => builder
+                .check()
+                .problems
+                .into_iter()
+                .for_each(|err| error_handler(err.localize(locale))),
+        },
+        PreparationInput::Validate => print_validation_report(&builder.validate(), locale),
+        PreparationInput::AddPlayer(player_name) => {
+            if let Err(err) = builder.add_player(&player_name) {
+                error_handler(err.localize(locale))
+            } else {
+                say(
+                    locale,
+                    Message::new("cli.playerJoined")
+                        .arg("player", color_player_name(player_name).to_string()),
                 );
-                driver.end_game();
-                break;
             }
         }
+        PreparationInput::RemovePlayer(player_name) => {
+            if let Err(err) = builder.remove_player(&player_name) {
+                error_handler(err.localize(locale))
+            } else {
+                say(
+                    locale,
+                    Message::new("cli.playerLeft")
+                        .arg("player", color_player_name(player_name).to_string()),
+                );
+            }
+        }
+        PreparationInput::SetHandSize(hand_size) => {
+            if let Err(err) = builder.set_hand_size(hand_size) {
+                error_handler(err.localize(locale))
+            }
+        }
+        PreparationInput::SetWinTarget(win_target) => {
+            if let Err(err) = builder.set_win_target(win_target) {
+                error_handler(err.localize(locale))
+            }
+        }
+        PreparationInput::LoadCards(source) => {
+            if load_cards(builder, &source, locale) {
+                print_validation_report(&builder.validate(), locale);
+            }
+        }
+        PreparationInput::ClearCards => {
+            builder.clear_all_questions();
+            builder.clear_all_answers();
+            say(locale, Message::new("cli.cardsCleared"));
+        }
+        PreparationInput::SetCountBaseOne => config.count_base = CountBase::OneBased,
+        PreparationInput::SetCountBaseZero => config.count_base = CountBase::ZeroBased,
+        PreparationInput::SetPrivacy(privacy) => {
+            config.privacy = privacy;
+            say(
+                locale,
+                Message::new(if privacy {
+                    "cli.privacyOn"
+                } else {
+                    "cli.privacyOff"
+                }),
+            );
+        }
+        PreparationInput::SetLocale(code) => match Locale::from_code(&code) {
+            Some(locale) => {
+                config.locale = locale;
+                say(locale, Message::new("cli.usingLocale"));
+            }
+            None => error_handler(
+                Message::new("cli.noSuchLocale")
+                    .arg("locale", code)
+                    .localize(locale),
+            ),
+        },
+        PreparationInput::UsePreset(name) => match GameConfig::preset(&name) {
+            Some(preset) => {
+                if let Err(err) = builder.set_config(preset) {
+                    error_handler(err.localize(locale))
+                } else {
+                    say(locale, Message::new("cli.usingPreset").arg("preset", name));
+                }
+            }
+            None => error_handler(
+                Message::new("cli.noSuchPreset")
+                    .arg("preset", name)
+                    .localize(locale),
+            ),
+        },
+        PreparationInput::LoadConfig(path) => {
+            let loaded = fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|json| GameConfig::from_json(&json).map_err(|err| err.localize(locale)))
+                .and_then(|game_config| {
+                    builder
+                        .set_config(game_config)
+                        .map_err(|err| err.localize(locale))
+                });
+
+            match loaded {
+                Ok(()) => say(locale, Message::new("cli.configLoaded").arg("path", path)),
+                Err(err) => error_handler(err),
+            }
+        }
+        PreparationInput::SaveConfig(path) => match fs::write(&path, builder.config().to_json()) {
+            Ok(()) => say(locale, Message::new("cli.configSaved").arg("path", path)),
+            Err(err) => error_handler(err.to_string()),
+        },
+    }
+
+    None
+}
+
+/// Clear the screen, including what could be scrolled back to, and wait for `player`.
+fn pass_device_to(player: &str, locale: Locale) {
+    terminal::clear_screen();
+    say(
+        locale,
+        Message::new("cli.passTo").arg("player", color_player_name(player).to_string()),
+    );
+    InputManager::wait_for_enter(locale);
+}
+
+/// The cards in the hand of `player`, for them alone to see.
+pub(crate) fn hand_of(driver: &GenericDriver, player: &str) -> Vec<String> {
+    driver
+        .view_for(player)
+        .map(|view| view.hand.into_iter().map(|card| card.content).collect())
+        .unwrap_or_default()
+}
+
+/// A player's hand, numbered from the count base, and the question to answer.
+pub(crate) fn print_hand(
+    locale: Locale,
+    count_base: CountBase,
+    player: &str,
+    hand: &[String],
+    question: &str,
+) {
+    say(
+        locale,
+        Message::new("cli.yourHand").arg("player", color_player_name(player).to_string()),
+    );
+    for (i, card_content) in hand.iter().enumerate() {
+        println!("{} - {}", i + count_base.first_index(), card_content);
+    }
+    println!();
+    say(
+        locale,
+        Message::new("cli.answerThis").arg("question", question),
+    );
+    say(
+        locale,
+        Message::new("cli.submitAnswers").arg("player", color_player_name(player).to_string()),
+    );
+}
+
+/// The ranking between two separator lines. Tied players share a rank.
+pub(crate) fn ranking_lines(ranking: &[(String, i32)]) -> Vec<String> {
+    let mut lines = vec!["======================================================".to_owned()];
+    let mut rank_tracker = 0;
+    let mut last_points = ranking.first().map_or(0, |(_, points)| points + 1);
+    for (name, points) in ranking {
+        if *points != last_points {
+            rank_tracker += 1
+        }
+        lines.push(format!(
+            "{} {} - {}",
+            {
+                if rank_tracker == 1 {
+                    rank_tracker.to_string().red().bold()
+                } else {
+                    rank_tracker.to_string().as_str().into()
+                }
+            },
+            color_player_name(name),
+            points
+        ));
+        last_points = *points;
+    }
+    lines.push("======================================================".to_owned());
+
+    lines
+}
+
+pub(crate) fn print_preparation_help(locale: Locale) {
+    let locale_codes: Vec<_> = Locale::ALL.iter().map(|locale| locale.code()).collect();
+
+    for message in [
+        Message::new("cli.preparation"),
+        Message::new("cli.help.add"),
+        Message::new("cli.help.start"),
+        Message::new("cli.help.validate"),
+        Message::new("cli.help.handSize"),
+        Message::new("cli.help.winTarget"),
+        Message::new("cli.help.countBase"),
+        Message::new("cli.help.privacy"),
+        Message::new("cli.help.preset").arg("presets", PRESET_NAMES.join(" | ")),
+        Message::new("cli.help.config"),
+        Message::new("cli.help.cards"),
+        Message::new("cli.help.locale").arg("locales", locale_codes.join(" | ")),
+    ] {
+        say(locale, message);
+    }
+}
+
+fn print_validation_report(report: &ValidationReport, locale: Locale) {
+    if report.is_clean() {
+        say(locale, Message::new("cli.noProblems"));
+        return;
+    }
+
+    for issue in report.issues.iter() {
+        let line = issue.to_string();
+        println!(
+            "{}",
+            match issue.severity {
+                Severity::Error => line.red(),
+                Severity::Warning => line.yellow(),
+                Severity::Info => line.normal(),
+            }
+        );
     }
+    say(
+        locale,
+        Message::new("cli.reportSummary")
+            .arg("errors", report.count(Severity::Error))
+            .arg("warnings", report.count(Severity::Warning))
+            .arg("notes", report.count(Severity::Info)),
+    );
 }
 
-fn color_player_name(text: impl Into<String>) -> ColoredString {
+pub(crate) fn color_player_name(text: impl Into<String>) -> ColoredString {
     text.into().green().bold()
 }
 
-fn find_non_judge_players<'a>(
+pub(crate) fn find_non_judge_players<'a>(
     ordered_players: &'a [String],
     judge_name: &String,
 ) -> Vec<&'a String> {
@@ -225,6 +683,6 @@ fn find_non_judge_players<'a>(
         .collect()
 }
 
-fn sleep(seconds: u64) {
+pub(crate) fn sleep(seconds: u64) {
     thread::sleep(Duration::new(seconds, 0));
 }
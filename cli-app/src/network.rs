@@ -0,0 +1,127 @@
+//! What `host` and `join` send each other over TCP: one JSON message per line.
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::commands::GameCommand;
+
+pub(crate) const DEFAULT_ADDRESS: &str = "127.0.0.1:7879";
+
+/// From the host to one player. Text is already in that player's language.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum HostMessage {
+    Say {
+        text: String,
+    },
+    Error {
+        text: String,
+    },
+    /// Ask for answers from the player's own hand, numbered from `first_index`.
+    #[serde(rename_all = "camelCase")]
+    Submit {
+        hand: Vec<String>,
+        question: String,
+        first_index: usize,
+    },
+    /// Ask the Judge for their favorite of the answers shown to everyone.
+    #[serde(rename_all = "camelCase")]
+    Choose {
+        first_index: usize,
+    },
+    /// What a command of the player showed; what was asked last is asked again.
+    Reply {
+        lines: Vec<String>,
+    },
+    /// The host closes the connection.
+    Bye,
+}
+
+/// From a player to the host.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum GuestMessage {
+    /// The first message. `locale` is a language code.
+    Join { player: String, locale: String },
+    /// ZERO-based indices into the hand.
+    Answers { indices: Vec<usize> },
+    /// ZERO-based index into the answers.
+    Favorite { index: usize },
+    /// Typed instead of answers or a favorite.
+    Command { command: GameCommand },
+}
+
+pub(crate) struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    pub fn connect(address: impl ToSocketAddrs) -> io::Result<Self> {
+        Self::new(TcpStream::connect(address)?)
+    }
+
+    pub fn new(stream: TcpStream) -> io::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    pub fn send(&mut self, message: &impl Serialize) -> io::Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.flush()
+    }
+
+    /// Fails with `UnexpectedEof` once the other side hangs up.
+    pub fn receive<T: DeserializeOwned>(&mut self) -> io::Result<T> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        Ok(serde_json::from_str(&line)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use super::*;
+
+    #[test]
+    fn messages_cross_the_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut guest = Connection::connect(listener.local_addr().unwrap()).unwrap();
+        let mut host = Connection::new(listener.accept().unwrap().0).unwrap();
+
+        let join = GuestMessage::Join {
+            player: "Alex".to_owned(),
+            locale: "es".to_owned(),
+        };
+        guest.send(&join).unwrap();
+        assert_eq!(host.receive::<GuestMessage>().unwrap(), join);
+
+        let submit = HostMessage::Submit {
+            hand: vec!["video".to_owned(), "blood\nand tears".to_owned()],
+            question: "I only believe in __.".to_owned(),
+            first_index: 1,
+        };
+        host.send(&submit).unwrap();
+        host.send(&HostMessage::Bye).unwrap();
+        assert_eq!(guest.receive::<HostMessage>().unwrap(), submit);
+        assert_eq!(guest.receive::<HostMessage>().unwrap(), HostMessage::Bye);
+
+        drop(host);
+        let hung_up = guest.receive::<HostMessage>().unwrap_err();
+        assert_eq!(hung_up.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
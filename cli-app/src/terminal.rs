@@ -0,0 +1,151 @@
+//! Just enough terminal control for the full-screen mode: keys as they are pressed, the
+//! alternate screen and the window size.
+
+use std::io::{self, IsTerminal, Write};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Space,
+    Backspace,
+    Char(char),
+    /// Ctrl-C or Ctrl-D.
+    Quit,
+}
+
+/// Whether stdin and stdout are both terminals that can be taken over.
+pub(crate) fn is_supported() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// (columns, rows) of the terminal, or 80 x 24 if it does not say.
+pub(crate) fn size() -> (usize, usize) {
+    match terminal::size() {
+        Ok((columns, rows)) if columns > 0 && rows > 0 => (columns as usize, rows as usize),
+        _ => (80, 24),
+    }
+}
+
+/// Clear the screen and the scrollback, so nothing printed before can be scrolled back to.
+pub(crate) fn clear_screen() {
+    let _ = execute!(
+        io::stdout(),
+        MoveTo(0, 0),
+        Clear(ClearType::All),
+        Clear(ClearType::Purge)
+    );
+}
+
+/// Reads keys without echo on the alternate screen, until dropped.
+pub(crate) struct FullScreen(());
+
+impl FullScreen {
+    pub fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        if let Err(err) = execute!(io::stdout(), EnterAlternateScreen, Hide) {
+            let _ = terminal::disable_raw_mode();
+            return Err(err);
+        }
+
+        Ok(Self(()))
+    }
+
+    /// Replace what is on the screen with `lines`, which must fit in it.
+    pub fn draw(&mut self, lines: &[String]) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+
+        queue!(stdout, MoveTo(0, 0), Clear(ClearType::All))?;
+        write!(stdout, "{}", lines.join("\r\n"))?;
+        stdout.flush()
+    }
+
+    /// Wait for a key press. A lone Esc and keys without a `Key` are skipped.
+    pub fn read_key(&mut self) -> io::Result<Key> {
+        loop {
+            if let Event::Key(event) = event::read()? {
+                if let Some(key) = key_of(event) {
+                    return Ok(key);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for FullScreen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn key_of(event: KeyEvent) -> Option<Key> {
+    // Some terminals report releases and repeats too.
+    if event.kind == KeyEventKind::Release {
+        return None;
+    }
+
+    Some(match event.code {
+        KeyCode::Char('c') | KeyCode::Char('d')
+            if event.modifiers.contains(KeyModifiers::CONTROL) =>
+        {
+            Key::Quit
+        }
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Char(' ') => Key::Space,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Char(c) => Key::Char(c),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Option<Key> {
+        key_of(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn read_keys() {
+        assert_eq!(key(KeyCode::Up, KeyModifiers::NONE), Some(Key::Up));
+        assert_eq!(key(KeyCode::Enter, KeyModifiers::NONE), Some(Key::Enter));
+        assert_eq!(
+            key(KeyCode::Char(' '), KeyModifiers::NONE),
+            Some(Key::Space)
+        );
+        assert_eq!(
+            key(KeyCode::Char('好'), KeyModifiers::NONE),
+            Some(Key::Char('好'))
+        );
+        assert_eq!(
+            key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            Some(Key::Quit)
+        );
+    }
+
+    #[test]
+    fn skip_other_keys() {
+        assert_eq!(key(KeyCode::Esc, KeyModifiers::NONE), None);
+        assert_eq!(key(KeyCode::F(5), KeyModifiers::NONE), None);
+
+        let mut release = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        release.kind = KeyEventKind::Release;
+        assert_eq!(key_of(release), None);
+    }
+}
@@ -0,0 +1,866 @@
+//! The full-screen mode of the hot seat: the question, the hand and the scores in panels, cards
+//! picked with the arrow keys under a live preview of the filled-in question, then the reveal
+//! and the judging.
+
+use std::io;
+
+use bot_against_humanity_core::{
+    drivers::{generic::GenericDriver, GameCoreDriver},
+    i18n::{Locale, Message},
+};
+use colored::Colorize;
+
+use crate::{
+    color_player_name,
+    commands::{self, Ending, GameCommand},
+    error_handler, find_non_judge_players, hand_of,
+    input::CountBase,
+    say, sleep,
+    terminal::{self, FullScreen, Key},
+    Config,
+};
+
+/// Narrower screens show the scores under the other panels.
+const SIDE_BY_SIDE_WIDTH: usize = 60;
+const SCORES_WIDTH: usize = 24;
+const MIN_WIDTH: usize = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Style {
+    Normal,
+    Cursor,
+    Picked,
+    Error,
+    Hint,
+}
+
+struct Line {
+    text: String,
+    style: Style,
+}
+
+impl Line {
+    fn new(text: impl Into<String>, style: Style) -> Self {
+        Self {
+            text: text.into(),
+            style,
+        }
+    }
+}
+
+enum Outcome {
+    Winner(String),
+    /// The leader and their Awesome Points.
+    DeckRanOut(String, i32),
+    Ended(Ending),
+    /// The round could not be completed, for this reason.
+    Failed(String),
+}
+
+/// What every screen of a round shows.
+struct Round<'a> {
+    locale: Locale,
+    first_index: usize,
+    /// ONE-based
+    number: usize,
+    judge: &'a str,
+    question: &'a str,
+    scores: Vec<(String, i32)>,
+}
+
+/// Return the player kicked out, if any.
+pub(crate) fn play(mut driver: GenericDriver, config: &Config) -> io::Result<Option<String>> {
+    let outcome = {
+        let mut screen = FullScreen::enter()?;
+        play_rounds(&mut screen, &mut driver, config)
+    };
+    driver.end_game();
+
+    // Back on the normal screen, where the result stays.
+    let locale = config.locale;
+    match outcome? {
+        Outcome::Winner(player) => say(
+            locale,
+            Message::new("cli.winner").arg("player", color_player_name(player).to_string()),
+        ),
+        Outcome::DeckRanOut(player, points) => say(
+            locale,
+            Message::new("cli.deckRanOut")
+                .arg("player", color_player_name(player).to_string())
+                .arg("points", points),
+        ),
+        Outcome::Ended(ending) => {
+            say(locale, Message::new("cli.gameEnded"));
+            println!();
+            if let Ending::Kick(player) = ending {
+                return Ok(Some(player));
+            }
+            return Ok(None);
+        }
+        Outcome::Failed(reason) => {
+            error_handler(reason);
+            say(locale, Message::new("cli.gameEnded"));
+            println!();
+            return Ok(None);
+        }
+    }
+    println!();
+
+    Ok(None)
+}
+
+fn play_rounds(
+    screen: &mut FullScreen,
+    driver: &mut GenericDriver,
+    config: &Config,
+) -> io::Result<Outcome> {
+    let locale = config.locale;
+    let ordered_players = driver.ordered_players();
+
+    loop {
+        let round_information = driver.start_round();
+        let round = Round {
+            locale,
+            first_index: config.count_base.first_index(),
+            number: driver.round(),
+            judge: &round_information.judge,
+            question: &round_information.question,
+            scores: driver.scores(),
+        };
+
+        let mut submitted_answers = vec![];
+        for player in find_non_judge_players(&ordered_players, &round_information.judge) {
+            let mut error = None;
+
+            if config.privacy && !pass_device(screen, &round, player)? {
+                return Ok(Outcome::Ended(Ending::Quit));
+            }
+
+            loop {
+                let picked = match pick_answers(screen, &round, driver, player, error.take())? {
+                    Ok(picked) => picked,
+                    Err(ending) => return Ok(Outcome::Ended(ending)),
+                };
+                match driver.submit_answers(player, picked) {
+                    Ok(all_submitted) => {
+                        submitted_answers = all_submitted.unwrap_or_default();
+                        break;
+                    }
+                    Err(err) if driver.is_game_over() => {
+                        return Ok(Outcome::Failed(err.localize(locale)))
+                    }
+                    Err(err) => error = Some(err.localize(locale)),
+                }
+            }
+        }
+
+        let answers: Vec<&str> = submitted_answers
+            .iter()
+            .map(|(_, answer)| answer.as_str())
+            .collect();
+        reveal(screen, &round, &answers, config.delays)?;
+
+        let mut error = None;
+        let (chosen, ranking) = loop {
+            let chosen = match choose_favorite(screen, &round, driver, &answers, error.take())? {
+                Ok(chosen) => chosen,
+                Err(ending) => return Ok(Outcome::Ended(ending)),
+            };
+            match driver.end_round(&submitted_answers[chosen].0) {
+                Ok(ranking) => break (chosen, ranking),
+                Err(err) => error = Some(err.localize(locale)),
+            }
+        };
+
+        let (leader, points) = ranking[0].clone();
+        let outcome = if points >= driver.config().win_target as i32 {
+            Some(Outcome::Winner(leader))
+        } else if driver.is_game_over() {
+            Some(Outcome::DeckRanOut(leader, points))
+        } else {
+            None
+        };
+
+        let round = Round {
+            scores: ranking,
+            ..round
+        };
+        if !show_round_winner(
+            screen,
+            &round,
+            &answers,
+            chosen,
+            &submitted_answers[chosen].0,
+        )? {
+            return Ok(Outcome::Ended(Ending::Quit));
+        }
+        if let Some(outcome) = outcome {
+            return Ok(outcome);
+        }
+    }
+}
+
+/// Hide the last hand until `player` has the device. Return false to end the game.
+fn pass_device(screen: &mut FullScreen, round: &Round, player: &str) -> io::Result<bool> {
+    let (width, _) = terminal::size();
+    let header = Message::new("cli.tui.header")
+        .arg("round", round.number)
+        .arg("judge", round.judge)
+        .localize(round.locale);
+    let message = Message::new("cli.passTo")
+        .arg("player", player)
+        .localize(round.locale);
+
+    let mut lines = vec![pad(&header, width).bold().to_string(), String::new()];
+    lines.extend(wrap(&message, width.max(MIN_WIDTH)));
+    screen.draw(&lines)?;
+
+    loop {
+        match screen.read_key()? {
+            Key::Enter => return Ok(true),
+            Key::Char('q') | Key::Quit => return Ok(false),
+            _ => {}
+        }
+    }
+}
+
+/// Return the ZERO-based indices of the picked cards, in order, or why the game ends.
+fn pick_answers(
+    screen: &mut FullScreen,
+    round: &Round,
+    driver: &mut GenericDriver,
+    player: &str,
+    mut error: Option<String>,
+) -> io::Result<Result<Vec<usize>, Ending>> {
+    let blanks = count_blanks(round.question);
+    let title = Message::new("cli.tui.hand")
+        .arg("player", player)
+        .localize(round.locale);
+    let mut picking = Picking::default();
+
+    loop {
+        // Read again every time, since a redraw changes it.
+        let hand = &hand_of(driver, player);
+        // The deck can run out under `ShortHands` and leave nothing to pick from.
+        if hand.len() < blanks && error.is_none() {
+            error = Some(
+                Message::new("cli.tui.handTooShort")
+                    .arg("cards", hand.len())
+                    .arg("blanks", blanks)
+                    .localize(round.locale),
+            );
+        }
+
+        let cards = hand
+            .iter()
+            .enumerate()
+            .map(|(i, card)| {
+                let mark = match picking.picked.iter().position(|&p| p == i) {
+                    Some(order) => format!("[{}]", order + 1),
+                    None => "   ".to_owned(),
+                };
+                let style = if i == picking.cursor {
+                    Style::Cursor
+                } else if picking.picked.contains(&i) {
+                    Style::Picked
+                } else {
+                    Style::Normal
+                };
+
+                Line::new(
+                    format!("{} {} - {}", mark, i + round.first_index, card),
+                    style,
+                )
+            })
+            .collect();
+        let hint = hint_or_error(&mut error, || {
+            Message::new("cli.tui.pickHelp").localize(round.locale)
+        });
+        screen.draw(&render(
+            round,
+            &preview(round.question, &picking.previewed(hand, blanks)),
+            &title,
+            cards,
+            hint,
+        ))?;
+
+        let key = screen.read_key()?;
+        if let Some(command) = command_for(screen, round, driver, key, true)? {
+            if let Err(ending) = show_command(screen, round, driver, player, &title, command)? {
+                return Ok(Err(ending));
+            }
+            picking = Picking::default();
+            continue;
+        }
+
+        if let Some(picked) = picking.press(key, hand.len(), blanks) {
+            return Ok(Ok(picked));
+        }
+    }
+}
+
+/// The indices of the cards picked so far, in order, and of the card under the cursor.
+#[derive(Default)]
+struct Picking {
+    cursor: usize,
+    picked: Vec<usize>,
+}
+
+impl Picking {
+    /// The card under the cursor fills the next blank until it is picked.
+    fn previewed<'a>(&self, hand: &'a [String], blanks: usize) -> Vec<&'a str> {
+        let mut previewed: Vec<&str> = self.picked.iter().map(|&i| hand[i].as_str()).collect();
+        if previewed.len() < blanks && !self.picked.contains(&self.cursor) {
+            if let Some(card) = hand.get(self.cursor) {
+                previewed.push(card);
+            }
+        }
+
+        previewed
+    }
+
+    /// Return the picked cards once every blank is filled. A hand with fewer cards than blanks
+    /// ignores every key.
+    fn press(&mut self, key: Key, hand_size: usize, blanks: usize) -> Option<Vec<usize>> {
+        if hand_size == 0 || hand_size < blanks {
+            return None;
+        }
+
+        match key {
+            Key::Up | Key::Left => self.cursor = (self.cursor + hand_size - 1) % hand_size,
+            Key::Down | Key::Right => self.cursor = (self.cursor + 1) % hand_size,
+            Key::Enter => {
+                if self.picked.len() < blanks && !self.picked.contains(&self.cursor) {
+                    self.picked.push(self.cursor);
+                }
+                if self.picked.len() >= blanks {
+                    return Some(std::mem::take(&mut self.picked));
+                }
+            }
+            Key::Space => match self.picked.iter().position(|&p| p == self.cursor) {
+                Some(order) => {
+                    self.picked.remove(order);
+                }
+                None => self.picked.push(self.cursor),
+            },
+            Key::Backspace => {
+                self.picked.pop();
+            }
+            _ => {}
+        }
+
+        None
+    }
+}
+
+/// Show the combined answers one by one, a second apart, or all at once without `delays`.
+fn reveal(
+    screen: &mut FullScreen,
+    round: &Round,
+    answers: &[&str],
+    delays: bool,
+) -> io::Result<()> {
+    let title = Message::new("cli.tui.answers").localize(round.locale);
+
+    let first_shown = if delays { 0 } else { answers.len() };
+    for shown in first_shown..=answers.len() {
+        let hint = Line::new(
+            Message::new("cli.tui.revealing").localize(round.locale),
+            Style::Hint,
+        );
+        screen.draw(&render(
+            round,
+            round.question,
+            &title,
+            numbered_answers(round, &answers[..shown], None, Style::Normal),
+            hint,
+        ))?;
+
+        if shown < answers.len() {
+            sleep(1);
+        }
+    }
+
+    Ok(())
+}
+
+/// Return the index of the Judge's favorite answer, or why the game ends.
+fn choose_favorite(
+    screen: &mut FullScreen,
+    round: &Round,
+    driver: &mut GenericDriver,
+    answers: &[&str],
+    mut error: Option<String>,
+) -> io::Result<Result<usize, Ending>> {
+    let title = Message::new("cli.tui.answers").localize(round.locale);
+    let mut cursor = 0;
+
+    loop {
+        let hint = hint_or_error(&mut error, || {
+            Message::new("cli.tui.judgeHelp")
+                .arg("judge", round.judge)
+                .localize(round.locale)
+        });
+        screen.draw(&render(
+            round,
+            answers[cursor],
+            &title,
+            numbered_answers(round, answers, Some(cursor), Style::Cursor),
+            hint,
+        ))?;
+
+        let key = screen.read_key()?;
+        if let Some(command) = command_for(screen, round, driver, key, false)? {
+            if let Err(ending) = show_command(screen, round, driver, round.judge, &title, command)?
+            {
+                return Ok(Err(ending));
+            }
+            continue;
+        }
+
+        match key {
+            Key::Up | Key::Left => cursor = (cursor + answers.len() - 1) % answers.len(),
+            Key::Down | Key::Right => cursor = (cursor + 1) % answers.len(),
+            Key::Enter => return Ok(Ok(cursor)),
+            _ => {}
+        }
+    }
+}
+
+/// The command `key` stands for where players answer: r to redraw (with `can_redraw`), k to
+/// kick a player picked next, and q or Ctrl-C to quit.
+fn command_for(
+    screen: &mut FullScreen,
+    round: &Round,
+    driver: &GenericDriver,
+    key: Key,
+    can_redraw: bool,
+) -> io::Result<Option<GameCommand>> {
+    Ok(match key {
+        Key::Char('r') if can_redraw => Some(GameCommand::Redraw),
+        Key::Char('k') => pick_player(screen, round, driver)?.map(GameCommand::Kick),
+        Key::Char('q') | Key::Quit => Some(GameCommand::Quit),
+        _ => None,
+    })
+}
+
+/// Run `command` for `player` and show what it says under `title` until a key is pressed.
+fn show_command(
+    screen: &mut FullScreen,
+    round: &Round,
+    driver: &mut GenericDriver,
+    player: &str,
+    title: &str,
+    command: GameCommand,
+) -> io::Result<Result<(), Ending>> {
+    let count_base = CountBase::from_first_index(round.first_index);
+    let lines = match commands::run(command, driver, player, round.locale, count_base) {
+        Ok(lines) => lines,
+        Err(ending) => return Ok(Err(ending)),
+    };
+
+    let body = lines
+        .into_iter()
+        .map(|line| Line::new(line, Style::Normal))
+        .collect();
+    let hint = Line::new(
+        Message::new("cli.tui.backHelp").localize(round.locale),
+        Style::Hint,
+    );
+    screen.draw(&render(round, round.question, title, body, hint))?;
+    screen.read_key()?;
+
+    Ok(Ok(()))
+}
+
+/// Return the player to kick, or None to go back.
+fn pick_player(
+    screen: &mut FullScreen,
+    round: &Round,
+    driver: &GenericDriver,
+) -> io::Result<Option<String>> {
+    let players = driver.ordered_players();
+    let title = Message::new("cli.tui.kick").localize(round.locale);
+    let mut cursor = 0;
+
+    loop {
+        let body = players
+            .iter()
+            .enumerate()
+            .map(|(i, player)| {
+                let style = if i == cursor {
+                    Style::Cursor
+                } else {
+                    Style::Normal
+                };
+                Line::new(player.as_str(), style)
+            })
+            .collect();
+        let hint = Line::new(
+            Message::new("cli.tui.kickHelp").localize(round.locale),
+            Style::Hint,
+        );
+        screen.draw(&render(round, round.question, &title, body, hint))?;
+
+        match screen.read_key()? {
+            Key::Up | Key::Left => cursor = (cursor + players.len() - 1) % players.len(),
+            Key::Down | Key::Right => cursor = (cursor + 1) % players.len(),
+            Key::Enter => return Ok(Some(players[cursor].clone())),
+            Key::Backspace | Key::Char('q') | Key::Quit => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// Return false to end the game.
+fn show_round_winner(
+    screen: &mut FullScreen,
+    round: &Round,
+    answers: &[&str],
+    chosen: usize,
+    player: &str,
+) -> io::Result<bool> {
+    let hint = Line::new(
+        Message::new("cli.tui.roundWinner")
+            .arg("player", player)
+            .localize(round.locale),
+        Style::Picked,
+    );
+    screen.draw(&render(
+        round,
+        answers[chosen],
+        &Message::new("cli.tui.answers").localize(round.locale),
+        numbered_answers(round, answers, Some(chosen), Style::Picked),
+        hint,
+    ))?;
+
+    loop {
+        match screen.read_key()? {
+            Key::Enter | Key::Space => return Ok(true),
+            Key::Char('q') | Key::Quit => return Ok(false),
+            _ => {}
+        }
+    }
+}
+
+fn numbered_answers(
+    round: &Round,
+    answers: &[&str],
+    marked: Option<usize>,
+    style: Style,
+) -> Vec<Line> {
+    answers
+        .iter()
+        .enumerate()
+        .map(|(i, answer)| {
+            let text = format!("{} - {}", i + round.first_index, answer);
+            if marked == Some(i) {
+                Line::new(text, style)
+            } else {
+                Line::new(text, Style::Normal)
+            }
+        })
+        .collect()
+}
+
+/// Show an error once, then the hint again.
+fn hint_or_error(error: &mut Option<String>, hint: impl FnOnce() -> String) -> Line {
+    match error.take() {
+        Some(error) => Line::new(error, Style::Error),
+        None => Line::new(hint(), Style::Hint),
+    }
+}
+
+/// Questions come from the driver with one `_` per blank.
+fn count_blanks(question: &str) -> usize {
+    question.matches('_').count()
+}
+
+/// Fill the blanks of `question` in order, like the driver does, leaving the rest blank.
+fn preview(question: &str, answers: &[&str]) -> String {
+    let mut answers = answers.iter();
+    let mut pieces = question.split('_');
+    let mut preview = pieces.next().unwrap_or_default().to_owned();
+
+    for piece in pieces {
+        match answers.next() {
+            Some(answer) => preview += &format!("**{}**", answer),
+            None => preview.push('_'),
+        }
+        preview += piece;
+    }
+
+    preview
+}
+
+fn render(round: &Round, question: &str, title: &str, body: Vec<Line>, hint: Line) -> Vec<String> {
+    let (width, height) = terminal::size();
+
+    layout(round, question, title, body, hint, width, height)
+}
+
+/// The header, the question, `body` and the scores, then `hint` on the last line.
+fn layout(
+    round: &Round,
+    question: &str,
+    title: &str,
+    body: Vec<Line>,
+    hint: Line,
+    width: usize,
+    height: usize,
+) -> Vec<String> {
+    let width = width.max(MIN_WIDTH);
+    let header = Message::new("cli.tui.header")
+        .arg("round", round.number)
+        .arg("judge", round.judge)
+        .localize(round.locale);
+    let scores_title = Message::new("cli.tui.scores").localize(round.locale);
+    let scores: Vec<Line> = round
+        .scores
+        .iter()
+        .map(|(player, points)| Line::new(format!("{:>3} {}", points, player), Style::Normal))
+        .collect();
+
+    let mut lines = vec![pad(&header, width).bold().to_string()];
+    lines.extend(panel(
+        &Message::new("cli.tui.question").localize(round.locale),
+        &[Line::new(question, Style::Normal)],
+        width,
+        0,
+    ));
+    if width >= SIDE_BY_SIDE_WIDTH {
+        let body_width = width - SCORES_WIDTH - 1;
+        let rows = panel_rows(&body, body_width).max(panel_rows(&scores, SCORES_WIDTH));
+
+        let left = panel(title, &body, body_width, rows);
+        let right = panel(&scores_title, &scores, SCORES_WIDTH, rows);
+        lines.extend(left.into_iter().zip(right).map(|(l, r)| l + " " + &r));
+    } else {
+        lines.extend(panel(title, &body, width, 0));
+        lines.extend(panel(&scores_title, &scores, width, 0));
+    }
+
+    lines.truncate(height.saturating_sub(1));
+    lines.push(styled(&pad(&hint.text, width), hint.style));
+
+    lines
+}
+
+/// A box `width` wide around `body`, with at least `min_rows` rows inside.
+fn panel(title: &str, body: &[Line], width: usize, min_rows: usize) -> Vec<String> {
+    let inner = width - 4;
+    let title: String = title.chars().take(width - 6).collect();
+
+    let mut lines = vec![format!(
+        "┌─ {} {}┐",
+        title,
+        "─".repeat(width - 5 - title.chars().count())
+    )];
+    for line in body {
+        for piece in wrap(&line.text, inner) {
+            lines.push(format!("│ {} │", styled(&pad(&piece, inner), line.style)));
+        }
+    }
+    while lines.len() < min_rows + 1 {
+        lines.push(format!("│ {} │", " ".repeat(inner)));
+    }
+    lines.push(format!("└{}┘", "─".repeat(width - 2)));
+
+    lines
+}
+
+fn panel_rows(body: &[Line], width: usize) -> usize {
+    body.iter()
+        .map(|line| wrap(&line.text, width - 4).len())
+        .sum()
+}
+
+/// Break `text` into lines of at most `width` characters, between words where possible.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+
+        loop {
+            let len = line.chars().count();
+            if len + word.len() + (len > 0) as usize <= width {
+                if len > 0 {
+                    line.push(' ');
+                }
+                line.extend(word);
+                break;
+            }
+            if len > 0 {
+                lines.push(std::mem::take(&mut line));
+                continue;
+            }
+
+            let rest = word.split_off(width);
+            lines.push(word.into_iter().collect());
+            word = rest;
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Cut or fill `text` to exactly `width` characters.
+fn pad(text: &str, width: usize) -> String {
+    let text: String = text.chars().take(width).collect();
+    let len = text.chars().count();
+
+    text + &" ".repeat(width - len)
+}
+
+fn styled(text: &str, style: Style) -> String {
+    match style {
+        Style::Normal => text.to_owned(),
+        Style::Cursor => text.reversed().to_string(),
+        Style::Picked => text.green().bold().to_string(),
+        Style::Error => text.red().to_string(),
+        Style::Hint => text.dimmed().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Without the ANSI escapes.
+    fn visible(line: &str) -> String {
+        let mut visible = String::new();
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                visible.push(c);
+            }
+        }
+
+        visible
+    }
+
+    fn round() -> Round<'static> {
+        Round {
+            locale: Locale::English,
+            first_index: 1,
+            number: 2,
+            judge: "Alex",
+            question: "_ and _ are the best things in the world.",
+            scores: vec![("Alex".to_owned(), 1), ("Blake".to_owned(), 0)],
+        }
+    }
+
+    #[test]
+    fn preview_fills_blanks_in_order() {
+        let question = "_ and _ are the best things in the world.";
+
+        assert_eq!(preview(question, &[]), question);
+        assert_eq!(
+            preview(question, &["video"]),
+            "**video** and _ are the best things in the world."
+        );
+        assert_eq!(
+            preview(question, &["video", "blood"]),
+            "**video** and **blood** are the best things in the world."
+        );
+        assert_eq!(count_blanks(question), 2);
+    }
+
+    #[test]
+    fn pick_until_every_blank_is_filled() {
+        let hand: Vec<String> = ["video", "blood", "cake"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        let mut picking = Picking::default();
+
+        assert_eq!(picking.press(Key::Up, hand.len(), 2), None);
+        assert_eq!(picking.previewed(&hand, 2), vec!["cake"]);
+        assert_eq!(picking.press(Key::Enter, hand.len(), 2), None);
+        assert_eq!(picking.previewed(&hand, 2), vec!["cake"]);
+        assert_eq!(picking.press(Key::Down, hand.len(), 2), None);
+        assert_eq!(picking.previewed(&hand, 2), vec!["cake", "video"]);
+        assert_eq!(picking.press(Key::Enter, hand.len(), 2), Some(vec![2, 0]));
+    }
+
+    #[test]
+    fn short_hands_cannot_be_picked_from() {
+        let mut picking = Picking::default();
+        assert!(picking.previewed(&[], 1).is_empty());
+        for key in [Key::Up, Key::Down, Key::Space, Key::Enter] {
+            assert_eq!(picking.press(key, 0, 1), None);
+        }
+
+        let hand = vec!["video".to_owned()];
+        for key in [Key::Space, Key::Enter] {
+            assert_eq!(picking.press(key, hand.len(), 2), None);
+        }
+        assert!(picking.picked.is_empty());
+        assert_eq!(picking.previewed(&hand, 2), vec!["video"]);
+    }
+
+    #[test]
+    fn wrap_between_words() {
+        assert_eq!(wrap("a bb ccc", 4), vec!["a bb", "ccc"]);
+        assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("", 4), vec![""]);
+    }
+
+    #[test]
+    fn every_line_fills_the_width() {
+        let long_card = "a card with so many words that it cannot fit on one line of the hand";
+
+        for (width, height) in [(100, 40), (60, 30), (59, 30), (30, 40), (5, 40)] {
+            let body = vec![
+                Line::new(long_card, Style::Cursor),
+                Line::new("video", Style::Picked),
+            ];
+            let hint = Line::new("↑↓", Style::Hint);
+            let lines = layout(
+                &round(),
+                "**video** and _",
+                "Blake's hand",
+                body,
+                hint,
+                width,
+                height,
+            );
+
+            assert!(lines.len() <= height);
+            for line in lines {
+                assert_eq!(
+                    visible(&line).chars().count(),
+                    width.max(MIN_WIDTH),
+                    "{:?} at {} x {}",
+                    line,
+                    width,
+                    height
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn the_hint_stays_on_small_screens() {
+        let body = (0..30).map(|i| Line::new(i.to_string(), Style::Normal));
+        let lines = layout(
+            &round(),
+            "_",
+            "Blake's hand",
+            body.collect(),
+            Line::new("press q", Style::Hint),
+            80,
+            10,
+        );
+
+        assert_eq!(lines.len(), 10);
+        assert!(visible(&lines[9]).starts_with("press q"));
+    }
+}
@@ -0,0 +1,88 @@
+//! Whole games played by `cli-app script`: each `tests/scripts/<name>.txt` must print exactly
+//! `tests/scripts/<name>.transcript`. Run with `UPDATE_TRANSCRIPTS=1` to write them anew.
+
+use std::{
+    env,
+    fs::{self, File},
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+#[test]
+fn scripts_print_their_transcripts() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let scripts = directory.join("tests/scripts");
+    let update = env::var_os("UPDATE_TRANSCRIPTS").is_some();
+
+    let mut paths: Vec<_> = fs::read_dir(&scripts)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+
+    let mut mismatches = vec![];
+    for script in paths {
+        let transcript = run(directory, &script);
+        let golden = script.with_extension("transcript");
+
+        if update {
+            fs::write(&golden, transcript).unwrap();
+        } else if fs::read_to_string(&golden).ok().as_deref() != Some(transcript.as_str()) {
+            mismatches.push(script.file_name().unwrap().to_string_lossy().into_owned());
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "These scripts printed something other than their transcripts: {:?}",
+        mismatches
+    );
+}
+
+#[test]
+fn fail_when_the_input_ends_during_a_game() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bot-against-humanity-cli-app"))
+        .arg("script")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"/add Ann\n/add Bob\n/add Cat\n/start\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("The input ended"));
+}
+
+/// Errors go to the same file as the rest, so they show where they happened.
+fn run(directory: &Path, script: &Path) -> String {
+    let output_path = env::temp_dir().join(format!(
+        "cli-app-{}-{}",
+        std::process::id(),
+        script.file_stem().unwrap().to_string_lossy()
+    ));
+    let output = File::create(&output_path).unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_bot-against-humanity-cli-app"))
+        .arg("script")
+        .arg(script.strip_prefix(directory).unwrap())
+        .current_dir(directory)
+        .stdout(output.try_clone().unwrap())
+        .stderr(output)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let transcript = fs::read_to_string(&output_path).unwrap();
+    fs::remove_file(&output_path).unwrap();
+    transcript
+}
@@ -1,7 +1,8 @@
-use super::card::Card;
+use super::card::{content_id, Card};
 
 #[derive(Clone, Debug)]
 pub(crate) struct AnswerCard {
+    pub(crate) id: String,
     pub(crate) content: String,
 }
 
@@ -15,8 +16,11 @@ impl From<String> for AnswerCard {
 
 impl AnswerCard {
     pub(crate) fn new(content: impl Into<String>) -> Self {
+        let content = content.into();
+
         Self {
-            content: content.into(),
+            id: content_id("a", &content),
+            content,
         }
     }
 }
@@ -50,6 +54,13 @@ mod tests {
         assert_eq!(answer_card.content, "Hello World");
     }
 
+    #[test]
+    fn id_from_content() {
+        assert_eq!(AnswerCard::new("Hello").id, AnswerCard::new("Hello").id);
+        assert_ne!(AnswerCard::new("Hello").id, AnswerCard::new("World").id);
+        assert!(AnswerCard::new("Hello").id.starts_with("a-"));
+    }
+
     #[test]
     fn card_storage() {
         let mut card_storage = CardStorage::new();
@@ -1 +1,30 @@
 pub(crate) trait Card {}
+
+/// A stable ID derived from the content of a card (64-bit FNV-1a), e.g. `a-af63dc4c8601ec8c` for "a".
+///
+/// The same content always gets the same ID, across games and platforms.
+pub(crate) fn content_id(prefix: &str, content: &str) -> String {
+    let hash = content
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+
+    format!("{}-{:016x}", prefix, hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_id_is_stable() {
+        assert_eq!(content_id("a", ""), "a-cbf29ce484222325");
+        assert_eq!(content_id("a", "a"), "a-af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn content_id_differs_by_content() {
+        assert_ne!(content_id("a", "Hello"), content_id("a", "Hello "));
+    }
+}
@@ -1,4 +1,5 @@
-use rand::{prelude::SliceRandom, thread_rng};
+use rand::{prelude::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 use super::card::Card;
 
@@ -9,6 +10,7 @@ where
 {
     deck: Vec<C>,
     discard_pile: Vec<C>,
+    rng: ChaCha8Rng,
 }
 
 impl<C> CardStorage<C>
@@ -21,13 +23,19 @@ where
         Self {
             deck: vec![],
             discard_pile: vec![],
+            rng: ChaCha8Rng::from_entropy(),
         }
     }
 
-    pub(crate) fn from_cards(cards: impl IntoIterator<Item = impl Into<C>>) -> Self {
+    /// `rng` shuffles the deck from then on.
+    pub(crate) fn from_cards(
+        cards: impl IntoIterator<Item = impl Into<C>>,
+        rng: ChaCha8Rng,
+    ) -> Self {
         Self {
             deck: cards.into_iter().map(|c| c.into()).collect(),
             discard_pile: vec![],
+            rng,
         }
     }
 
@@ -37,6 +45,14 @@ where
         self.deck.len() + self.discard_pile.len()
     }
 
+    pub(crate) fn num_cards_in_deck(&self) -> usize {
+        self.deck.len()
+    }
+
+    pub(crate) fn num_cards_in_discard_pile(&self) -> usize {
+        self.discard_pile.len()
+    }
+
     pub(crate) fn add_card_to_deck(&mut self, card: C) {
         self.deck.push(card);
     }
@@ -60,14 +76,18 @@ where
         })
     }
 
+    /// Unlike `draw_card_from_deck()`, the discard pile is never touched.
+    pub(crate) fn draw_card_without_recycling(&mut self) -> Option<C> {
+        self.deck.pop()
+    }
+
     pub(crate) fn shuffle_deck(&mut self) {
-        self.deck.shuffle(&mut thread_rng());
+        self.deck.shuffle(&mut self.rng);
     }
 
     /// Move all cards from the discard pile to the deck and shuffle the deck.
     pub(crate) fn refill_deck_and_shuffle(&mut self) {
-        while !self.discard_pile.is_empty() {
-            let card = self.discard_pile.pop().unwrap();
+        while let Some(card) = self.discard_pile.pop() {
             self.add_card_to_deck(card);
         }
         self.shuffle_deck()
@@ -144,6 +164,18 @@ mod tests {
         assert!(draw_card_result.is_some());
     }
 
+    #[test]
+    fn draw_a_card_without_recycling() {
+        let mut card_storage = CardStorage::new();
+        card_storage.add_card_to_deck(DummyCard { id: 0 });
+        card_storage.discard_card(DummyCard { id: 1 });
+
+        assert_eq!(card_storage.draw_card_without_recycling().unwrap().id, 0);
+        assert!(card_storage.draw_card_without_recycling().is_none());
+        assert_eq!(card_storage.num_cards_in_deck(), 0);
+        assert_eq!(card_storage.num_cards_total(), 1);
+    }
+
     #[test]
     fn shuffle_deck() {
         let mut card_storage = CardStorage::new();
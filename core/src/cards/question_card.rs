@@ -4,10 +4,11 @@ use regex::Regex;
 
 use crate::error::GameCoreError;
 
-use super::card::Card;
+use super::card::{content_id, Card};
 
 #[derive(Clone, Debug)]
 pub(crate) struct QuestionCard {
+    pub(crate) id: String,
     tokens: Vec<QuestionToken>,
 }
 
@@ -45,6 +46,7 @@ impl QuestionCard {
     pub(crate) fn new(question: impl Into<String>) -> Self {
         let underscores_regex = Regex::new("_+").unwrap();
         let question = question.into();
+        let id = content_id("q", &question);
 
         if underscores_regex.is_match(&question) {
             let tokens = underscores_regex
@@ -55,6 +57,7 @@ impl QuestionCard {
             let num_tokens_with_extra_none = tokens.len();
 
             Self {
+                id,
                 tokens: tokens
                     .into_iter()
                     .take(num_tokens_with_extra_none - 1)
@@ -62,6 +65,7 @@ impl QuestionCard {
             }
         } else {
             Self {
+                id,
                 tokens: vec![Some(question + " "), None],
             }
         }
@@ -196,6 +200,15 @@ mod tests {
         assert_eq!(question_card.num_blanks(), 2);
     }
 
+    #[test]
+    fn id_from_original_text() {
+        let question_card = QuestionCard::new("How are __?");
+
+        assert_eq!(question_card.id, QuestionCard::new("How are __?").id);
+        assert_ne!(question_card.id, QuestionCard::new("How are _?").id);
+        assert!(question_card.id.starts_with("q-"));
+    }
+
     #[test]
     fn display_question() {
         let question_card = QuestionCard::new("Who are you?");
@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::GameCoreError,
+    game_state::{ExhaustionPolicy, JudgeStrategy, DEFAULT_MAX_PLAYERS, DEFAULT_MIN_PLAYERS},
+};
+
+/// Names accepted by `GameConfig::preset()`.
+pub const PRESET_NAMES: [&str; 3] = ["Classic", "Quick game", "Party"];
+
+/// Every setting of a game, in one place so that it can be saved and shared as JSON.
+///
+/// Fields missing from a JSON config take their values from the "Classic" preset.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameConfig {
+    pub hand_size: usize,
+    /// Awesome Points needed to win. Frontends declare the winner.
+    pub win_target: usize,
+    pub min_players: usize,
+    pub max_players: usize,
+    pub judge_strategy: JudgeStrategy,
+    pub exhaustion_policy: ExhaustionPolicy,
+    pub allow_redraws: bool,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl GameConfig {
+    pub fn classic() -> Self {
+        Self {
+            hand_size: 10,
+            win_target: 5,
+            min_players: DEFAULT_MIN_PLAYERS,
+            max_players: DEFAULT_MAX_PLAYERS,
+            judge_strategy: JudgeStrategy::RoundRobin,
+            exhaustion_policy: ExhaustionPolicy::Recycle,
+            allow_redraws: true,
+        }
+    }
+
+    /// Small hands and a low win target for a short session.
+    pub fn quick_game() -> Self {
+        Self {
+            hand_size: 7,
+            win_target: 3,
+            max_players: 6,
+            allow_redraws: false,
+            ..Self::classic()
+        }
+    }
+
+    /// Big hands and a random Judge every round.
+    pub fn party() -> Self {
+        Self {
+            hand_size: 15,
+            judge_strategy: JudgeStrategy::Random,
+            ..Self::classic()
+        }
+    }
+
+    /// Look up one of `PRESET_NAMES`, ignoring case.
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "classic" => Some(Self::classic()),
+            "quick game" => Some(Self::quick_game()),
+            "party" => Some(Self::party()),
+            _ => None,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, GameCoreError> {
+        let config: Self =
+            serde_json::from_str(json).map_err(|err| GameCoreError::InvalidConfig {
+                reason: err.to_string(),
+            })?;
+
+        config.check()?;
+
+        Ok(config)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Return Err() if the settings contradict each other or cannot make a game.
+    pub fn check(&self) -> Result<(), GameCoreError> {
+        if self.hand_size == 0 {
+            return Err(GameCoreError::HandSizeCannotBeZero);
+        }
+
+        if self.win_target == 0 {
+            return Err(GameCoreError::InvalidConfig {
+                reason: "The win target cannot be zero.".to_owned(),
+            });
+        }
+
+        if self.min_players < 2 || self.min_players > self.max_players {
+            return Err(GameCoreError::InvalidPlayerBounds {
+                min_players: self.min_players,
+                max_players: self.max_players,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_valid() {
+        for name in PRESET_NAMES {
+            let config = GameConfig::preset(name).unwrap();
+
+            assert!(config.check().is_ok(), "{} should be valid", name);
+        }
+        assert_eq!(
+            GameConfig::preset("QUICK GAME"),
+            Some(GameConfig::quick_game())
+        );
+        assert_eq!(GameConfig::preset("Marathon"), None);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let config = GameConfig::party();
+
+        assert_eq!(GameConfig::from_json(&config.to_json()).unwrap(), config);
+    }
+
+    #[test]
+    fn json_uses_camel_case() {
+        let json = GameConfig::classic().to_json();
+
+        assert!(json.contains("\"handSize\": 10"));
+        assert!(json.contains("\"judgeStrategy\": \"roundRobin\""));
+        assert!(json.contains("\"exhaustionPolicy\": \"recycle\""));
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_classic() {
+        let config =
+            GameConfig::from_json(r#"{ "winTarget": 8, "judgeStrategy": "random" }"#).unwrap();
+
+        assert_eq!(
+            config,
+            GameConfig {
+                win_target: 8,
+                judge_strategy: JudgeStrategy::Random,
+                ..GameConfig::classic()
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(matches!(
+            GameConfig::from_json(r#"{ "handSize": "ten" }"#),
+            Err(GameCoreError::InvalidConfig { .. })
+        ));
+        assert!(matches!(
+            GameConfig::from_json(r#"{ "judgeStrategy": "oldest" }"#),
+            Err(GameCoreError::InvalidConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn inconsistent_settings() {
+        assert_eq!(
+            GameConfig::from_json(r#"{ "handSize": 0 }"#),
+            Err(GameCoreError::HandSizeCannotBeZero)
+        );
+        assert_eq!(
+            GameConfig::from_json(r#"{ "minPlayers": 8, "maxPlayers": 6 }"#),
+            Err(GameCoreError::InvalidPlayerBounds {
+                min_players: 8,
+                max_players: 6
+            })
+        );
+        assert!(GameConfig::from_json(r#"{ "winTarget": 0 }"#).is_err());
+    }
+}
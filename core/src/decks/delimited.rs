@@ -0,0 +1,488 @@
+use crate::error::GameCoreError;
+
+use super::{Deck, DeckAnswer, DeckImport, DeckImportIssue, DeckQuestion};
+
+const COLUMNS: [&str; 5] = ["type", "text", "pack", "pick", "tags"];
+const TAG_SEPARATOR: char = ';';
+
+/// Parse a deck from comma-separated values.
+///
+/// The first row names the columns: `type` and `text` are required, `pack`, `pick` and `tags`
+/// are optional, and the order is free. `type` is `question` (or `black`) or `answer` (or `white`).
+/// Tags are separated by semicolons; `\;` and `\\` stand for a semicolon and a backslash in a tag.
+/// Fields may be quoted as in RFC 4180.
+///
+/// Malformed rows are skipped and reported with their line numbers in `issues`.
+/// Err() is returned only when the header is unusable or a quoted field never ends.
+pub fn parse_csv(text: &str) -> Result<DeckImport, GameCoreError> {
+    parse_records(split_records(text, ',')?)
+}
+
+/// Parse a deck from tab-separated values, as spreadsheets export them: one record per line and
+/// no quoting. `\t`, `\n`, `\r` and `\\` stand for a tab, a line break, a carriage return and a
+/// backslash. See `parse_csv()` for the columns.
+pub fn parse_tsv(text: &str) -> Result<DeckImport, GameCoreError> {
+    parse_records(split_lines(text))
+}
+
+/// Write a deck as comma-separated values, readable by `parse_csv()`.
+pub fn to_csv(deck: &Deck) -> String {
+    write_delimited(deck, ',')
+}
+
+/// Write a deck as tab-separated values, readable by `parse_tsv()`.
+pub fn to_tsv(deck: &Deck) -> String {
+    write_delimited(deck, '\t')
+}
+
+enum CardType {
+    Question,
+    Answer,
+}
+
+struct Record {
+    /// ONE-based line number where the record starts.
+    line: usize,
+    fields: Vec<String>,
+}
+
+struct Columns {
+    card_type: usize,
+    text: usize,
+    pack: Option<usize>,
+    pick: Option<usize>,
+    tags: Option<usize>,
+}
+
+fn parse_records(records: Vec<Record>) -> Result<DeckImport, GameCoreError> {
+    let mut records = records.into_iter();
+
+    let header = if let Some(header) = records.next() {
+        header
+    } else {
+        return Err(GameCoreError::InvalidDeck {
+            reason: "There is no header row.".to_owned(),
+        });
+    };
+    let columns = Columns::from_header(&header)?;
+
+    let mut import = DeckImport::default();
+    let mut report = |line: usize, reason: String| {
+        import.issues.push(DeckImportIssue {
+            location: format!("line {}", line),
+            reason,
+        })
+    };
+    let mut deck = Deck::default();
+
+    for record in records {
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|ind| record.fields.get(ind))
+                .map(|f| f.trim())
+                .unwrap_or_default()
+        };
+
+        let card_type = match field(Some(columns.card_type)).to_lowercase().as_str() {
+            "question" | "black" => CardType::Question,
+            "answer" | "white" => CardType::Answer,
+            other => {
+                report(record.line, format!("Unknown card type `{}`.", other));
+                continue;
+            }
+        };
+
+        let text = field(Some(columns.text)).to_owned();
+        if text.is_empty() {
+            report(record.line, "The card is empty.".to_owned());
+            continue;
+        }
+
+        let pack = Some(field(columns.pack))
+            .filter(|p| !p.is_empty())
+            .map(str::to_owned);
+        let tags = split_tags(field(columns.tags));
+
+        match card_type {
+            CardType::Question => {
+                let mut question = DeckQuestion {
+                    text,
+                    pick: 1,
+                    pack,
+                    tags,
+                };
+
+                question.pick = match field(columns.pick) {
+                    "" => question.num_blanks().max(1),
+                    pick => match pick.parse::<usize>() {
+                        Ok(pick) if pick >= 1 => pick,
+                        _ => {
+                            report(record.line, "`pick` must be a positive integer.".to_owned());
+                            continue;
+                        }
+                    },
+                };
+
+                if let Some(reason) = question.reconcile_pick() {
+                    report(record.line, reason);
+                }
+
+                deck.questions.push(question);
+            }
+            CardType::Answer => deck.answers.push(DeckAnswer { text, pack, tags }),
+        }
+    }
+
+    import.deck = deck;
+
+    Ok(import)
+}
+
+impl Columns {
+    fn from_header(header: &Record) -> Result<Self, GameCoreError> {
+        let find = |name: &str| {
+            header
+                .fields
+                .iter()
+                .position(|f| f.trim().eq_ignore_ascii_case(name))
+        };
+
+        let required = |name: &str| {
+            find(name).ok_or_else(|| GameCoreError::InvalidDeck {
+                reason: format!("line {}: The `{}` column is missing.", header.line, name),
+            })
+        };
+
+        Ok(Self {
+            card_type: required("type")?,
+            text: required("text")?,
+            pack: find("pack"),
+            pick: find("pick"),
+            tags: find("tags"),
+        })
+    }
+}
+
+/// Split delimited text into records of fields as in RFC 4180, skipping blank lines.
+fn split_records(text: &str, delimiter: char) -> Result<Vec<Record>, GameCoreError> {
+    let mut records = vec![];
+
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut field_was_quoted = false;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() && !field_was_quoted => {
+                in_quotes = true;
+                field_was_quoted = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                push_record(&mut records, record_line, std::mem::take(&mut fields));
+                field_was_quoted = false;
+                line += 1;
+                record_line = line;
+            }
+            c if c == delimiter => {
+                fields.push(std::mem::take(&mut field));
+                field_was_quoted = false;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(GameCoreError::InvalidDeck {
+            reason: format!("line {}: A quoted field is never closed.", record_line),
+        });
+    }
+
+    fields.push(field);
+    push_record(&mut records, record_line, fields);
+
+    Ok(records)
+}
+
+/// Split tab-separated text into records, skipping blank lines.
+fn split_lines(text: &str) -> Vec<Record> {
+    let mut records = vec![];
+
+    for (i, line) in text.lines().enumerate() {
+        let fields = line.split('\t').map(unescape).collect();
+        push_record(&mut records, i + 1, fields);
+    }
+
+    records
+}
+
+fn push_record(records: &mut Vec<Record>, line: usize, fields: Vec<String>) {
+    let is_blank = fields.iter().all(|f| f.trim().is_empty());
+
+    if !is_blank {
+        records.push(Record { line, fields });
+    }
+}
+
+fn write_delimited(deck: &Deck, delimiter: char) -> String {
+    let mut rows = vec![COLUMNS.iter().map(|&c| c.to_owned()).collect::<Vec<_>>()];
+
+    for question in deck.questions.iter() {
+        rows.push(vec![
+            "question".to_owned(),
+            question.text.clone(),
+            question.pack.clone().unwrap_or_default(),
+            question.pick.to_string(),
+            join_tags(&question.tags),
+        ]);
+    }
+
+    for answer in deck.answers.iter() {
+        rows.push(vec![
+            "answer".to_owned(),
+            answer.text.clone(),
+            answer.pack.clone().unwrap_or_default(),
+            String::new(),
+            join_tags(&answer.tags),
+        ]);
+    }
+
+    rows.into_iter()
+        .map(|row| {
+            row.iter()
+                .map(|f| format_field(f, delimiter))
+                .collect::<Vec<_>>()
+                .join(&delimiter.to_string())
+                + "\n"
+        })
+        .collect()
+}
+
+/// Split at the separators that are not escaped, and unescape each tag.
+fn split_tags(field: &str) -> Vec<String> {
+    let mut tags = vec![];
+    let mut tag = String::new();
+
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => tag.extend(chars.next()),
+            TAG_SEPARATOR => tags.push(std::mem::take(&mut tag)),
+            _ => tag.push(c),
+        }
+    }
+    tags.push(tag);
+
+    tags.into_iter()
+        .map(|t| t.trim().to_owned())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn join_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|t| {
+            t.replace('\\', "\\\\")
+                .replace(TAG_SEPARATOR, &format!("\\{}", TAG_SEPARATOR))
+        })
+        .collect::<Vec<_>>()
+        .join(&TAG_SEPARATOR.to_string())
+}
+
+fn format_field(field: &str, delimiter: char) -> String {
+    if delimiter == '\t' {
+        escape(field)
+    } else if field.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Write what cannot be in a field of tab-separated values as `unescape()` reads it.
+fn escape(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Read `\t`, `\n`, `\r` and `\\`, and keep any other backslash as it is.
+fn unescape(field: &str) -> String {
+    let mut unescaped = String::new();
+
+    let mut chars = field.chars().peekable();
+    while let Some(c) = chars.next() {
+        let escaped = match (c, chars.peek()) {
+            ('\\', Some('t')) => '\t',
+            ('\\', Some('n')) => '\n',
+            ('\\', Some('r')) => '\r',
+            ('\\', Some('\\')) => '\\',
+            _ => {
+                unescaped.push(c);
+                continue;
+            }
+        };
+        chars.next();
+        unescaped.push(escaped);
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_csv_rows() {
+        let csv = "type,text,pack,pick,tags\n\
+                   question,I only believe in _.,Base,1,religion;short\n\
+                   answer,\"Zombies, obviously\",Base,,\n\
+                   white,blood,,,\n";
+
+        let import = parse_csv(csv).unwrap();
+
+        assert!(import.issues.is_empty());
+        assert_eq!(import.deck.questions.len(), 1);
+        assert_eq!(import.deck.questions[0].tags, vec!["religion", "short"]);
+        assert_eq!(
+            import.deck.answer_texts(),
+            vec!["Zombies, obviously", "blood"]
+        );
+        assert_eq!(import.deck.answers[0].pack.as_deref(), Some("Base"));
+        assert_eq!(import.deck.answers[1].pack, None);
+    }
+
+    #[test]
+    fn parse_tsv_rows_in_any_column_order() {
+        let tsv = "Text\tType\tPick\n\
+                   Make a haiku.\tblack\t3\n\
+                   tennis ball\tanswer\t\n";
+
+        let import = parse_tsv(tsv).unwrap();
+
+        assert!(import.issues.is_empty());
+        assert_eq!(import.deck.questions[0].pick, 3);
+        assert_eq!(import.deck.answer_texts(), vec!["tennis ball"]);
+    }
+
+    #[test]
+    fn parse_tsv_without_quotes() {
+        let tsv = "type\ttext\n\
+                   answer\t\"Quoted\" thing\n\
+                   answer\t\"never closed\n\
+                   answer\tC:\\Games\n";
+
+        let import = parse_tsv(tsv).unwrap();
+
+        assert!(import.issues.is_empty());
+        assert_eq!(
+            import.deck.answer_texts(),
+            vec!["\"Quoted\" thing", "\"never closed", "C:\\Games"]
+        );
+    }
+
+    #[test]
+    fn report_line_numbers() {
+        let csv = "type,text,pick\r\n\
+                   question,\"A question\nover two lines _\",1\r\n\
+                   \r\n\
+                   joker,Who?,1\r\n\
+                   answer,   ,\r\n\
+                   question,Who?,zero\r\n\
+                   question,_ and _.,1\r\n";
+
+        let import = parse_csv(csv).unwrap();
+        let locations = import
+            .issues
+            .iter()
+            .map(|issue| issue.location.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(locations, vec!["line 5", "line 6", "line 7", "line 8"]);
+        assert_eq!(import.deck.questions.len(), 2);
+        assert_eq!(
+            import.deck.questions[0].text,
+            "A question\nover two lines _"
+        );
+        assert_eq!(import.deck.questions[1].pick, 2);
+    }
+
+    #[test]
+    fn missing_required_column() {
+        assert_eq!(
+            parse_csv("type,pack\nanswer,Base\n").err().unwrap(),
+            GameCoreError::InvalidDeck {
+                reason: "line 1: The `text` column is missing.".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn unclosed_quote() {
+        assert_eq!(
+            parse_csv("type,text\nanswer,ok\nanswer,\"never closed\n")
+                .err()
+                .unwrap(),
+            GameCoreError::InvalidDeck {
+                reason: "line 3: A quoted field is never closed.".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn empty_input() {
+        assert!(parse_csv("").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let deck = Deck {
+            questions: vec![DeckQuestion {
+                text: "Say \"hi\", then _.".to_owned(),
+                pick: 1,
+                pack: Some("Base".to_owned()),
+                tags: vec!["greeting".to_owned()],
+            }],
+            answers: vec![
+                DeckAnswer {
+                    text: "a\ttab, and a comma".to_owned(),
+                    pack: None,
+                    tags: vec!["odd; or not".to_owned(), "back\\slash".to_owned()],
+                },
+                DeckAnswer {
+                    text: "\"Quoted\" thing\nover \\n two lines".to_owned(),
+                    pack: None,
+                    tags: vec![],
+                },
+            ],
+        };
+
+        assert_eq!(parse_csv(&to_csv(&deck)).unwrap().deck, deck);
+        assert_eq!(parse_tsv(&to_tsv(&deck)).unwrap().deck, deck);
+    }
+}
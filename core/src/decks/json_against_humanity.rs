@@ -0,0 +1,353 @@
+use serde_json::{Map, Value};
+
+use crate::error::GameCoreError;
+
+use super::{DeckAnswer, DeckImport, DeckImportIssue, DeckQuestion};
+
+/// Parse a deck in the JSON Against Humanity format.
+///
+/// Both layouts of the format are accepted:
+/// - compact: `{ "white": [..], "black": [..], "packs": [{ "name", "white": [index], "black": [index] }] }`,
+///   where the older `"metadata": { key: { "name", "white", "black" } }` grouping also works.
+/// - full: `[{ "name", "white": [{ "text" }], "black": [{ "text", "pick" }] }]`
+///
+/// Malformed entries are skipped (or kept, if they are still playable) and reported in `issues`.
+/// Err() is returned only when the input is not JSON or is in neither layout.
+pub fn parse_json_against_humanity(json: &str) -> Result<DeckImport, GameCoreError> {
+    let value: Value = serde_json::from_str(json).map_err(|e| GameCoreError::InvalidDeck {
+        reason: e.to_string(),
+    })?;
+
+    let mut importer = Importer::default();
+
+    match value {
+        Value::Array(packs) => importer.import_full(&packs),
+        Value::Object(object) => importer.import_compact(&object)?,
+        _ => {
+            return Err(GameCoreError::InvalidDeck {
+                reason: "Expected an object or an array of packs.".to_owned(),
+            })
+        }
+    }
+
+    Ok(importer.import)
+}
+
+#[derive(Default)]
+struct Importer {
+    import: DeckImport,
+}
+
+impl Importer {
+    fn report(&mut self, location: impl Into<String>, reason: impl Into<String>) {
+        self.import.issues.push(DeckImportIssue {
+            location: location.into(),
+            reason: reason.into(),
+        });
+    }
+
+    fn import_full(&mut self, packs: &[Value]) {
+        for (i, pack) in packs.iter().enumerate() {
+            let location = format!("[{}]", i);
+            let pack = if let Some(pack) = pack.as_object() {
+                pack
+            } else {
+                self.report(location, "A pack must be an object.");
+                continue;
+            };
+
+            let pack_name = pack.get("name").and_then(Value::as_str).map(str::to_owned);
+            if pack_name.is_none() {
+                self.report(format!("{}.name", location), "The pack has no name.");
+            }
+
+            for (j, entry) in array_field(pack, "white").iter().enumerate() {
+                if let Some(text) = self.parse_white(entry, &format!("{}.white[{}]", location, j)) {
+                    self.import.deck.answers.push(DeckAnswer {
+                        text,
+                        pack: pack_name.clone(),
+                        tags: vec![],
+                    });
+                }
+            }
+
+            for (j, entry) in array_field(pack, "black").iter().enumerate() {
+                if let Some((text, pick)) =
+                    self.parse_black(entry, &format!("{}.black[{}]", location, j))
+                {
+                    self.import.deck.questions.push(DeckQuestion {
+                        text,
+                        pick,
+                        pack: pack_name.clone(),
+                        tags: vec![],
+                    });
+                }
+            }
+        }
+    }
+
+    fn import_compact(&mut self, object: &Map<String, Value>) -> Result<(), GameCoreError> {
+        if !object.contains_key("white") && !object.contains_key("black") {
+            return Err(GameCoreError::InvalidDeck {
+                reason: "Expected `white` or `black` cards.".to_owned(),
+            });
+        }
+
+        let whites = array_field(object, "white");
+        let blacks = array_field(object, "black");
+        let mut white_packs: Vec<Option<String>> = vec![None; whites.len()];
+        let mut black_packs: Vec<Option<String>> = vec![None; blacks.len()];
+
+        for (location, pack) in pack_groupings(object) {
+            let pack_name = if let Some(name) = pack.get("name").and_then(Value::as_str) {
+                name.to_owned()
+            } else {
+                self.report(format!("{}.name", location), "The pack has no name.");
+                continue;
+            };
+
+            for (color, packs) in [("white", &mut white_packs), ("black", &mut black_packs)] {
+                for (j, index) in array_field(pack, color).iter().enumerate() {
+                    let index_location = format!("{}.{}[{}]", location, color, j);
+                    match index.as_u64().map(|ind| ind as usize) {
+                        Some(ind) if ind < packs.len() => {
+                            packs[ind].get_or_insert_with(|| pack_name.clone());
+                        }
+                        _ => self.report(
+                            index_location,
+                            format!("Not an index of the `{}` cards.", color),
+                        ),
+                    }
+                }
+            }
+        }
+
+        for (i, (entry, pack)) in whites.iter().zip(white_packs).enumerate() {
+            if let Some(text) = self.parse_white(entry, &format!("white[{}]", i)) {
+                self.import.deck.answers.push(DeckAnswer {
+                    text,
+                    pack,
+                    tags: vec![],
+                });
+            }
+        }
+
+        for (i, (entry, pack)) in blacks.iter().zip(black_packs).enumerate() {
+            if let Some((text, pick)) = self.parse_black(entry, &format!("black[{}]", i)) {
+                self.import.deck.questions.push(DeckQuestion {
+                    text,
+                    pick,
+                    pack,
+                    tags: vec![],
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A white card is either a string or an object with a `text` string.
+    fn parse_white(&mut self, entry: &Value, location: &str) -> Option<String> {
+        let text = entry
+            .as_str()
+            .or_else(|| entry.get("text").and_then(Value::as_str));
+
+        match text.map(str::trim) {
+            None => {
+                self.report(location, "Expected a string or an object with `text`.");
+                None
+            }
+            Some("") => {
+                self.report(location, "The card is empty.");
+                None
+            }
+            Some(text) => Some(text.to_owned()),
+        }
+    }
+
+    /// A black card is an object with a `text` string and an optional positive `pick`.
+    /// A plain string is also accepted.
+    fn parse_black(&mut self, entry: &Value, location: &str) -> Option<(String, usize)> {
+        let text = entry
+            .as_str()
+            .or_else(|| entry.get("text").and_then(Value::as_str))
+            .map(str::trim);
+
+        let text = match text {
+            None => {
+                self.report(location, "Expected an object with `text`.");
+                return None;
+            }
+            Some("") => {
+                self.report(location, "The card is empty.");
+                return None;
+            }
+            Some(text) => text.to_owned(),
+        };
+
+        let mut question = DeckQuestion {
+            text,
+            pick: 1,
+            pack: None,
+            tags: vec![],
+        };
+        let num_blanks = question.num_blanks();
+
+        question.pick = match entry.get("pick") {
+            None => num_blanks.max(1),
+            Some(pick) => match pick.as_u64() {
+                Some(pick) if pick >= 1 => pick as usize,
+                _ => {
+                    self.report(location, "`pick` must be a positive integer.");
+                    return None;
+                }
+            },
+        };
+
+        if let Some(reason) = question.reconcile_pick() {
+            self.report(location, reason);
+        }
+
+        Some((question.text, question.pick))
+    }
+}
+
+/// The pack objects of the compact layout, with their locations.
+fn pack_groupings(object: &Map<String, Value>) -> Vec<(String, &Map<String, Value>)> {
+    if let Some(packs) = object.get("packs").and_then(Value::as_array) {
+        packs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pack)| Some((format!("packs[{}]", i), pack.as_object()?)))
+            .collect()
+    } else if let Some(metadata) = object.get("metadata").and_then(Value::as_object) {
+        metadata
+            .iter()
+            .filter_map(|(key, pack)| Some((format!("metadata.{}", key), pack.as_object()?)))
+            .collect()
+    } else {
+        vec![]
+    }
+}
+
+fn array_field<'a>(object: &'a Map<String, Value>, field: &str) -> &'a [Value] {
+    object
+        .get(field)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_compact() {
+        let json = r#"{
+            "white": ["Zombie Apocalypse", { "text": "Winnie the Pooh" }, "blood"],
+            "black": [
+                { "text": "I only believe in _.", "pick": 1 },
+                { "text": "Make a haiku.", "pick": 3 }
+            ],
+            "packs": [
+                { "name": "Base", "white": [0, 1], "black": [0] },
+                { "name": "Extra", "white": [2], "black": [1] }
+            ]
+        }"#;
+
+        let import = parse_json_against_humanity(json).unwrap();
+
+        assert!(import.issues.is_empty());
+        assert_eq!(import.deck.answers.len(), 3);
+        assert_eq!(import.deck.answers[1].text, "Winnie the Pooh");
+        assert_eq!(import.deck.answers[2].pack.as_deref(), Some("Extra"));
+        assert_eq!(import.deck.questions[1].pick, 3);
+        assert_eq!(import.deck.questions[1].pack.as_deref(), Some("Extra"));
+        assert_eq!(import.deck.pack_names(), vec!["Base", "Extra"]);
+    }
+
+    #[test]
+    fn parse_compact_with_metadata() {
+        let json = r#"{
+            "white": ["a", "b"],
+            "black": [{ "text": "_?", "pick": 1 }],
+            "metadata": { "Base": { "name": "Base Set", "white": [0, 1], "black": [0] } }
+        }"#;
+
+        let import = parse_json_against_humanity(json).unwrap();
+
+        assert!(import.issues.is_empty());
+        assert_eq!(import.deck.pack_names(), vec!["Base Set"]);
+    }
+
+    #[test]
+    fn parse_full() {
+        let json = r#"[
+            {
+                "name": "Base",
+                "white": [{ "text": "a", "pack": 0 }, { "text": "b", "pack": 0 }],
+                "black": [{ "text": "_ and _.", "pick": 2, "pack": 0 }]
+            },
+            { "name": "Extra", "white": ["c"], "black": [] }
+        ]"#;
+
+        let import = parse_json_against_humanity(json).unwrap();
+
+        assert!(import.issues.is_empty());
+        assert_eq!(import.deck.answer_texts(), vec!["a", "b", "c"]);
+        assert_eq!(import.deck.questions[0].pick, 2);
+        assert_eq!(import.deck.pack_names(), vec!["Base", "Extra"]);
+    }
+
+    #[test]
+    fn report_malformed_entries() {
+        let json = r#"{
+            "white": ["fine", 42, "   "],
+            "black": [
+                { "pick": 1 },
+                { "text": "Who?", "pick": 0 },
+                { "text": "_ and _.", "pick": 1 }
+            ],
+            "packs": [{ "name": "Base", "white": [0, 7], "black": [] }]
+        }"#;
+
+        let import = parse_json_against_humanity(json).unwrap();
+        let locations = import
+            .issues
+            .iter()
+            .map(|issue| issue.location.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            locations,
+            vec![
+                "packs[0].white[1]",
+                "white[1]",
+                "white[2]",
+                "black[0]",
+                "black[1]",
+                "black[2]"
+            ]
+        );
+        assert_eq!(import.deck.answer_texts(), vec!["fine"]);
+        assert_eq!(import.deck.questions.len(), 1);
+        assert_eq!(import.deck.questions[0].pick, 2);
+    }
+
+    #[test]
+    fn not_json() {
+        assert!(matches!(
+            parse_json_against_humanity("white: a"),
+            Err(GameCoreError::InvalidDeck { .. })
+        ));
+    }
+
+    #[test]
+    fn neither_layout() {
+        assert!(matches!(
+            parse_json_against_humanity(r#"{ "questions": [] }"#),
+            Err(GameCoreError::InvalidDeck { .. })
+        ));
+    }
+}
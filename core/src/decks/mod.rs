@@ -0,0 +1,176 @@
+use regex::Regex;
+use serde::Serialize;
+
+pub mod delimited;
+pub mod json_against_humanity;
+
+pub use delimited::{parse_csv, parse_tsv, to_csv, to_tsv};
+pub use json_against_humanity::parse_json_against_humanity;
+
+/// Cards imported from an external source, before they are handed to a builder.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deck {
+    pub questions: Vec<DeckQuestion>,
+    pub answers: Vec<DeckAnswer>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeckQuestion {
+    pub text: String,
+    /// Number of Answer cards to be played on this question.
+    pub pick: usize,
+    pub pack: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeckAnswer {
+    pub text: String,
+    pub pack: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// An entry that was skipped or looks suspicious during an import.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeckImportIssue {
+    /// Where the entry was found, e.g. `black[12]`.
+    pub location: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeckImport {
+    pub deck: Deck,
+    pub issues: Vec<DeckImportIssue>,
+}
+
+impl DeckQuestion {
+    /// Number of blanks (runs of underscores) written in the text.
+    pub fn num_blanks(&self) -> usize {
+        Regex::new("_+").unwrap().find_iter(&self.text).count()
+    }
+
+    /// The text in the blank notation understood by the game.
+    ///
+    /// A question without blanks gets one blank per pick appended, so that "Make a haiku." with
+    /// a pick of 3 still asks for 3 Answer cards.
+    pub fn to_question_text(&self) -> String {
+        if self.pick > 1 && self.num_blanks() == 0 {
+            format!("{} {}", self.text, vec!["_"; self.pick].join(", "))
+        } else {
+            self.text.clone()
+        }
+    }
+
+    /// Make `pick` agree with the blanks written in the text, since the blanks are what the game
+    /// uses. Return the reason if they disagreed.
+    pub(crate) fn reconcile_pick(&mut self) -> Option<String> {
+        let num_blanks = self.num_blanks();
+
+        if num_blanks > 0 && num_blanks != self.pick {
+            let reason = format!(
+                "`pick` ({}) does not match the number of blanks ({}); the blanks are used.",
+                self.pick, num_blanks
+            );
+            self.pick = num_blanks;
+
+            Some(reason)
+        } else {
+            None
+        }
+    }
+}
+
+impl Deck {
+    /// Names of the packs in the order they first appear.
+    pub fn pack_names(&self) -> Vec<String> {
+        let mut pack_names: Vec<String> = vec![];
+
+        let packs = self
+            .questions
+            .iter()
+            .map(|q| &q.pack)
+            .chain(self.answers.iter().map(|a| &a.pack));
+        for pack in packs.flatten() {
+            if !pack_names.contains(pack) {
+                pack_names.push(pack.clone());
+            }
+        }
+
+        pack_names
+    }
+
+    pub fn question_texts(&self) -> Vec<String> {
+        self.questions
+            .iter()
+            .map(DeckQuestion::to_question_text)
+            .collect()
+    }
+
+    pub fn answer_texts(&self) -> Vec<String> {
+        self.answers.iter().map(|a| a.text.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn question(text: &str, pick: usize, pack: Option<&str>) -> DeckQuestion {
+        DeckQuestion {
+            text: text.to_owned(),
+            pick,
+            pack: pack.map(|p| p.to_owned()),
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn question_text_keeps_blanks() {
+        let q = question("_ and _ are the best.", 2, None);
+
+        assert_eq!(q.num_blanks(), 2);
+        assert_eq!(q.to_question_text(), "_ and _ are the best.");
+    }
+
+    #[test]
+    fn question_text_adds_blanks_for_pick() {
+        let q = question("Make a haiku.", 3, None);
+
+        assert_eq!(q.num_blanks(), 0);
+        assert_eq!(q.to_question_text(), "Make a haiku. _, _, _");
+    }
+
+    #[test]
+    fn question_text_single_pick_without_blanks() {
+        let q = question("Who am I?", 1, None);
+
+        assert_eq!(q.to_question_text(), "Who am I?");
+    }
+
+    #[test]
+    fn pack_names_in_order_of_appearance() {
+        let deck = Deck {
+            questions: vec![question("A?", 1, Some("Base")), question("B?", 1, None)],
+            answers: vec![
+                DeckAnswer {
+                    text: "a".to_owned(),
+                    pack: Some("Extra".to_owned()),
+                    tags: vec![],
+                },
+                DeckAnswer {
+                    text: "b".to_owned(),
+                    pack: Some("Base".to_owned()),
+                    tags: vec![],
+                },
+            ],
+        };
+
+        assert_eq!(deck.pack_names(), vec!["Base", "Extra"]);
+    }
+}
@@ -3,34 +3,66 @@ use std::collections::HashMap;
 use serde::Serialize;
 
 use crate::{
+    config::GameConfig,
+    decks::Deck,
     error::GameCoreError,
-    game_state::{GameState, GameStateBuilder},
+    events::{EventListeners, GameEvent, ListenerId},
+    game_state::{
+        AllSubmittedAnswers, BuildCheck, CardId, DeckSizes, ExhaustionPolicy, GameState,
+        GameStateBuilder, JudgeStrategy, ValidationReport,
+    },
+    rules::{default_rules, Judging, RoundStart, Rule, RuleEffects, Submission},
 };
 
-use super::GameCoreDriver;
+use super::{
+    views::{PlayerView, PublicView},
+    GameCoreDriver,
+};
 
+/// Players are identified by stable IDs (e.g. a Discord user ID), which every method takes as
+/// `player_name`. Each player also has a display name, which may change and need not be unique.
 pub struct GenericDriverBuilder {
     game_state_builder: GameStateBuilder,
-    hand_size_config: usize,
+    config: GameConfig,
+    rules: Vec<Box<dyn Rule>>,
+    display_names: HashMap<String, String>,
 }
 
 pub struct GenericDriver {
     game_state: GameState,
+    config: GameConfig,
+    rules: Vec<Box<dyn Rule>>,
+    display_names: HashMap<String, String>,
+
+    /// ONE-based; 0 before the first round.
+    round: usize,
+    judge: String,
+    question: String,
+    /// As shown to the Judge, once every player has submitted.
+    submissions: Vec<(String, String)>,
+
+    listeners: EventListeners,
+    game_over_emitted: bool,
 }
 
+/// What everyone may know when a round starts. Each hand is only in `view_for()`.
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RoundInformation {
     pub judge: String,
     pub question: String,
-    pub player_hands: HashMap<String, Vec<String>>,
+    pub question_id: CardId,
+    /// { player ID: display name }
+    pub display_names: HashMap<String, String>,
 }
 
 impl Default for GenericDriverBuilder {
     fn default() -> Self {
         Self {
             game_state_builder: Default::default(),
-            hand_size_config: 10,
+            config: Default::default(),
+            rules: default_rules(),
+            display_names: HashMap::new(),
         }
     }
 }
@@ -40,25 +72,151 @@ impl GenericDriverBuilder {
         Default::default()
     }
 
+    /// Rules run in the order they were added. The builder starts with `default_rules()`.
+    pub fn add_rule(&mut self, rule: impl Rule + 'static) {
+        self.rules.push(Box::new(rule));
+    }
+
+    /// Replace every rule, the default ones included.
+    pub fn set_rules(&mut self, rules: Vec<Box<dyn Rule>>) {
+        self.rules = rules;
+    }
+
+    pub fn rule_names(&self) -> Vec<String> {
+        self.rules.iter().map(|r| r.name().to_owned()).collect()
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// Replace every setting at once. Nothing changes if `config` is invalid.
+    pub fn set_config(&mut self, config: GameConfig) -> Result<(), GameCoreError> {
+        config.check()?;
+
+        self.game_state_builder
+            .set_player_bounds(config.min_players, config.max_players)?;
+        self.game_state_builder
+            .set_exhaustion_policy(config.exhaustion_policy);
+        self.game_state_builder
+            .set_judge_strategy(config.judge_strategy);
+        self.config = config;
+
+        Ok(())
+    }
+
     pub fn set_hand_size(&mut self, hand_size: usize) -> Result<(), GameCoreError> {
         if hand_size == 0 {
             Err(GameCoreError::HandSizeCannotBeZero)
         } else {
-            self.hand_size_config = hand_size;
+            self.config.hand_size = hand_size;
             Ok(())
         }
     }
 
+    pub fn set_win_target(&mut self, win_target: usize) -> Result<(), GameCoreError> {
+        self.set_config(GameConfig {
+            win_target,
+            ..self.config.clone()
+        })
+    }
+
+    pub fn set_judge_strategy(&mut self, judge_strategy: JudgeStrategy) {
+        self.config.judge_strategy = judge_strategy;
+        self.game_state_builder.set_judge_strategy(judge_strategy);
+    }
+
+    pub fn set_allow_redraws(&mut self, allow_redraws: bool) {
+        self.config.allow_redraws = allow_redraws;
+    }
+
+    /// `add_player()` refuses players beyond `max_players`; the game cannot start with fewer than
+    /// `min_players`.
+    pub fn set_player_bounds(
+        &mut self,
+        min_players: usize,
+        max_players: usize,
+    ) -> Result<(), GameCoreError> {
+        self.game_state_builder
+            .set_player_bounds(min_players, max_players)?;
+        self.config.min_players = min_players;
+        self.config.max_players = max_players;
+
+        Ok(())
+    }
+
+    /// What to do when the Answer deck cannot refill every hand. Recycle played cards by default.
+    pub fn set_exhaustion_policy(&mut self, exhaustion_policy: ExhaustionPolicy) {
+        self.config.exhaustion_policy = exhaustion_policy;
+        self.game_state_builder
+            .set_exhaustion_policy(exhaustion_policy);
+    }
+
+    /// Seed the shuffles and Judge picks, e.g. for replays and tests. Random without one.
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.game_state_builder.set_seed(seed);
+    }
+
+    /// The player ID doubles as the display name.
     pub fn add_player(&mut self, player_name: impl Into<String>) -> Result<(), GameCoreError> {
-        self.game_state_builder.add_new_player(player_name)
+        let player_name = player_name.into();
+
+        self.add_player_with_name(player_name.clone(), player_name)
+    }
+
+    pub fn add_player_with_name(
+        &mut self,
+        player_name: impl Into<String>,
+        display_name: impl Into<String>,
+    ) -> Result<(), GameCoreError> {
+        let player_name = player_name.into();
+
+        self.game_state_builder
+            .add_new_player(player_name.clone())
+            .map_err(|err| err.with_display_names(|id| display_name_of(&self.display_names, id)))?;
+        self.display_names.insert(player_name, display_name.into());
+
+        Ok(())
+    }
+
+    pub fn rename_player(
+        &mut self,
+        player_name: impl Into<String>,
+        display_name: impl Into<String>,
+    ) -> Result<(), GameCoreError> {
+        rename(
+            &mut self.display_names,
+            player_name.into(),
+            display_name.into(),
+        )
+    }
+
+    pub fn display_name(&self, player_name: &str) -> Option<String> {
+        self.display_names.get(player_name).cloned()
+    }
+
+    /// Sorted.
+    pub fn players(&self) -> Vec<String> {
+        let mut players: Vec<_> = self.display_names.keys().cloned().collect();
+        players.sort();
+
+        players
     }
 
     pub fn remove_player(&mut self, player_name: impl Into<String>) -> Result<(), GameCoreError> {
-        self.game_state_builder.withdraw_player(player_name)
+        let player_name = player_name.into();
+
+        self.game_state_builder
+            .withdraw_player(player_name.clone())
+            .map_err(|err| err.with_display_names(|id| display_name_of(&self.display_names, id)))?;
+        self.display_names.remove(&player_name);
+
+        Ok(())
     }
 
     pub fn remove_all_players(&mut self) {
-        self.game_state_builder.withdraw_all_players()
+        self.game_state_builder.withdraw_all_players();
+        self.display_names.clear();
     }
 
     pub fn add_new_questions(&mut self, questions: impl IntoIterator<Item = impl Into<String>>) {
@@ -77,10 +235,322 @@ impl GenericDriverBuilder {
         self.game_state_builder.remove_all_answers();
     }
 
+    /// Add all questions and answers of an imported deck.
+    pub fn add_deck(&mut self, deck: &Deck) {
+        self.game_state_builder
+            .add_new_questions(deck.question_texts());
+        self.game_state_builder.add_new_answers(deck.answer_texts());
+    }
+
+    /// Report problems in the questions and answers for the configured hand size.
+    pub fn validate(&self) -> ValidationReport {
+        self.game_state_builder.validate(self.config.hand_size)
+    }
+
+    /// Report every problem that would stop `build()`, and the deck warnings, without building.
+    pub fn check(&self) -> BuildCheck {
+        self.game_state_builder.check(self.config.hand_size)
+    }
+
     pub fn build(&self) -> Result<GenericDriver, GameCoreError> {
-        let game_state = self.game_state_builder.build(self.hand_size_config)?;
+        let game_state = self.game_state_builder.build(self.config.hand_size)?;
 
-        Ok(GenericDriver { game_state })
+        Ok(GenericDriver {
+            game_state,
+            config: self.config.clone(),
+            rules: self.rules.clone(),
+            display_names: self.display_names.clone(),
+            round: 0,
+            judge: String::new(),
+            question: String::new(),
+            submissions: vec![],
+            listeners: Default::default(),
+            game_over_emitted: false,
+        })
+    }
+}
+
+impl GenericDriver {
+    /// Call `listener` with every event from now on, until `unsubscribe()`.
+    pub fn subscribe(&mut self, listener: impl FnMut(&GameEvent) + Send + 'static) -> ListenerId {
+        self.listeners.subscribe(listener)
+    }
+
+    /// Return false if no listener has the ID.
+    pub fn unsubscribe(&mut self, listener_id: ListenerId) -> bool {
+        self.listeners.unsubscribe(listener_id)
+    }
+
+    /// ONE-based; 0 before the first round.
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    /// None before the first round.
+    pub fn current_judge(&self) -> Option<String> {
+        Some(self.judge.clone()).filter(|_| self.round > 0)
+    }
+
+    /// None before the first round.
+    pub fn current_question(&self) -> Option<String> {
+        Some(self.question.clone()).filter(|_| self.round > 0)
+    }
+
+    /// Players who submitted in the current round, in the order of `ordered_players()`.
+    pub fn submitted_players(&self) -> Vec<String> {
+        let submitted_card_ids = self.game_state.report_submitted_card_ids();
+
+        self.ordered_players()
+            .into_iter()
+            .filter(|p| submitted_card_ids.contains_key(p))
+            .collect()
+    }
+
+    /// Players who still owe an answer in the current round, in the order of `ordered_players()`.
+    /// Empty before the first round.
+    pub fn pending_players(&self) -> Vec<String> {
+        if self.round == 0 {
+            return vec![];
+        }
+
+        let submitted_card_ids = self.game_state.report_submitted_card_ids();
+
+        self.ordered_players()
+            .into_iter()
+            .filter(|p| *p != self.judge && !submitted_card_ids.contains_key(p))
+            .collect()
+    }
+
+    /// In descending order of Awesome Points.
+    pub fn scores(&self) -> Vec<(String, i32)> {
+        self.game_state.report_awesome_point_ranking()
+    }
+
+    pub fn deck_sizes(&self) -> DeckSizes {
+        self.game_state.deck_sizes()
+    }
+
+    /// The leader, once they have reached the win target.
+    pub fn winner(&self) -> Option<String> {
+        self.scores()
+            .into_iter()
+            .next()
+            .filter(|(_, points)| *points >= self.config.win_target as i32)
+            .map(|(player_name, _)| player_name)
+    }
+
+    /// Everything that may be shown to all players, e.g. in a shared channel.
+    pub fn public_view(&self) -> PublicView {
+        PublicView {
+            round: self.round,
+            judge: self.current_judge(),
+            question: self.current_question(),
+            players: self.ordered_players(),
+            submitted: self.submitted_players(),
+            waiting_for: self.pending_players(),
+            answers: self.submissions.iter().map(|(_, a)| a.clone()).collect(),
+            ranking: self.scores(),
+            is_game_over: self.is_game_over(),
+            display_names: self.display_names(),
+        }
+    }
+
+    /// Everything `player_name` may see: the public view, their own hand and their own
+    /// submission, but nothing of the other players' hands.
+    pub fn view_for(&self, player_name: impl Into<String>) -> Result<PlayerView, GameCoreError> {
+        let player_name = player_name.into();
+
+        let hand = self.game_state.hand_cards_of(&player_name).ok_or_else(|| {
+            GameCoreError::PlayerDoesNotExist {
+                name: player_name.clone(),
+                display_name: display_name_of(&self.display_names, &player_name),
+            }
+        })?;
+
+        Ok(PlayerView {
+            display_name: display_name_of(&self.display_names, &player_name),
+            is_judge: self.current_judge().as_ref() == Some(&player_name),
+            hand,
+            submitted_card_ids: self
+                .game_state
+                .report_submitted_card_ids()
+                .remove(&player_name),
+            public: self.public_view(),
+            player: player_name,
+        })
+    }
+
+    pub fn display_name(&self, player_name: &str) -> Option<String> {
+        self.display_names.get(player_name).cloned()
+    }
+
+    /// { player ID: display name }
+    pub fn display_names(&self) -> HashMap<String, String> {
+        self.display_names.clone()
+    }
+
+    pub fn rename_player(
+        &mut self,
+        player_name: impl Into<String>,
+        display_name: impl Into<String>,
+    ) -> Result<(), GameCoreError> {
+        let player_name = player_name.into();
+        let display_name = display_name.into();
+
+        rename(
+            &mut self.display_names,
+            player_name.clone(),
+            display_name.clone(),
+        )?;
+        self.listeners.emit(GameEvent::PlayerRenamed {
+            player: player_name,
+            display_name,
+        });
+
+        Ok(())
+    }
+
+    /// The settings the game was built with.
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// Same as `submit_answers()`, but the cards are picked by their IDs, so a stale hand shown
+    /// by a frontend cannot play the wrong card.
+    pub fn submit_answers_by_ids(
+        &mut self,
+        player_name: impl Into<String>,
+        card_ids: impl IntoIterator<Item = impl Into<CardId>>,
+    ) -> Result<AllSubmittedAnswers<String>, GameCoreError> {
+        let player_name = player_name.into();
+        let card_ids: Vec<CardId> = card_ids.into_iter().map(Into::into).collect();
+
+        let hand = self
+            .game_state
+            .report_hand_cards()
+            .remove(&player_name)
+            .unwrap_or_default();
+        let answers = card_ids
+            .iter()
+            .map(|id| hand.iter().find(|c| c.id == *id).map(|c| c.content.clone()))
+            .collect();
+
+        self.submit_with_rules(player_name, answers, |game_state, player_name| {
+            game_state.submit_answers_by_ids(player_name, &card_ids)
+        })
+    }
+
+    /// True once the Answer deck ran out under `ExhaustionPolicy::EndGame` or `Error`.
+    /// Under `EndGame`, the round in progress can still be judged; under `Error`, the last
+    /// submission of the round failed with `GameCoreError::AnswerDeckExhausted` instead.
+    pub fn is_game_over(&self) -> bool {
+        self.game_state.is_over()
+    }
+
+    /// IDs of the cards each player submitted in the current round, in the order played.
+    pub fn submitted_card_ids(&self) -> HashMap<String, Vec<CardId>> {
+        self.game_state.report_submitted_card_ids()
+    }
+
+    pub fn rule_names(&self) -> Vec<String> {
+        self.rules.iter().map(|r| r.name().to_owned()).collect()
+    }
+
+    /// `answers` are the contents of the submitted cards, or None if a card is not in the hand,
+    /// in which case `submit` reports the error and the rules are not consulted.
+    fn submit_with_rules(
+        &mut self,
+        player_name: String,
+        answers: Option<Vec<String>>,
+        submit: impl FnOnce(
+            &mut GameState,
+            &String,
+        ) -> Result<AllSubmittedAnswers<String>, GameCoreError>,
+    ) -> Result<AllSubmittedAnswers<String>, GameCoreError> {
+        if let Some(answers) = &answers {
+            let submission = Submission {
+                round: self.round,
+                player: &player_name,
+                answers,
+            };
+
+            for rule in self.rules.iter_mut() {
+                rule.validate_submission(&submission)?;
+            }
+        }
+
+        let submitted_answers = match submit(&mut self.game_state, &player_name) {
+            Ok(submitted_answers) => submitted_answers,
+            Err(err) => {
+                if self.game_state.is_over() {
+                    self.emit_game_over();
+                }
+                return Err(err.with_display_names(|id| display_name_of(&self.display_names, id)));
+            }
+        };
+
+        let num_submitted = self.game_state.report_submitted_card_ids().len();
+        self.listeners.emit(GameEvent::PlayerSubmitted {
+            round: self.round,
+            player: player_name.clone(),
+            num_waiting: self.game_state.ordered_players().len() - 1 - num_submitted,
+        });
+
+        let mut effects = RuleEffects::default();
+        let submission = Submission {
+            round: self.round,
+            player: &player_name,
+            answers: &answers.unwrap_or_default(),
+        };
+        for rule in self.rules.iter_mut() {
+            rule.after_submission(&submission, &mut effects);
+        }
+        self.apply(effects);
+
+        Ok(submitted_answers.map(|mut submissions| {
+            for rule in self.rules.iter_mut() {
+                rule.on_submissions_closed(&mut submissions);
+            }
+            self.submissions = submissions.clone();
+            self.listeners.emit(GameEvent::SubmissionsClosed {
+                round: self.round,
+                submissions: submissions.clone(),
+            });
+
+            submissions
+        }))
+    }
+
+    /// Emit `GameEvent::GameOver` unless it already was. For handles that cannot consume
+    /// the driver with `end_game()`.
+    pub(crate) fn emit_game_over(&mut self) {
+        if !self.game_over_emitted {
+            self.game_over_emitted = true;
+            self.listeners.emit(GameEvent::GameOver {
+                ranking: self.game_state.report_awesome_point_ranking(),
+            });
+        }
+    }
+
+    fn apply(&mut self, effects: RuleEffects) {
+        let mut scores_changed = false;
+        for (player_name, points) in effects.awards {
+            scores_changed |= self
+                .game_state
+                .add_awesome_points(&player_name, points)
+                .is_ok();
+        }
+        if scores_changed {
+            self.listeners.emit(GameEvent::ScoresChanged {
+                ranking: self.game_state.report_awesome_point_ranking(),
+            });
+        }
+
+        for player_name in effects.redraws {
+            let _ = self
+                .game_state
+                .redraw_hands(std::slice::from_ref(&player_name));
+        }
     }
 }
 
@@ -95,12 +565,37 @@ impl GameCoreDriver for GenericDriver {
     }
 
     fn start_round(&mut self) -> Self::RoundStartInfo {
-        let game_state = &mut self.game_state;
+        let judge = self.game_state.next_judge();
+        let question = self.game_state.draw_next_question_card();
+
+        self.round += 1;
+        self.judge = judge.clone();
+        self.question = question.clone();
+        self.submissions.clear();
+        self.listeners.emit(GameEvent::RoundStarted {
+            round: self.round,
+            judge: judge.clone(),
+            question: question.clone(),
+        });
 
+        let mut effects = RuleEffects::default();
+        let round_start = RoundStart {
+            round: self.round,
+            judge: &judge,
+            question: &question,
+            players: &self.game_state.ordered_players(),
+        };
+        for rule in self.rules.iter_mut() {
+            rule.on_round_start(&round_start, &mut effects);
+        }
+        self.apply(effects);
+
+        let game_state = &self.game_state;
         RoundInformation {
-            judge: game_state.next_judge(),
-            question: game_state.draw_next_question_card(),
-            player_hands: game_state.report_hands(),
+            judge,
+            question,
+            question_id: game_state.current_question_id().unwrap(),
+            display_names: self.display_names.clone(),
         }
     }
 
@@ -110,16 +605,32 @@ impl GameCoreDriver for GenericDriver {
         player_name: impl Into<Self::PlayerName>,
         answer_indices: impl IntoIterator<Item = impl Into<usize>>,
     ) -> Result<Option<Vec<(Self::PlayerName, String)>>, Self::Error> {
+        let player_name = player_name.into();
         let answer_indices: Vec<usize> = answer_indices.into_iter().map(Into::into).collect();
 
-        self.game_state
-            .submit_answers(&player_name.into(), &answer_indices)
+        let hand = self
+            .game_state
+            .report_hands()
+            .remove(&player_name)
+            .unwrap_or_default();
+        let answers = answer_indices
+            .iter()
+            .map(|&ind| hand.get(ind).cloned())
+            .collect();
+
+        self.submit_with_rules(player_name, answers, |game_state, player_name| {
+            game_state.submit_answers(player_name, &answer_indices)
+        })
     }
 
     fn redraw_hands(
         &mut self,
         player_names: impl IntoIterator<Item = impl Into<Self::PlayerName>>,
     ) -> Result<(), Self::Error> {
+        if !self.config.allow_redraws {
+            return Err(GameCoreError::RedrawsNotAllowed);
+        }
+
         let player_names = player_names
             .into_iter()
             .map(|p| p.into())
@@ -128,19 +639,76 @@ impl GameCoreDriver for GenericDriver {
         self.game_state.redraw_hands(&player_names)
     }
 
-    /// Return Err() only when the game is not started or `chosen_player` is unknown.
+    /// Return Err() only when the game is not started, `chosen_player` is unknown or the Judge,
+    /// or a rule rejects the choice.
     fn end_round(
         &mut self,
         chosen_player: impl Into<Self::PlayerName>,
     ) -> Result<Self::RoundEndInfo, Self::Error> {
-        self.game_state
-            .increment_awesome_points(&chosen_player.into())?;
+        let chosen_player = chosen_player.into();
+        self.game_state.check_chosen_player(&chosen_player)?;
+
+        let judging = Judging {
+            round: self.round,
+            judge: &self.judge,
+            chosen_player: &chosen_player,
+            submissions: &self.submissions,
+        };
+        for rule in self.rules.iter_mut() {
+            rule.validate_judging(&judging)?;
+        }
+
+        let mut effects = RuleEffects::default();
+        for rule in self.rules.iter_mut() {
+            rule.on_round_end(&judging, &mut effects);
+        }
+
+        self.listeners.emit(GameEvent::JudgeChose {
+            round: self.round,
+            judge: self.judge.clone(),
+            chosen_player: chosen_player.clone(),
+            answer: self
+                .submissions
+                .iter()
+                .find(|(player, _)| *player == chosen_player)
+                .map(|(_, answer)| answer.clone()),
+        });
+        self.apply(effects);
+
+        if self.game_state.is_over() {
+            self.emit_game_over();
+        }
 
         Ok(self.game_state.report_awesome_point_ranking())
     }
 
-    /// No-op.
-    fn end_game(self) {}
+    /// Only tells the listeners that the game is over.
+    fn end_game(mut self) {
+        self.emit_game_over();
+    }
+}
+
+fn display_name_of(display_names: &HashMap<String, String>, player_name: &str) -> String {
+    display_names
+        .get(player_name)
+        .cloned()
+        .unwrap_or_else(|| player_name.to_owned())
+}
+
+fn rename(
+    display_names: &mut HashMap<String, String>,
+    player_name: String,
+    display_name: String,
+) -> Result<(), GameCoreError> {
+    if let Some(current) = display_names.get_mut(&player_name) {
+        *current = display_name;
+        Ok(())
+    } else {
+        Err(GameCoreError::PlayerDoesNotExist {
+            display_name: player_name.clone(),
+            name: player_name,
+        })
+    }
 }
 
 #[allow(dead_code)]
@@ -232,14 +800,8 @@ mod integration_tests {
             for player in non_judge_players.iter() {
                 println!("======================================================");
                 println!("{}, Your hand is:", player);
-                for (i, card_content) in round_information
-                    .player_hands
-                    .get(player)
-                    .unwrap()
-                    .iter()
-                    .enumerate()
-                {
-                    println!("{} - {}", i + 1, card_content);
+                for (i, card) in driver.view_for(player).unwrap().hand.iter().enumerate() {
+                    println!("{} - {}", i + 1, card.content);
                 }
                 println!("======================================================");
             }
@@ -247,10 +809,7 @@ mod integration_tests {
             let redraw_players = [non_judge_players[1].clone(), non_judge_players[0].clone()];
 
             // Simulating users' input of choosing answers
-            let correct_num_blanks = Regex::new("_+")
-                .unwrap()
-                .find_iter(&round_information.question)
-                .count();
+            let correct_num_blanks = num_blanks(&round_information.question);
             let mut submitted_answers = None;
             for player in non_judge_players {
                 submitted_answers = driver
@@ -297,6 +856,397 @@ mod integration_tests {
         driver.end_game();
     }
 
+    #[test]
+    fn test_submit_answers_by_ids() {
+        let mut driver = set_up_a_game(&mut GenericDriverBuilder::new(), true);
+        let round_information = driver.start_round();
+        let non_judge_players = find_non_judge_players(&round_information.judge);
+        let num_blanks = num_blanks(&round_information.question);
+
+        let player = &non_judge_players[0];
+        let card_ids = driver
+            .view_for(player)
+            .unwrap()
+            .hand
+            .iter()
+            .take(num_blanks)
+            .map(|card| card.id.clone())
+            .collect::<Vec<_>>();
+        driver
+            .submit_answers_by_ids(player, card_ids.clone())
+            .unwrap();
+
+        assert_eq!(driver.submitted_card_ids()[player], card_ids);
+        assert!(driver
+            .submit_answers_by_ids(&non_judge_players[1], card_ids)
+            .is_err());
+    }
+
+    #[test]
+    fn test_set_config() {
+        let mut builder = GenericDriverBuilder::new();
+        builder.set_config(GameConfig::quick_game()).unwrap();
+
+        assert!(builder
+            .set_config(GameConfig {
+                min_players: 5,
+                max_players: 4,
+                ..GameConfig::party()
+            })
+            .is_err());
+        assert_eq!(builder.config(), &GameConfig::quick_game());
+
+        for player in players() {
+            builder.add_player(player).unwrap();
+        }
+        builder.add_new_questions(questions());
+        builder.add_new_answers(answers());
+        let mut driver = builder.build().unwrap();
+
+        assert_eq!(driver.config(), &GameConfig::quick_game());
+        driver.start_round();
+        assert!(players()
+            .into_iter()
+            .all(|player| driver.view_for(player).unwrap().hand.len()
+                == GameConfig::quick_game().hand_size));
+        assert_eq!(
+            driver.redraw_hands(players()).err().unwrap(),
+            GameCoreError::RedrawsNotAllowed
+        );
+    }
+
+    /// Log every hook, and give the Judge 2 Awesome Points at the end of each round.
+    #[derive(Clone, Default)]
+    struct RecordingRule {
+        log: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl Rule for RecordingRule {
+        fn name(&self) -> &str {
+            "Recording"
+        }
+
+        fn on_round_start(&mut self, round: &RoundStart, _effects: &mut RuleEffects) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("start {}", round.round));
+        }
+
+        fn validate_submission(&mut self, submission: &Submission) -> Result<(), GameCoreError> {
+            if submission.answers.iter().any(|a| a == "blood") {
+                return Err("No blood, please.".into());
+            }
+
+            Ok(())
+        }
+
+        fn after_submission(&mut self, submission: &Submission, _effects: &mut RuleEffects) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("submit {}", submission.answers.len()));
+        }
+
+        fn on_submissions_closed(&mut self, submissions: &mut Vec<(String, String)>) {
+            submissions.sort();
+            self.log.lock().unwrap().push("closed".to_owned());
+        }
+
+        fn validate_judging(&mut self, judging: &Judging) -> Result<(), GameCoreError> {
+            if judging.submissions[0].0 != judging.chosen_player {
+                return Err("Only the first submission may win.".into());
+            }
+
+            Ok(())
+        }
+
+        fn on_round_end(&mut self, judging: &Judging, effects: &mut RuleEffects) {
+            effects.award(judging.judge, 2);
+            self.log.lock().unwrap().push("end".to_owned());
+        }
+    }
+
+    #[test]
+    fn test_default_rules() {
+        let mut builder = GenericDriverBuilder::new();
+
+        assert_eq!(builder.rule_names(), vec!["Awesome Point for the winner"]);
+
+        builder.set_rules(vec![]);
+        let mut driver = set_up_a_game(&mut builder, true);
+        let round_information = driver.start_round();
+        let non_judge_players = find_non_judge_players(&round_information.judge);
+
+        assert!(driver.rule_names().is_empty());
+        let ranking = driver.end_round(&non_judge_players[0]).unwrap();
+        assert!(ranking.iter().all(|(_, points)| *points == 0));
+    }
+
+    #[test]
+    fn test_rule_hooks() {
+        let rule = RecordingRule::default();
+        let mut builder = GenericDriverBuilder::new();
+        builder.add_rule(rule.clone());
+        let mut driver = set_up_a_game(&mut builder, true);
+
+        let round_information = driver.start_round();
+        let judge = round_information.judge.clone();
+        let num_blanks = num_blanks(&round_information.question);
+
+        let mut submissions = None;
+        for player in find_non_judge_players(&judge) {
+            let hand = driver
+                .view_for(&player)
+                .unwrap()
+                .hand
+                .into_iter()
+                .map(|card| card.content)
+                .collect::<Vec<_>>();
+            if let Some(ind) = hand.iter().position(|a| a == "blood") {
+                assert!(driver.submit_answers(&player, vec![ind]).is_err());
+            }
+
+            let indices = (0..hand.len())
+                .filter(|&i| hand[i] != "blood")
+                .take(num_blanks)
+                .collect::<Vec<_>>();
+            submissions = driver.submit_answers(&player, indices).unwrap();
+        }
+        let submissions = submissions.unwrap();
+
+        let mut sorted = submissions.clone();
+        sorted.sort();
+        assert_eq!(submissions, sorted);
+
+        assert!(driver.end_round(&submissions[1].0).is_err());
+        let ranking = driver.end_round(&submissions[0].0).unwrap();
+
+        let points = ranking.into_iter().collect::<HashMap<_, _>>();
+        assert_eq!(points[&judge], 2);
+        assert_eq!(points[&submissions[0].0], 1);
+        assert_eq!(
+            *rule.log.lock().unwrap(),
+            vec![
+                "start 1".to_owned(),
+                format!("submit {}", num_blanks),
+                format!("submit {}", num_blanks),
+                format!("submit {}", num_blanks),
+                "closed".to_owned(),
+                "end".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_events() {
+        let events = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let mut driver = set_up_a_game(&mut GenericDriverBuilder::new(), true);
+        let listener_id = {
+            let events = events.clone();
+            driver.subscribe(move |e| events.lock().unwrap().push(e.clone()))
+        };
+
+        let round_information = driver.start_round();
+        let num_blanks = num_blanks(&round_information.question);
+        let mut submissions = None;
+        for player in find_non_judge_players(&round_information.judge) {
+            submissions = driver
+                .submit_answers(&player, (0..num_blanks).collect::<Vec<_>>())
+                .unwrap();
+        }
+        let submissions = submissions.unwrap();
+        let ranking = driver.end_round(&submissions[0].0).unwrap();
+
+        assert!(driver.unsubscribe(listener_id));
+        driver.start_round();
+
+        let events = events.lock().unwrap().clone();
+        assert_eq!(events.len(), 7);
+        assert_eq!(
+            events[0],
+            GameEvent::RoundStarted {
+                round: 1,
+                judge: round_information.judge.clone(),
+                question: round_information.question.clone(),
+            }
+        );
+        assert!(matches!(
+            events[1],
+            GameEvent::PlayerSubmitted { num_waiting: 2, .. }
+        ));
+        assert!(matches!(
+            events[3],
+            GameEvent::PlayerSubmitted { num_waiting: 0, .. }
+        ));
+        assert_eq!(
+            events[4],
+            GameEvent::SubmissionsClosed {
+                round: 1,
+                submissions: submissions.clone(),
+            }
+        );
+        assert_eq!(
+            events[5],
+            GameEvent::JudgeChose {
+                round: 1,
+                judge: round_information.judge,
+                chosen_player: submissions[0].0.clone(),
+                answer: Some(submissions[0].1.clone()),
+            }
+        );
+        assert_eq!(events[6], GameEvent::ScoresChanged { ranking });
+    }
+
+    #[test]
+    fn test_game_over_event() {
+        let events = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let mut driver = set_up_a_game(&mut GenericDriverBuilder::new(), true);
+        {
+            let events = events.clone();
+            driver.subscribe(move |e| events.lock().unwrap().push(e.clone()));
+        }
+
+        driver.end_game();
+
+        assert!(matches!(
+            events.lock().unwrap()[..],
+            [GameEvent::GameOver { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_game_under_error_policy_fails_the_last_submission() {
+        let mut builder = GenericDriverBuilder::new();
+        builder.set_hand_size(2).unwrap();
+        builder.set_exhaustion_policy(ExhaustionPolicy::Error);
+        for player in ["A", "B", "C"] {
+            builder.add_player(player).unwrap();
+        }
+        builder.add_new_questions(["Who _?"]);
+        builder.add_new_answers((1..=9).map(|i| i.to_string()));
+        let mut driver = builder.build().unwrap();
+        let events = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        {
+            let events = events.clone();
+            driver.subscribe(move |e| events.lock().unwrap().push(e.clone()));
+        }
+
+        let mut error = None;
+        while error.is_none() {
+            let judge = driver.start_round().judge;
+            let mut submissions = None;
+            for player in ["A", "B", "C"].iter().copied().filter(|p| *p != judge) {
+                match driver.submit_answers(player, [0_usize]) {
+                    Ok(submitted) => submissions = submitted,
+                    Err(err) => error = Some(err),
+                }
+            }
+            if let Some(submissions) = submissions {
+                driver.end_round(submissions[0].0.clone()).unwrap();
+            }
+        }
+
+        assert!(matches!(
+            error,
+            Some(GameCoreError::AnswerDeckExhausted { .. })
+        ));
+        assert!(driver.is_game_over());
+        assert!(events
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|e| matches!(e, GameEvent::GameOver { .. })));
+    }
+
+    #[test]
+    fn test_queries() {
+        let mut driver = set_up_a_game(&mut GenericDriverBuilder::new(), true);
+
+        assert_eq!(driver.round(), 0);
+        assert_eq!(driver.current_judge(), None);
+        assert_eq!(driver.current_question(), None);
+        assert!(driver.pending_players().is_empty());
+        assert!(driver.scores().iter().all(|(_, points)| *points == 0));
+        assert_eq!(driver.deck_sizes().questions_in_deck, questions().len());
+        assert_eq!(
+            driver.deck_sizes().answers_in_deck,
+            answers().len() - 10 * players().len()
+        );
+
+        let round_information = driver.start_round();
+        let non_judge_players = find_non_judge_players(&round_information.judge);
+        let num_blanks = num_blanks(&round_information.question);
+
+        assert_eq!(driver.round(), 1);
+        assert_eq!(driver.current_judge(), Some(round_information.judge));
+        assert_eq!(driver.current_question(), Some(round_information.question));
+        assert_eq!(driver.pending_players().len(), 3);
+
+        driver
+            .submit_answers(&non_judge_players[0], (0..num_blanks).collect::<Vec<_>>())
+            .unwrap();
+
+        assert_eq!(
+            driver.submitted_players(),
+            vec![non_judge_players[0].clone()]
+        );
+        assert!(!driver.pending_players().contains(&non_judge_players[0]));
+        assert_eq!(driver.pending_players().len(), 2);
+
+        let ranking = driver.end_round(&non_judge_players[1]).unwrap();
+        assert_eq!(driver.scores(), ranking);
+    }
+
+    #[test]
+    fn test_views() {
+        let mut driver = set_up_a_game(&mut GenericDriverBuilder::new(), true);
+
+        let public_view = driver.public_view();
+        assert_eq!(public_view.round, 0);
+        assert_eq!(public_view.judge, None);
+        assert!(public_view.waiting_for.is_empty());
+
+        let round_information = driver.start_round();
+        let judge = round_information.judge.clone();
+        let non_judge_players = find_non_judge_players(&judge);
+        let num_blanks = num_blanks(&round_information.question);
+        let hand = driver.view_for(&non_judge_players[0]).unwrap().hand;
+        driver
+            .submit_answers(&non_judge_players[0], (0..num_blanks).collect::<Vec<_>>())
+            .unwrap();
+
+        let view = driver.view_for(&non_judge_players[0]).unwrap();
+        assert!(!view.is_judge);
+        assert_eq!(
+            view.hand,
+            driver
+                .game_state
+                .hand_cards_of(&non_judge_players[0])
+                .unwrap()
+        );
+        assert_eq!(
+            view.submitted_card_ids,
+            Some(hand[..num_blanks].iter().map(|c| c.id.clone()).collect())
+        );
+        assert_eq!(view.public.question, Some(round_information.question));
+        assert_eq!(view.public.submitted, vec![non_judge_players[0].clone()]);
+        assert_eq!(view.public.waiting_for.len(), 2);
+        assert!(view.public.answers.is_empty());
+
+        let judge_view = driver.view_for(&judge).unwrap();
+        assert!(judge_view.is_judge);
+        assert_eq!(judge_view.submitted_card_ids, None);
+
+        assert_eq!(
+            driver.view_for("Player E").err().unwrap(),
+            GameCoreError::PlayerDoesNotExist {
+                name: "Player E".to_owned(),
+                display_name: "Player E".to_owned(),
+            }
+        );
+    }
+
     #[test]
     fn test_run_multiple_times() {
         let mut builder = GenericDriverBuilder::new();
@@ -309,10 +1259,102 @@ mod integration_tests {
         run_a_game(set_up_a_game(&mut builder, false));
     }
 
+    #[test]
+    fn test_seed() {
+        let play = |seed, reverse: bool| {
+            let mut builder = GenericDriverBuilder::new();
+            let mut names = players();
+            if reverse {
+                names.reverse();
+            }
+            for player in names {
+                builder.add_player(player).unwrap();
+            }
+            builder.add_new_questions(questions());
+            builder.add_new_answers(answers());
+            builder.set_judge_strategy(JudgeStrategy::Random);
+            builder.set_seed(seed);
+
+            let mut driver = builder.build().unwrap();
+            let mut rounds = vec![];
+            for _ in 0..3 {
+                let round_information = driver.start_round();
+                let num_blanks = num_blanks(&round_information.question);
+                let hands = players()
+                    .into_iter()
+                    .map(|player| driver.view_for(player).unwrap().hand)
+                    .collect::<Vec<_>>();
+                let mut submissions = None;
+                for player in find_non_judge_players(&round_information.judge) {
+                    submissions = driver.submit_answers(player, 0..num_blanks).unwrap();
+                }
+                let submissions = submissions.unwrap();
+                driver.end_round(&submissions[0].0).unwrap();
+                rounds.push((hands, submissions));
+            }
+
+            rounds
+        };
+
+        assert_eq!(play(Some(7), false), play(Some(7), true));
+        assert_ne!(play(Some(7), false), play(Some(8), false));
+    }
+
+    #[test]
+    fn test_display_names() {
+        let mut builder = GenericDriverBuilder::new();
+        for (id, display_name) in players().into_iter().zip(["Alex", "Alex", "Sam", "Kim"]) {
+            builder.add_player_with_name(id, display_name).unwrap();
+        }
+        assert_eq!(
+            builder
+                .add_player_with_name("Player A", "Robin")
+                .unwrap_err()
+                .to_string(),
+            "A Player with the name Alex [Player A] already exists."
+        );
+        assert!(builder.rename_player("Player Z", "Robin").is_err());
+        builder.rename_player("Player D", "Robin").unwrap();
+        builder.add_new_questions(questions());
+        builder.add_new_answers(answers());
+        let mut driver = builder.build().unwrap();
+
+        let received = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        {
+            let received = received.clone();
+            driver.subscribe(move |e| received.lock().unwrap().push(e.clone()));
+        }
+        driver.rename_player("Player C", "Charlie").unwrap();
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![GameEvent::PlayerRenamed {
+                player: "Player C".to_owned(),
+                display_name: "Charlie".to_owned(),
+            }]
+        );
+
+        let round_information = driver.start_round();
+        let judge = round_information.judge.clone();
+        assert_eq!(round_information.display_names["Player D"], "Robin");
+        assert_eq!(driver.view_for("Player C").unwrap().display_name, "Charlie");
+        assert_eq!(driver.public_view().display_names["Player B"], "Alex");
+
+        let error = driver.submit_answers(&judge, vec![0usize]).unwrap_err();
+        assert!(error.to_string().contains(&format!(
+            "{} [{}]",
+            driver.display_name(&judge).unwrap(),
+            judge
+        )));
+    }
+
     fn find_non_judge_players(judge_name: &String) -> Vec<String> {
         players().into_iter().filter(|p| p != judge_name).collect()
     }
 
+    fn num_blanks(question: &str) -> usize {
+        Regex::new("_+").unwrap().find_iter(question).count()
+    }
+
     fn players() -> Vec<String> {
         vec!["Player A", "Player B", "Player C", "Player D"]
             .into_iter()
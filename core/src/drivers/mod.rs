@@ -1,6 +1,8 @@
 use crate::game_state::{self, AllSubmittedAnswers};
 
 pub mod generic;
+pub mod typescript;
+pub mod views;
 pub mod wasm;
 
 pub trait GameCoreDriver {
@@ -0,0 +1,383 @@
+//! TypeScript declarations for the objects `WasmDriverBuilder` and `WasmDriver` exchange with
+//! JavaScript, added by wasm-bindgen to the generated `.d.ts` file.
+//!
+//! Keep `TYPESCRIPT_TYPES` in sync with the serde output of the Rust types; the tests below
+//! compare the two.
+
+use wasm_bindgen::prelude::*;
+
+// Only read by wasm-bindgen when targeting WASM, and by the tests.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+const TYPESCRIPT_TYPES: &str = r#"
+export interface HandCard {
+    id: string;
+    content: string;
+}
+
+/** What everyone may know when a round starts. Each hand is only in `viewFor()`. */
+export interface RoundInformation {
+    judge: string;
+    question: string;
+    questionId: string;
+    displayNames: Record<string, string>;
+}
+
+/** [player, combined answer], in the order shown to the Judge. */
+export type Submission = [string, string];
+
+/** [player, Awesome Points], in descending order of Awesome Points. */
+export type Ranking = [string, number][];
+
+export interface GameConfig {
+    handSize: number;
+    winTarget: number;
+    minPlayers: number;
+    maxPlayers: number;
+    judgeStrategy: "roundRobin" | "random";
+    exhaustionPolicy: "error" | "shortHands" | "recycle" | "endGame";
+    allowRedraws: boolean;
+}
+
+export interface DeckSizes {
+    questionsInDeck: number;
+    questionsDiscarded: number;
+    answersInDeck: number;
+    answersDiscarded: number;
+}
+
+export interface PublicView {
+    round: number;
+    judge: string | null;
+    question: string | null;
+    players: string[];
+    submitted: string[];
+    waitingFor: string[];
+    answers: string[];
+    ranking: Ranking;
+    isGameOver: boolean;
+    displayNames: Record<string, string>;
+}
+
+export interface PlayerView {
+    player: string;
+    displayName: string;
+    isJudge: boolean;
+    hand: HandCard[];
+    submittedCardIds: string[] | null;
+    public: PublicView;
+}
+
+export type GameEvent =
+    | { type: "roundStarted"; round: number; judge: string; question: string }
+    | { type: "playerSubmitted"; round: number; player: string; numWaiting: number }
+    | { type: "submissionsClosed"; round: number; submissions: Submission[] }
+    | { type: "judgeChose"; round: number; judge: string; chosenPlayer: string; answer: string | null }
+    | { type: "scoresChanged"; ranking: Ranking }
+    | { type: "playerRenamed"; player: string; displayName: string }
+    | { type: "gameOver"; ranking: Ranking };
+
+export type GameCoreErrorCode =
+    | "custom"
+    | "questionBlanksAndNumAnswersMismatch"
+    | "playerChoosingCardOutOfHandBound"
+    | "playerChoosingTheSameCardMultipleTimes"
+    | "judgeTryingToSubmitAnswers"
+    | "playerAlreadySubmittedAnswers"
+    | "insufficientAnswerCardsToDeal"
+    | "noQuestionCards"
+    | "noActiveQuestionCard"
+    | "noActiveJudge"
+    | "notEnoughPlayers"
+    | "tooManyPlayers"
+    | "invalidPlayerBounds"
+    | "playerAlreadyExists"
+    | "playerDoesNotExist"
+    | "gameNotStarted"
+    | "gameAlreadyInProgress"
+    | "gameEnded"
+    | "handSizeCannotBeZero"
+    | "judgeCannotBeChosen"
+    | "invalidDeck"
+    | "cardNotInHand"
+    | "answerDeckExhausted"
+    | "invalidConfig"
+    | "redrawsNotAllowed"
+    | "sessionAlreadyExists"
+    | "sessionDoesNotExist"
+    | "notSessionOwner"
+    | "ownerAlreadyHasLobby"
+    | "notALobby"
+    | "notAGame";
+
+/** Thrown by every method that can fail. `message` is in English; see `translate`. */
+export interface GameCoreError extends Error {
+    name: "GameCoreError";
+    code: GameCoreErrorCode;
+    details: Record<string, unknown>;
+    key: string;
+    args: Record<string, unknown>;
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_APPEND_CONTENT: &'static str = TYPESCRIPT_TYPES;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "RoundInformation")]
+    pub type JsRoundInformation;
+
+    #[wasm_bindgen(typescript_type = "Submission[] | null")]
+    pub type JsSubmissions;
+
+    #[wasm_bindgen(typescript_type = "Ranking")]
+    pub type JsRanking;
+
+    #[wasm_bindgen(typescript_type = "GameConfig")]
+    pub type JsGameConfig;
+
+    #[wasm_bindgen(typescript_type = "DeckSizes")]
+    pub type JsDeckSizes;
+
+    #[wasm_bindgen(typescript_type = "PublicView")]
+    pub type JsPublicView;
+
+    #[wasm_bindgen(typescript_type = "PlayerView")]
+    pub type JsPlayerView;
+
+    #[wasm_bindgen(typescript_type = "string[]")]
+    pub type JsStrings;
+
+    #[wasm_bindgen(typescript_type = "(event: GameEvent) => void")]
+    pub type JsEventListener;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::{
+        config::GameConfig,
+        drivers::{generic::GenericDriverBuilder, GameCoreDriver},
+        error::GameCoreError,
+        events::GameEvent,
+    };
+
+    /// Field names of `export interface <name> { ... }`.
+    fn declared_fields(name: &str) -> BTreeSet<String> {
+        let start = TYPESCRIPT_TYPES
+            .find(&format!("export interface {} ", name))
+            .unwrap_or_else(|| panic!("{} is not declared", name));
+        let body = &TYPESCRIPT_TYPES[start..];
+        let body = &body[body.find('{').unwrap() + 1..body.find("\n}").unwrap()];
+
+        body.lines()
+            .filter_map(|line| line.trim().split(':').next())
+            .filter(|field| !field.is_empty())
+            .map(ToOwned::to_owned)
+            .collect()
+    }
+
+    fn serialized_fields(value: impl serde::Serialize) -> BTreeSet<String> {
+        match serde_json::to_value(value).unwrap() {
+            Value::Object(fields) => fields.keys().cloned().collect(),
+            value => panic!("{} is not an object", value),
+        }
+    }
+
+    #[test]
+    fn interfaces_match_serde_output() {
+        let mut builder = GenericDriverBuilder::new();
+        for player in ["A", "B", "C"] {
+            builder.add_player(player).unwrap();
+        }
+        builder.add_new_questions(vec!["Why _?".to_owned()]);
+        builder.add_new_answers((0..40).map(|i| i.to_string()).collect::<Vec<_>>());
+        let mut driver = builder.build().unwrap();
+        let round_information = driver.start_round();
+        let judge = round_information.judge.clone();
+
+        let hand_card = &driver.view_for(&judge).unwrap().hand[0];
+        assert_eq!(serialized_fields(hand_card), declared_fields("HandCard"));
+        assert_eq!(
+            serialized_fields(&round_information),
+            declared_fields("RoundInformation")
+        );
+        assert_eq!(
+            serialized_fields(GameConfig::default()),
+            declared_fields("GameConfig")
+        );
+        assert_eq!(
+            serialized_fields(driver.deck_sizes()),
+            declared_fields("DeckSizes")
+        );
+        assert_eq!(
+            serialized_fields(driver.public_view()),
+            declared_fields("PublicView")
+        );
+        assert_eq!(
+            serialized_fields(driver.view_for(&judge).unwrap()),
+            declared_fields("PlayerView")
+        );
+    }
+
+    #[test]
+    fn event_types_are_declared() {
+        let events = [
+            GameEvent::RoundStarted {
+                round: 1,
+                judge: String::new(),
+                question: String::new(),
+            },
+            GameEvent::PlayerSubmitted {
+                round: 1,
+                player: String::new(),
+                num_waiting: 0,
+            },
+            GameEvent::SubmissionsClosed {
+                round: 1,
+                submissions: vec![],
+            },
+            GameEvent::JudgeChose {
+                round: 1,
+                judge: String::new(),
+                chosen_player: String::new(),
+                answer: None,
+            },
+            GameEvent::ScoresChanged { ranking: vec![] },
+            GameEvent::PlayerRenamed {
+                player: String::new(),
+                display_name: String::new(),
+            },
+            GameEvent::GameOver { ranking: vec![] },
+        ];
+
+        for event in events.iter() {
+            let fields = serialized_fields(event);
+            let event_type = serde_json::to_value(event).unwrap()["type"].clone();
+            let declaration = format!("type: {};", event_type);
+            let line = TYPESCRIPT_TYPES
+                .lines()
+                .find(|line| line.contains(&declaration))
+                .unwrap_or_else(|| panic!("{} is not declared", event_type));
+
+            for field in fields {
+                assert!(
+                    line.contains(&format!("{}:", field)),
+                    "{} lacks {}",
+                    event_type,
+                    field
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn error_codes_are_declared() {
+        let errors = [
+            GameCoreError::Custom(String::new()),
+            GameCoreError::QuestionBlanksAndNumAnswersMismatch {
+                num_blanks: 0,
+                num_answers: 0,
+            },
+            GameCoreError::PlayerChoosingCardOutOfHandBound {
+                chosen_ind: 0,
+                hand_bound: 0,
+            },
+            GameCoreError::PlayerChoosingTheSameCardMultipleTimes { chosen_ind: 0 },
+            GameCoreError::JudgeTryingToSubmitAnswers {
+                judge_name: String::new(),
+                display_name: String::new(),
+            },
+            GameCoreError::PlayerAlreadySubmittedAnswers {
+                player_name: String::new(),
+                display_name: String::new(),
+            },
+            GameCoreError::InsufficientAnswerCardsToDeal {
+                num_players: 0,
+                each_deal: 0,
+                num_answer_cards: 0,
+            },
+            GameCoreError::NoQuestionCards,
+            GameCoreError::NoActiveQuestionCard,
+            GameCoreError::NoActiveJudge,
+            GameCoreError::NotEnoughPlayers {
+                num_players: 0,
+                min_players: 0,
+            },
+            GameCoreError::TooManyPlayers {
+                num_players: 0,
+                max_players: 0,
+            },
+            GameCoreError::InvalidPlayerBounds {
+                min_players: 0,
+                max_players: 0,
+            },
+            GameCoreError::PlayerAlreadyExists {
+                name: String::new(),
+                display_name: String::new(),
+            },
+            GameCoreError::PlayerDoesNotExist {
+                name: String::new(),
+                display_name: String::new(),
+            },
+            GameCoreError::GameNotStarted,
+            GameCoreError::GameAlreadyInProgress,
+            GameCoreError::GameEnded,
+            GameCoreError::HandSizeCannotBeZero,
+            GameCoreError::JudgeCannotBeChosen,
+            GameCoreError::InvalidDeck {
+                reason: String::new(),
+            },
+            GameCoreError::CardNotInHand {
+                card_id: String::new(),
+            },
+            GameCoreError::AnswerDeckExhausted {
+                needed: 0,
+                available: 0,
+            },
+            GameCoreError::InvalidConfig {
+                reason: String::new(),
+            },
+            GameCoreError::RedrawsNotAllowed,
+            GameCoreError::SessionAlreadyExists {
+                session_id: String::new(),
+            },
+            GameCoreError::SessionDoesNotExist {
+                session_id: String::new(),
+            },
+            GameCoreError::NotSessionOwner {
+                session_id: String::new(),
+                player_name: String::new(),
+            },
+            GameCoreError::OwnerAlreadyHasLobby {
+                owner: String::new(),
+                session_id: String::new(),
+            },
+            GameCoreError::NotALobby {
+                session_id: String::new(),
+            },
+            GameCoreError::NotAGame {
+                session_id: String::new(),
+            },
+        ];
+
+        let start = TYPESCRIPT_TYPES
+            .find("export type GameCoreErrorCode")
+            .unwrap();
+        let declaration = &TYPESCRIPT_TYPES[start..];
+        let declaration = &declaration[..declaration.find(';').unwrap()];
+        let declared_codes: BTreeSet<_> = declaration
+            .split('"')
+            .skip(1)
+            .step_by(2)
+            .map(ToOwned::to_owned)
+            .collect();
+        let codes: BTreeSet<_> = errors.iter().map(GameCoreError::code).collect();
+
+        assert_eq!(codes, declared_codes);
+    }
+}
@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::game_state::{CardId, HandCard};
+
+/// What everyone may see, e.g. in a shared channel.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicView {
+    /// ONE-based; 0 before the first round.
+    pub round: usize,
+    pub judge: Option<String>,
+    pub question: Option<String>,
+    /// In the order of `ordered_players()`.
+    pub players: Vec<String>,
+    /// Players who submitted in this round. What they submitted stays secret.
+    pub submitted: Vec<String>,
+    /// Players who still have to submit in this round.
+    pub waiting_for: Vec<String>,
+    /// Combined answers in the order shown to the Judge, without who wrote them.
+    /// Empty until every player has submitted.
+    pub answers: Vec<String>,
+    /// In descending order of Awesome Points.
+    pub ranking: Vec<(String, i32)>,
+    pub is_game_over: bool,
+    /// { player ID: display name }
+    pub display_names: HashMap<String, String>,
+}
+
+/// What one player may see: the public view plus their own hand.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerView {
+    pub player: String,
+    pub display_name: String,
+    pub is_judge: bool,
+    pub hand: Vec<HandCard>,
+    /// IDs of the cards this player submitted in this round, in the order played.
+    pub submitted_card_ids: Option<Vec<CardId>>,
+    pub public: PublicView,
+}
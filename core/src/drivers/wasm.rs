@@ -1,32 +1,114 @@
-use std::convert::TryInto;
-
-use wasm_bindgen::prelude::*;
-
-use crate::{drivers::GameCoreDriver, error::GameCoreError};
-
-use super::generic::{GenericDriver, GenericDriverBuilder};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    convert::TryInto,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::{
+    config::{GameConfig, PRESET_NAMES},
+    decks::parse_json_against_humanity,
+    drivers::GameCoreDriver,
+    error::GameCoreError,
+    events::{GameEvent, ListenerId},
+    game_state::ExhaustionPolicy,
+    i18n::{self, Locale},
+    sessions::{SessionGame, SessionLobby, SessionRegistry},
+};
+
+use super::{
+    generic::{GenericDriver, GenericDriverBuilder},
+    typescript::*,
+};
 
 /// Expose the API of `GenericDriverBuilder` to WASM
+///
+/// Clones are handles to the same builder, e.g. one held by a `WasmSessionRegistry`.
 #[wasm_bindgen]
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct WasmDriverBuilder {
-    generic_driver_builder: GenericDriverBuilder,
+    generic_driver_builder: Rc<RefCell<GenericDriverBuilder>>,
+    // Set when a `WasmSessionRegistry` starts the lobby's game, after which every handle fails
+    // to `build()` another one with `GameAlreadyInProgress`.
+    started: Rc<Cell<bool>>,
 }
 
 /// Expose the API of `GenericDriver` to WASM
+///
+/// Clones are handles to the same game, e.g. one held by a `WasmSessionRegistry`.
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct WasmDriver {
-    generic_driver: GenericDriver,
+    generic_driver: Rc<RefCell<GenericDriver>>,
+
+    // Filled by a listener on `generic_driver` and passed on to the JavaScript listeners at the
+    // end of every call, since those cannot be sent into the generic driver.
+    pending_events: Arc<Mutex<Vec<GameEvent>>>,
+    listeners: Rc<RefCell<JsListeners>>,
+    // Set by `end_game()`, after which every handle fails with `GameEnded`.
+    ended: Rc<Cell<bool>>,
+}
+
+#[derive(Default)]
+struct JsListeners {
+    next_id: ListenerId,
+    listeners: Vec<(ListenerId, js_sys::Function)>,
 }
 
 pub type Error = GameCoreError;
 
+/// Thrown to JavaScript as an `Error` { name: "GameCoreError", message: string, code: string,
+/// details: object, key: string, args: object }, e.g. { code: "notEnoughPlayers",
+/// details: { numPlayers: 2, minPlayers: 3 }, key: "error.notEnoughPlayers", args: (...) }.
+///
+/// `message` is in English; pass `key` and `args` to `translate` for other languages.
 impl From<Error> for JsValue {
     fn from(val: Error) -> Self {
-        val.to_string().into()
+        let error = js_sys::Error::new(&val.to_string());
+        error.set_name("GameCoreError");
+
+        let message = val.message();
+        let properties = [
+            ("code", JsValue::from(val.code())),
+            ("details", JsValue::from_serde(&val.details()).unwrap()),
+            ("key", JsValue::from(message.key)),
+            ("args", JsValue::from_serde(&message.args).unwrap()),
+        ];
+        for (name, value) in properties.iter() {
+            js_sys::Reflect::set(&error, &JsValue::from(*name), value).unwrap();
+        }
+
+        error.into()
     }
 }
 
+/// From JavaScript:
+/// - Input: `locale`: string (e.g. "es-ES"; unknown locales fall back to English), `key`: string,
+///   `args`: undefined | object
+/// - Return a string (the message, or `key` if the catalogue does not have it)
+#[wasm_bindgen(js_name = translate)]
+pub fn translate(locale: &str, key: &str, args: JsValue) -> String {
+    let locale = Locale::from_code(locale).unwrap_or_default();
+    let args = args.into_serde().unwrap_or_default();
+
+    i18n::translate(locale, key, &args)
+}
+
+/// Return an array of strings (codes of the languages with a message catalogue)
+#[wasm_bindgen(js_name = localeCodes)]
+pub fn locale_codes() -> JsStrings {
+    let codes: Vec<_> = Locale::ALL.iter().map(|locale| locale.code()).collect();
+
+    to_js(&codes)
+}
+
+/// Serialize `value` for JavaScript as the TypeScript type `T` declares it.
+fn to_js<T: JsCast>(value: &impl serde::Serialize) -> T {
+    JsValue::from_serde(value).unwrap().unchecked_into()
+}
+
 #[wasm_bindgen]
 impl WasmDriverBuilder {
     #[wasm_bindgen(constructor)]
@@ -34,71 +116,287 @@ impl WasmDriverBuilder {
         Default::default()
     }
 
+    /// Return an array of strings (names accepted by `usePreset`)
+    #[wasm_bindgen(js_name = presetNames)]
+    pub fn preset_names() -> JsStrings {
+        to_js(&PRESET_NAMES)
+    }
+
+    /// From JavaScript:
+    /// - Input: `name`: string (one of `presetNames()`, case-insensitive)
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = usePreset)]
+    pub fn use_preset(&mut self, name: &str) -> Result<(), Error> {
+        let config = GameConfig::preset(name).ok_or_else(|| GameCoreError::InvalidConfig {
+            reason: format!("There is no preset named {}.", name),
+        })?;
+
+        self.generic_driver_builder.borrow_mut().set_config(config)
+    }
+
+    /// From JavaScript:
+    /// - Input: `json`: string (a saved config; missing fields come from the "Classic" preset)
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = loadConfig)]
+    pub fn load_config(&mut self, json: &str) -> Result<(), Error> {
+        self.generic_driver_builder
+            .borrow_mut()
+            .set_config(GameConfig::from_json(json)?)
+    }
+
+    /// Return a string (the current config as JSON, readable by `loadConfig`)
+    #[wasm_bindgen(js_name = saveConfig)]
+    pub fn save_config(&self) -> String {
+        self.generic_driver_builder.borrow().config().to_json()
+    }
+
+    /// Return a `GameConfig`
+    #[wasm_bindgen(js_name = config)]
+    pub fn config(&self) -> JsGameConfig {
+        to_js(self.generic_driver_builder.borrow().config())
+    }
+
     #[wasm_bindgen(js_name = setHandSize)]
     pub fn set_hand_size(&mut self, hand_size: i32) -> Result<(), Error> {
         let hand_size = hand_size
             .try_into()
             .map_err(|_| "Hand size cannot be negative.")?;
 
-        self.generic_driver_builder.set_hand_size(hand_size)?;
+        self.generic_driver_builder
+            .borrow_mut()
+            .set_hand_size(hand_size)?;
 
         Ok(())
     }
 
+    /// From JavaScript:
+    /// - Input: `winTarget`: number (>= 1)
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = setWinTarget)]
+    pub fn set_win_target(&mut self, win_target: u32) -> Result<(), Error> {
+        self.generic_driver_builder
+            .borrow_mut()
+            .set_win_target(win_target as usize)
+    }
+
+    /// From JavaScript:
+    /// - Input: `minPlayers`: number, `maxPlayers`: number
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = setPlayerBounds)]
+    pub fn set_player_bounds(&mut self, min_players: u32, max_players: u32) -> Result<(), Error> {
+        self.generic_driver_builder
+            .borrow_mut()
+            .set_player_bounds(min_players as usize, max_players as usize)?;
+
+        Ok(())
+    }
+
+    /// From JavaScript:
+    /// - Input: `policy`: "error" | "shortHands" | "recycle" | "endGame"
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = setExhaustionPolicy)]
+    pub fn set_exhaustion_policy(&mut self, policy: &str) -> Result<(), Error> {
+        let policy = match policy {
+            "error" => ExhaustionPolicy::Error,
+            "shortHands" => ExhaustionPolicy::ShortHands,
+            "recycle" => ExhaustionPolicy::Recycle,
+            "endGame" => ExhaustionPolicy::EndGame,
+            _ => return Err(format!("Unknown exhaustion policy: {}", policy).into()),
+        };
+
+        self.generic_driver_builder
+            .borrow_mut()
+            .set_exhaustion_policy(policy);
+
+        Ok(())
+    }
+
+    /// From JavaScript:
+    /// - Input: `player_name`: string (a stable ID), `display_name`: undefined | string (defaults to the ID)
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
     #[wasm_bindgen(js_name = addPlayer)]
-    pub fn add_player(&mut self, player_name: &str) -> Result<(), Error> {
-        self.generic_driver_builder.add_player(player_name)
+    pub fn add_player(
+        &mut self,
+        player_name: &str,
+        display_name: Option<String>,
+    ) -> Result<(), Error> {
+        match display_name {
+            Some(display_name) => self
+                .generic_driver_builder
+                .borrow_mut()
+                .add_player_with_name(player_name, display_name),
+            None => self
+                .generic_driver_builder
+                .borrow_mut()
+                .add_player(player_name),
+        }
+    }
+
+    #[wasm_bindgen(js_name = renamePlayer)]
+    pub fn rename_player(&mut self, player_name: &str, display_name: &str) -> Result<(), Error> {
+        self.generic_driver_builder
+            .borrow_mut()
+            .rename_player(player_name, display_name)
     }
 
     #[wasm_bindgen(js_name = removePlayer)]
     pub fn remove_player(&mut self, player_name: &str) -> Result<(), Error> {
-        self.generic_driver_builder.remove_player(player_name)
+        self.generic_driver_builder
+            .borrow_mut()
+            .remove_player(player_name)
     }
 
     #[wasm_bindgen(js_name = removeAllPlayers)]
     pub fn remove_all_players(&mut self) {
-        self.generic_driver_builder.remove_all_players()
+        self.generic_driver_builder
+            .borrow_mut()
+            .remove_all_players()
     }
 
     #[wasm_bindgen(js_name = addNewQuestions)]
-    pub fn add_new_questions(&mut self, questions: JsValue) -> Result<(), Error> {
+    pub fn add_new_questions(&mut self, questions: JsStrings) -> Result<(), Error> {
         let questions: Vec<String> = questions
             .into_serde()
             .map_err(|_| "Supplied questions are not an array of strings.")?;
 
-        self.generic_driver_builder.add_new_questions(questions);
+        self.generic_driver_builder
+            .borrow_mut()
+            .add_new_questions(questions);
 
         Ok(())
     }
 
     #[wasm_bindgen(js_name = clearAllQuestions)]
     pub fn clear_all_questions(&mut self) {
-        self.generic_driver_builder.clear_all_questions();
+        self.generic_driver_builder
+            .borrow_mut()
+            .clear_all_questions();
     }
 
     #[wasm_bindgen(js_name = addNewAnswers)]
-    pub fn add_new_answers(&mut self, answers: JsValue) -> Result<(), Error> {
+    pub fn add_new_answers(&mut self, answers: JsStrings) -> Result<(), Error> {
         let answers: Vec<String> = answers
             .into_serde()
             .map_err(|_| "Supplied answers are not an array of strings.")?;
 
-        self.generic_driver_builder.add_new_answers(answers);
+        self.generic_driver_builder
+            .borrow_mut()
+            .add_new_answers(answers);
 
         Ok(())
     }
 
     #[wasm_bindgen(js_name = clearAllAnswers)]
     pub fn clear_all_answers(&mut self) {
-        self.generic_driver_builder.clear_all_answers();
+        self.generic_driver_builder.borrow_mut().clear_all_answers();
+    }
+
+    /// From JavaScript:
+    /// - Input: `json`: string (a deck in the JSON Against Humanity format)
+    /// - Success: an array of objects { location: string, reason: string } (skipped or suspicious entries)
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = importJsonAgainstHumanity)]
+    pub fn import_json_against_humanity(&mut self, json: &str) -> Result<JsValue, Error> {
+        let import = parse_json_against_humanity(json)?;
+
+        self.generic_driver_builder
+            .borrow_mut()
+            .add_deck(&import.deck);
+
+        Ok(JsValue::from_serde(&import.issues).unwrap())
+    }
+
+    /// Return an object { issues: \[{ severity: string, cardKind: string, card: string, problem: { kind: string, ... } }\] }
+    #[wasm_bindgen(js_name = validate)]
+    pub fn validate(&self) -> JsValue {
+        JsValue::from_serde(&self.generic_driver_builder.borrow().validate()).unwrap()
+    }
+
+    /// Return an object { ready: boolean, problems: \[string\], warnings: \[(same as the issues of `validate`)\] }
+    ///
+    /// `problems` are the error messages `build` would throw, all at once.
+    #[wasm_bindgen(js_name = check)]
+    pub fn check(&self) -> JsValue {
+        let check = self.generic_driver_builder.borrow().check();
+
+        JsValue::from_serde(&serde_json::json!({
+            "ready": check.is_ready(),
+            "problems": check.problems.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            "warnings": check.warnings,
+        }))
+        .unwrap()
     }
 
     /// - Success: a WasmDriver (game driver)
-    /// - Failure: a string (error message)
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
     #[wasm_bindgen(js_name = build)]
     pub fn build(&self) -> Result<WasmDriver, Error> {
+        if self.started.get() {
+            return Err(GameCoreError::GameAlreadyInProgress);
+        }
+
         self.generic_driver_builder
+            .borrow()
             .build()
-            .map(|generic_driver| WasmDriver { generic_driver })
+            .map(WasmDriver::new)
+    }
+}
+
+impl WasmDriver {
+    fn new(mut generic_driver: GenericDriver) -> Self {
+        let pending_events = Arc::new(Mutex::new(vec![]));
+        {
+            let pending_events = pending_events.clone();
+            generic_driver.subscribe(move |e| pending_events.lock().unwrap().push(e.clone()));
+        }
+
+        Self {
+            generic_driver: Rc::new(RefCell::new(generic_driver)),
+            pending_events,
+            listeners: Default::default(),
+            ended: Default::default(),
+        }
+    }
+
+    fn driver(&self) -> Result<Ref<'_, GenericDriver>, Error> {
+        if self.ended.get() {
+            return Err(GameCoreError::GameEnded);
+        }
+
+        Ok(self.generic_driver.borrow())
+    }
+
+    fn driver_mut(&self) -> Result<RefMut<'_, GenericDriver>, Error> {
+        if self.ended.get() {
+            return Err(GameCoreError::GameEnded);
+        }
+
+        Ok(self.generic_driver.borrow_mut())
+    }
+
+    /// Errors thrown by the listeners are ignored.
+    ///
+    /// No borrow is held while the listeners run, so they may call back into the driver.
+    fn flush_events(&self) {
+        let events = std::mem::take(&mut *self.pending_events.lock().unwrap());
+        if self.listeners.borrow().listeners.is_empty() {
+            return;
+        }
+
+        let listeners: Vec<_> = self
+            .listeners
+            .borrow()
+            .listeners
+            .iter()
+            .map(|(_, listener)| listener.clone())
+            .collect();
+
+        for event in events {
+            let event = JsValue::from_serde(&event).unwrap();
+            for listener in listeners.iter() {
+                let _ = listener.call1(&JsValue::NULL, &event);
+            }
+        }
     }
 }
 
@@ -107,24 +405,23 @@ impl WasmDriverBuilder {
 impl WasmDriver {
     /// Return an array of strings (ordered all player names)
     #[wasm_bindgen(js_name = orderedPlayers)]
-    pub fn ordered_players(&self) -> JsValue {
-        let ordered_players = self.generic_driver.ordered_players();
-
-        JsValue::from_serde(&ordered_players).unwrap()
+    pub fn ordered_players(&self) -> Result<JsStrings, Error> {
+        Ok(to_js(&self.driver()?.ordered_players()))
     }
 
-    /// Return an object { judge: string, question: string, player_hands: { `name`: \[`answer`: string\] } }
+    /// Return a `RoundInformation`. Each hand is only in `viewFor()`.
     #[wasm_bindgen(js_name = startRound)]
-    pub fn start_round(&mut self) -> JsValue {
-        let round_info = self.generic_driver.start_round();
+    pub fn start_round(&mut self) -> Result<JsRoundInformation, Error> {
+        let round_info = self.driver_mut()?.start_round();
+        self.flush_events();
 
-        JsValue::from_serde(&round_info).unwrap()
+        Ok(to_js(&round_info))
     }
 
     /// From JavaScript:
     /// - Input: `player_name`: string, `answer_indices`: \[number >= 0\]
-    /// - Success: null | an array of tuples of two strings (player names and their combined answers)
-    /// - Failure: a string (error message)
+    /// - Success: null (until every player has submitted) | `Submission[]`
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
     ///
     /// `answer_indices` correspond to ZERO-based indices of the player's hand.
     #[wasm_bindgen(js_name = submitAnswers)]
@@ -132,39 +429,417 @@ impl WasmDriver {
         &mut self,
         player_name: &str,
         answer_indices: &[usize],
-    ) -> Result<JsValue, GameCoreError> {
-        self.generic_driver
-            .submit_answers(player_name, answer_indices.to_owned())
-            .map(|submitted_answers_option| JsValue::from_serde(&submitted_answers_option).unwrap())
+    ) -> Result<JsSubmissions, GameCoreError> {
+        let submitted_answers = self
+            .driver_mut()?
+            .submit_answers(player_name, answer_indices.to_owned());
+        self.flush_events();
+
+        submitted_answers.map(|submitted_answers_option| to_js(&submitted_answers_option))
+    }
+
+    /// From JavaScript:
+    /// - Input: `player_name`: string, `card_ids`: an array of strings (IDs from `viewFor().hand`)
+    /// - Success: null (until every player has submitted) | `Submission[]`
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = submitAnswersByIds)]
+    pub fn submit_answers_by_ids(
+        &mut self,
+        player_name: &str,
+        card_ids: JsStrings,
+    ) -> Result<JsSubmissions, GameCoreError> {
+        let card_ids: Vec<String> = card_ids
+            .into_serde()
+            .map_err(|_| "Supplied card IDs are not an array of strings.")?;
+
+        let submitted_answers = self
+            .driver_mut()?
+            .submit_answers_by_ids(player_name, card_ids);
+        self.flush_events();
+
+        submitted_answers.map(|submitted_answers_option| to_js(&submitted_answers_option))
+    }
+
+    /// Return a number (ONE-based round number; 0 before the first round)
+    #[wasm_bindgen(js_name = round)]
+    pub fn round(&self) -> Result<usize, Error> {
+        Ok(self.driver()?.round())
+    }
+
+    /// Return null | a string (null before the first round)
+    #[wasm_bindgen(js_name = currentJudge)]
+    pub fn current_judge(&self) -> Result<Option<String>, Error> {
+        Ok(self.driver()?.current_judge())
+    }
+
+    /// Return null | a string (null before the first round)
+    #[wasm_bindgen(js_name = currentQuestion)]
+    pub fn current_question(&self) -> Result<Option<String>, Error> {
+        Ok(self.driver()?.current_question())
+    }
+
+    /// Return an array of strings (players who submitted in the current round)
+    #[wasm_bindgen(js_name = submittedPlayers)]
+    pub fn submitted_players(&self) -> Result<JsStrings, Error> {
+        Ok(to_js(&self.driver()?.submitted_players()))
+    }
+
+    /// Return an array of strings (players who still owe an answer in the current round)
+    #[wasm_bindgen(js_name = pendingPlayers)]
+    pub fn pending_players(&self) -> Result<JsStrings, Error> {
+        Ok(to_js(&self.driver()?.pending_players()))
+    }
+
+    /// Return a `Ranking`
+    #[wasm_bindgen(js_name = scores)]
+    pub fn scores(&self) -> Result<JsRanking, Error> {
+        Ok(to_js(&self.driver()?.scores()))
+    }
+
+    /// Return a `DeckSizes`
+    #[wasm_bindgen(js_name = deckSizes)]
+    pub fn deck_sizes(&self) -> Result<JsDeckSizes, Error> {
+        Ok(to_js(&self.driver()?.deck_sizes()))
+    }
+
+    /// Return a `PublicView`
+    ///
+    /// Safe to post in a shared channel: `answers` do not say who wrote them.
+    #[wasm_bindgen(js_name = publicView)]
+    pub fn public_view(&self) -> Result<JsPublicView, Error> {
+        Ok(to_js(&self.driver()?.public_view()))
+    }
+
+    /// From JavaScript:
+    /// - Input: `player_name`: string
+    /// - Success: a `PlayerView`
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = viewFor)]
+    pub fn view_for(&self, player_name: &str) -> Result<JsPlayerView, Error> {
+        self.driver()?
+            .view_for(player_name)
+            .map(|view| to_js(&view))
+    }
+
+    /// From JavaScript:
+    /// - Input: `listener`: a function called with every `GameEvent` from now on
+    /// - Return a number (to pass to `unsubscribe`)
+    #[wasm_bindgen(js_name = subscribe)]
+    pub fn subscribe(&mut self, listener: JsEventListener) -> Result<usize, Error> {
+        self.driver()?;
+
+        let mut listeners = self.listeners.borrow_mut();
+        let id = listeners.next_id;
+        listeners.next_id += 1;
+        listeners.listeners.push((id, listener.unchecked_into()));
+
+        Ok(id)
+    }
+
+    /// Return a boolean (false if no listener has the ID)
+    #[wasm_bindgen(js_name = unsubscribe)]
+    pub fn unsubscribe(&mut self, listener_id: usize) -> Result<bool, Error> {
+        self.driver()?;
+
+        let listeners = &mut self.listeners.borrow_mut().listeners;
+        let num_listeners = listeners.len();
+        listeners.retain(|(id, _)| *id != listener_id);
+
+        Ok(listeners.len() != num_listeners)
+    }
+
+    /// Return a string, or undefined if no player has the ID
+    #[wasm_bindgen(js_name = displayName)]
+    pub fn display_name(&self, player_name: &str) -> Result<Option<String>, Error> {
+        Ok(self.driver()?.display_name(player_name))
+    }
+
+    /// Return an object { `name`: `display name`: string }
+    #[wasm_bindgen(js_name = displayNames)]
+    pub fn display_names(&self) -> Result<JsValue, Error> {
+        Ok(to_js(&self.driver()?.display_names()))
+    }
+
+    /// From JavaScript:
+    /// - Input: `player_name`: string, `display_name`: string
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    ///
+    /// Listeners receive a "playerRenamed" event.
+    #[wasm_bindgen(js_name = renamePlayer)]
+    pub fn rename_player(&mut self, player_name: &str, display_name: &str) -> Result<(), Error> {
+        let result = self.driver_mut()?.rename_player(player_name, display_name);
+        self.flush_events();
+
+        result
+    }
+
+    /// Return a `GameConfig` (the settings the game was built with)
+    #[wasm_bindgen(js_name = config)]
+    pub fn config(&self) -> Result<JsGameConfig, Error> {
+        Ok(to_js(self.driver()?.config()))
+    }
+
+    /// Return a boolean (true once the Answer deck ran out under the "endGame" exhaustion policy)
+    #[wasm_bindgen(js_name = isGameOver)]
+    pub fn is_game_over(&self) -> Result<bool, Error> {
+        Ok(self.driver()?.is_game_over())
+    }
+
+    /// Return an object { `name`: \[`card id`: string\] } (cards submitted in the current round)
+    #[wasm_bindgen(js_name = submittedCardIds)]
+    pub fn submitted_card_ids(&self) -> Result<JsValue, Error> {
+        Ok(JsValue::from_serde(&self.driver()?.submitted_card_ids()).unwrap())
     }
 
     /// From JavaScript:
     /// - Input: `player_names`: an array of strings
     #[wasm_bindgen(js_name = redrawHands)]
-    pub fn redraw_hands(&mut self, player_names: JsValue) -> Result<(), GameCoreError> {
+    pub fn redraw_hands(&mut self, player_names: JsStrings) -> Result<(), GameCoreError> {
         let player_names: Vec<String> = player_names
             .into_serde()
             .map_err(|_| "Supplied player names are not an array of strings.")?;
 
-        self.generic_driver.redraw_hands(player_names)
+        self.driver_mut()?.redraw_hands(player_names)
     }
 
     /// From JavaScript:
     /// - Input: `chosen_player`: string
-    /// - Success: an array of tuples of a string and a number (player names and their awesome points, sorted in descending order of awesome points)
-    /// - Failure: a string (error message)
+    /// - Success: a `Ranking`
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
     #[wasm_bindgen(js_name = endRound)]
-    pub fn end_round(&mut self, chosen_player: &str) -> Result<JsValue, GameCoreError> {
-        self.generic_driver
-            .end_round(chosen_player)
-            .map(|ranking| JsValue::from_serde(&ranking).unwrap())
+    pub fn end_round(&mut self, chosen_player: &str) -> Result<JsRanking, GameCoreError> {
+        let ranking = self.driver_mut()?.end_round(chosen_player);
+        self.flush_events();
+
+        ranking.map(|ranking| to_js(&ranking))
     }
 
-    /// Invalidate this driver object.
+    /// Invalidate this driver object and every handle to the same game: their methods then fail
+    /// with "gameEnded". Ending a game again does nothing.
     ///
-    /// No side effects.
+    /// The listeners receive a "gameOver" event, unless they already did.
     #[wasm_bindgen(js_name = endGame)]
     pub fn end_game(self) {
-        self.generic_driver.end_game()
+        if self.ended.replace(true) {
+            return;
+        }
+
+        self.generic_driver.borrow_mut().emit_game_over();
+        self.flush_events();
+    }
+}
+
+impl SessionLobby for WasmDriverBuilder {
+    type Game = WasmDriver;
+
+    fn players(&self) -> Vec<String> {
+        self.generic_driver_builder.borrow().players()
+    }
+
+    fn build(&self) -> Result<WasmDriver, GameCoreError> {
+        WasmDriverBuilder::build(self)
+    }
+}
+
+impl SessionGame for WasmDriver {
+    fn players(&self) -> Vec<String> {
+        self.generic_driver.borrow().ordered_players()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.generic_driver.borrow().is_finished()
+    }
+
+    fn end(self) {
+        self.end_game();
+    }
+}
+
+/// Expose the API of `SessionRegistry` to WASM
+///
+/// Builders and drivers it returns are handles: changes made through them are seen by the
+/// registry. Builders of started games cannot build another one, and handles to ended games are
+/// stale.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct WasmSessionRegistry {
+    sessions: SessionRegistry<WasmDriverBuilder>,
+}
+
+#[wasm_bindgen]
+impl WasmSessionRegistry {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// From JavaScript:
+    /// - Input: `session_id`: string, `owner`: string
+    /// - Success: a WasmDriverBuilder (the new lobby)
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = createLobby)]
+    pub fn create_lobby(
+        &mut self,
+        session_id: &str,
+        owner: &str,
+    ) -> Result<WasmDriverBuilder, Error> {
+        self.sessions
+            .create_lobby(session_id, owner)
+            .map(|lobby| lobby.clone())
+    }
+
+    /// From JavaScript:
+    /// - Input: `session_id`: string
+    /// - Success: a WasmDriverBuilder
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = lobby)]
+    pub fn lobby(&self, session_id: &str) -> Result<WasmDriverBuilder, Error> {
+        self.sessions.lobby(session_id).cloned()
+    }
+
+    /// Return a string (the session ID), or undefined if the owner has no lobby
+    #[wasm_bindgen(js_name = lobbyOfOwner)]
+    pub fn lobby_of_owner(&self, owner: &str) -> Option<String> {
+        self.sessions.lobby_of_owner(owner).map(ToOwned::to_owned)
+    }
+
+    /// From JavaScript:
+    /// - Input: `session_id`: string, `requester`: string (must be the owner)
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = closeLobby)]
+    pub fn close_lobby(&mut self, session_id: &str, requester: &str) -> Result<(), Error> {
+        self.sessions.close_lobby(session_id, requester)
+    }
+
+    /// From JavaScript:
+    /// - Input: `lobby_id`: string, `requester`: string (must be the owner),
+    ///   `game_id`: string (the lobby's ID, or a new one for the game)
+    /// - Success: a WasmDriver
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = startGame)]
+    pub fn start_game(
+        &mut self,
+        lobby_id: &str,
+        requester: &str,
+        game_id: &str,
+    ) -> Result<WasmDriver, Error> {
+        let lobby = self.sessions.lobby(lobby_id)?.clone();
+        let game = self
+            .sessions
+            .start_game(lobby_id, requester, game_id)?
+            .clone();
+        lobby.started.set(true);
+
+        Ok(game)
+    }
+
+    /// From JavaScript:
+    /// - Input: `session_id`: string
+    /// - Success: a WasmDriver
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = game)]
+    pub fn game(&self, session_id: &str) -> Result<WasmDriver, Error> {
+        self.sessions.game(session_id).cloned()
+    }
+
+    /// From JavaScript:
+    /// - Input: `session_id`: string
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    ///
+    /// The game's listeners receive a "gameOver" event, unless they already did.
+    #[wasm_bindgen(js_name = endGame)]
+    pub fn end_game(&mut self, session_id: &str) -> Result<(), Error> {
+        self.sessions.end_game(session_id)
+    }
+
+    /// Return an array of strings (session IDs of the games that were over or had a winner,
+    /// now ended)
+    #[wasm_bindgen(js_name = removeFinishedGames)]
+    pub fn remove_finished_games(&mut self) -> JsStrings {
+        to_js(&self.sessions.remove_finished_games())
+    }
+
+    /// From JavaScript:
+    /// - Input: `session_id`: string
+    /// - Success: a string
+    /// - Failure: a `GameCoreError` (see `From<Error> for JsValue`)
+    #[wasm_bindgen(js_name = owner)]
+    pub fn owner(&self, session_id: &str) -> Result<String, Error> {
+        self.sessions.owner(session_id).map(ToOwned::to_owned)
+    }
+
+    /// Return "lobby" | "game", or undefined if there is no such session
+    #[wasm_bindgen(js_name = stage)]
+    pub fn stage(&self, session_id: &str) -> JsValue {
+        match self.sessions.stage(session_id) {
+            Some(stage) => JsValue::from_serde(&stage).unwrap(),
+            None => JsValue::UNDEFINED,
+        }
+    }
+
+    /// Return an array of strings (all session IDs, sorted)
+    #[wasm_bindgen(js_name = sessionIds)]
+    pub fn session_ids(&self) -> JsStrings {
+        to_js(&self.sessions.session_ids())
+    }
+
+    /// Return an array of strings (IDs of the lobbies and games the player is in, sorted)
+    #[wasm_bindgen(js_name = sessionsOfPlayer)]
+    pub fn sessions_of_player(&self, player_name: &str) -> JsStrings {
+        to_js(&self.sessions.sessions_of_player(player_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ended_game_fails_through_every_handle() {
+        let mut builder = GenericDriverBuilder::new();
+        for player in ["A", "B", "C"].iter() {
+            builder.add_player(*player).unwrap();
+        }
+        builder.add_new_questions(vec!["_?"]);
+        builder.add_new_answers((0..40).map(|answer| answer.to_string()));
+
+        let driver = WasmDriver::new(builder.build().unwrap());
+        let mut handle = driver.clone();
+        assert_eq!(handle.round(), Ok(0));
+
+        driver.end_game();
+        assert_eq!(handle.round(), Err(GameCoreError::GameEnded));
+        assert_eq!(handle.is_game_over(), Err(GameCoreError::GameEnded));
+        assert_eq!(
+            handle.rename_player("A", "Alex"),
+            Err(GameCoreError::GameEnded)
+        );
+        assert_eq!(handle.unsubscribe(0), Err(GameCoreError::GameEnded));
+        handle.clone().end_game();
+    }
+
+    #[test]
+    fn started_lobby_cannot_build_another_game() {
+        let mut sessions = WasmSessionRegistry::new();
+        let lobby = sessions.create_lobby("lobby", "A").unwrap();
+        {
+            let mut builder = lobby.generic_driver_builder.borrow_mut();
+            for player in ["A", "B", "C"].iter() {
+                builder.add_player(*player).unwrap();
+            }
+            builder.add_new_questions(vec!["_?"]);
+            builder.add_new_answers((0..40).map(|answer| answer.to_string()));
+        }
+        let stale = sessions.lobby("lobby").unwrap();
+
+        sessions.start_game("lobby", "A", "game").unwrap();
+        assert_eq!(
+            stale.build().err(),
+            Some(GameCoreError::GameAlreadyInProgress)
+        );
+        assert_eq!(
+            lobby.build().err(),
+            Some(GameCoreError::GameAlreadyInProgress)
+        );
     }
 }
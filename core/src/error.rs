@@ -1,27 +1,43 @@
 use std::fmt::Display;
 
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::i18n::{Locale, Message};
+
 use GameCoreError::*;
 
-#[derive(Debug, PartialEq, Eq)]
+/// Serialized as { `code`: { `field`: value } }, where `code` is the variant name in camelCase.
+/// The codes and fields are part of the public API: rename them only with care.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub enum GameCoreError {
     Custom(String),
+    #[serde(rename_all = "camelCase")]
     QuestionBlanksAndNumAnswersMismatch {
         num_blanks: usize,
         num_answers: usize,
     },
+    #[serde(rename_all = "camelCase")]
     PlayerChoosingCardOutOfHandBound {
         chosen_ind: usize,
         hand_bound: usize,
     },
+    #[serde(rename_all = "camelCase")]
     PlayerChoosingTheSameCardMultipleTimes {
         chosen_ind: usize,
     },
+    #[serde(rename_all = "camelCase")]
     JudgeTryingToSubmitAnswers {
         judge_name: String,
+        display_name: String,
     },
+    #[serde(rename_all = "camelCase")]
     PlayerAlreadySubmittedAnswers {
         player_name: String,
+        display_name: String,
     },
+    #[serde(rename_all = "camelCase")]
     InsufficientAnswerCardsToDeal {
         num_players: usize,
         each_deal: usize,
@@ -30,86 +46,175 @@ pub enum GameCoreError {
     NoQuestionCards,
     NoActiveQuestionCard,
     NoActiveJudge,
+    #[serde(rename_all = "camelCase")]
     NotEnoughPlayers {
         num_players: usize,
+        min_players: usize,
+    },
+    #[serde(rename_all = "camelCase")]
+    TooManyPlayers {
+        num_players: usize,
+        max_players: usize,
+    },
+    #[serde(rename_all = "camelCase")]
+    InvalidPlayerBounds {
+        min_players: usize,
+        max_players: usize,
     },
+    #[serde(rename_all = "camelCase")]
     PlayerAlreadyExists {
         name: String,
+        display_name: String,
     },
+    #[serde(rename_all = "camelCase")]
     PlayerDoesNotExist {
         name: String,
+        display_name: String,
     },
     GameNotStarted,
     GameAlreadyInProgress,
+    GameEnded,
     HandSizeCannotBeZero,
     JudgeCannotBeChosen,
+    InvalidDeck {
+        reason: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    CardNotInHand {
+        card_id: String,
+    },
+    AnswerDeckExhausted {
+        needed: usize,
+        available: usize,
+    },
+    InvalidConfig {
+        reason: String,
+    },
+    RedrawsNotAllowed,
+    #[serde(rename_all = "camelCase")]
+    SessionAlreadyExists {
+        session_id: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    SessionDoesNotExist {
+        session_id: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    NotSessionOwner {
+        session_id: String,
+        player_name: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    OwnerAlreadyHasLobby {
+        owner: String,
+        session_id: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    NotALobby {
+        session_id: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    NotAGame {
+        session_id: String,
+    },
 }
 
 impl Display for GameCoreError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let msg = match self {
-            Custom(msg) => {
-                format!("(custom) {}", msg)
-            }
-            QuestionBlanksAndNumAnswersMismatch {
-                num_blanks,
-                num_answers,
-            } => {
-                format!(
-                    "Mismatch in number of question blanks ({}) and number of answers ({}).",
-                    num_blanks, num_answers
-                )
-            }
-            PlayerChoosingCardOutOfHandBound {
-                chosen_ind,
-                hand_bound,
-            } => {
-                format!(
-                    "Player chose a card index ({}) >= hand size ({}).",
-                    chosen_ind, hand_bound
-                )
-            }
-            PlayerChoosingTheSameCardMultipleTimes { chosen_ind } => {
-                format!(
-                    "Player chose the same card index ({}) multiple times.",
-                    chosen_ind
-                )
-            }
-            PlayerAlreadyExists { name } => {
-                format!("A Player with the name {} already exists.", name)
-            }
-            InsufficientAnswerCardsToDeal {
-                num_players,
-                each_deal,
-                num_answer_cards,
-            } => {
-                format!(
-                    "Cannot deal {} cards to {} players when there are only {} cards in total.",
-                    each_deal, num_players, num_answer_cards
-                )
-            }
-            NotEnoughPlayers { num_players } => {
-                format!("There must be at least 3 players. (Now: {})", num_players)
-            }
-            NoQuestionCards => "There are no question cards.".to_owned(),
-            NoActiveQuestionCard => "There is no active question card.".to_owned(),
-            NoActiveJudge => "There is no active judge.".to_owned(),
-            PlayerDoesNotExist { name } => {
-                format!("Player with name {} does not exist.", name)
-            }
-            JudgeTryingToSubmitAnswers { judge_name } => {
-                format!("The Judge ({}) cannot submit answers.", judge_name)
-            }
-            PlayerAlreadySubmittedAnswers { player_name } => {
-                format!("Player {} already submitted answers.", player_name)
+        write!(f, "{}", self.localize(Locale::English))
+    }
+}
+
+/// The display name, followed by the player ID when they differ.
+fn describe_player(id: &str, display_name: &str) -> String {
+    if id == display_name {
+        id.to_owned()
+    } else {
+        format!("{} [{}]", display_name, id)
+    }
+}
+
+impl GameCoreError {
+    /// Fill in the display names of the players an error is about, whose IDs were used until now.
+    pub(crate) fn with_display_names(mut self, display_name_of: impl Fn(&str) -> String) -> Self {
+        match &mut self {
+            PlayerAlreadyExists { name, display_name }
+            | PlayerDoesNotExist { name, display_name }
+            | JudgeTryingToSubmitAnswers {
+                judge_name: name,
+                display_name,
             }
-            GameNotStarted => "The game is not started.".to_owned(),
-            GameAlreadyInProgress => "The game is already in progress.".to_owned(),
-            HandSizeCannotBeZero => "Hand size cannot be zero.".to_owned(),
-            JudgeCannotBeChosen => "The Judge cannot be chosen.".to_owned(),
+            | PlayerAlreadySubmittedAnswers {
+                player_name: name,
+                display_name,
+            } => *display_name = display_name_of(name),
+            _ => {}
+        }
+
+        self
+    }
+}
+
+impl std::error::Error for GameCoreError {}
+
+impl GameCoreError {
+    /// A stable, machine-readable name of the error, e.g. "playerAlreadyExists".
+    pub fn code(&self) -> String {
+        self.code_and_details().0
+    }
+
+    /// The fields of the error in camelCase, e.g. { "name": "A", "displayName": "Alex" }.
+    /// `Custom` errors have a single "reason".
+    pub fn details(&self) -> Map<String, Value> {
+        self.code_and_details().1
+    }
+
+    /// The catalogue key "error.`code`", with the details as arguments. Errors about a player
+    /// also have a "player" argument: the display name, followed by the ID when they differ.
+    pub fn message(&self) -> Message {
+        let (code, details) = self.code_and_details();
+        let mut message = Message {
+            key: format!("error.{}", code),
+            args: details,
         };
 
-        write!(f, "{}", msg)
+        if let PlayerAlreadyExists { name, display_name }
+        | PlayerDoesNotExist { name, display_name }
+        | JudgeTryingToSubmitAnswers {
+            judge_name: name,
+            display_name,
+        }
+        | PlayerAlreadySubmittedAnswers {
+            player_name: name,
+            display_name,
+        } = self
+        {
+            message = message.arg("player", describe_player(name, display_name));
+        }
+
+        message
+    }
+
+    pub fn localize(&self, locale: Locale) -> String {
+        self.message().localize(locale)
+    }
+
+    fn code_and_details(&self) -> (String, Map<String, Value>) {
+        match serde_json::to_value(self).unwrap() {
+            Value::String(code) => (code, Map::new()),
+            Value::Object(variant) => {
+                let (code, fields) = variant.into_iter().next().unwrap();
+
+                match fields {
+                    Value::Object(fields) => (code, fields),
+                    reason => (
+                        code,
+                        std::iter::once(("reason".to_owned(), reason)).collect(),
+                    ),
+                }
+            }
+            _ => unreachable!("GameCoreError serializes to a string or an object"),
+        }
     }
 }
 
@@ -130,3 +235,74 @@ impl From<&str> for GameCoreError {
         Self::Custom(msg.to_owned())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn codes_and_details() {
+        let error = PlayerAlreadyExists {
+            name: "1234".to_owned(),
+            display_name: "Alex".to_owned(),
+        };
+        assert_eq!(error.code(), "playerAlreadyExists");
+        assert_eq!(
+            Value::Object(error.details()),
+            json!({ "name": "1234", "displayName": "Alex" })
+        );
+
+        let error = QuestionBlanksAndNumAnswersMismatch {
+            num_blanks: 2,
+            num_answers: 1,
+        };
+        assert_eq!(error.code(), "questionBlanksAndNumAnswersMismatch");
+        assert_eq!(
+            Value::Object(error.details()),
+            json!({ "numBlanks": 2, "numAnswers": 1 })
+        );
+
+        assert_eq!(NoQuestionCards.code(), "noQuestionCards");
+        assert!(NoQuestionCards.details().is_empty());
+
+        let error = GameCoreError::from("Oops");
+        assert_eq!(error.code(), "custom");
+        assert_eq!(Value::Object(error.details()), json!({ "reason": "Oops" }));
+    }
+
+    #[test]
+    fn player_errors_show_the_display_name() {
+        let error = PlayerDoesNotExist {
+            name: "1234".to_owned(),
+            display_name: "Alex".to_owned(),
+        };
+        assert_eq!(
+            Value::Object(error.details()),
+            json!({ "name": "1234", "displayName": "Alex" })
+        );
+        assert_eq!(
+            error.to_string(),
+            "Player with name Alex [1234] does not exist."
+        );
+
+        let error = PlayerDoesNotExist {
+            name: "Alex".to_owned(),
+            display_name: "Alex".to_owned(),
+        };
+        assert_eq!(error.to_string(), "Player with name Alex does not exist.");
+    }
+
+    #[test]
+    fn usable_as_std_error() {
+        fn fails() -> Result<(), Box<dyn std::error::Error>> {
+            Err(HandSizeCannotBeZero)?
+        }
+
+        assert_eq!(
+            fails().unwrap_err().to_string(),
+            "Hand size cannot be zero."
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use serde::Serialize;
+
+/// Something that happened in a game, reported to the listeners subscribed to a driver.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GameEvent {
+    RoundStarted {
+        round: usize,
+        judge: String,
+        question: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    PlayerSubmitted {
+        round: usize,
+        player: String,
+        /// Number of players who have not submitted yet.
+        num_waiting: usize,
+    },
+    /// (player, combined answer), in the order shown to the Judge.
+    SubmissionsClosed {
+        round: usize,
+        submissions: Vec<(String, String)>,
+    },
+    #[serde(rename_all = "camelCase")]
+    JudgeChose {
+        round: usize,
+        judge: String,
+        chosen_player: String,
+        answer: Option<String>,
+    },
+    /// In descending order of Awesome Points.
+    ScoresChanged { ranking: Vec<(String, i32)> },
+    #[serde(rename_all = "camelCase")]
+    PlayerRenamed {
+        player: String,
+        display_name: String,
+    },
+    /// The Answer deck ran out under `ExhaustionPolicy::EndGame` or `Error`, or the game was
+    /// ended.
+    GameOver { ranking: Vec<(String, i32)> },
+}
+
+/// Returned by `subscribe()`, to `unsubscribe()` later.
+pub type ListenerId = usize;
+
+type Listener = Box<dyn FnMut(&GameEvent) + Send>;
+
+#[derive(Default)]
+pub(crate) struct EventListeners {
+    next_id: ListenerId,
+    listeners: Vec<(ListenerId, Listener)>,
+}
+
+impl EventListeners {
+    pub(crate) fn subscribe(
+        &mut self,
+        listener: impl FnMut(&GameEvent) + Send + 'static,
+    ) -> ListenerId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.listeners.push((id, Box::new(listener)));
+
+        id
+    }
+
+    /// Return false if no listener has the ID.
+    pub(crate) fn unsubscribe(&mut self, id: ListenerId) -> bool {
+        let num_listeners = self.listeners.len();
+        self.listeners.retain(|(listener_id, _)| *listener_id != id);
+
+        self.listeners.len() != num_listeners
+    }
+
+    /// Listeners are called in the order they subscribed.
+    pub(crate) fn emit(&mut self, event: GameEvent) {
+        for (_, listener) in self.listeners.iter_mut() {
+            listener(&event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[test]
+    fn subscribe_and_unsubscribe() {
+        let received = Arc::new(Mutex::new(vec![]));
+        let mut listeners = EventListeners::default();
+
+        let first = {
+            let received = received.clone();
+            listeners.subscribe(move |e| received.lock().unwrap().push((1, e.clone())))
+        };
+        {
+            let received = received.clone();
+            listeners.subscribe(move |e| received.lock().unwrap().push((2, e.clone())));
+        }
+
+        let event = GameEvent::ScoresChanged { ranking: vec![] };
+        listeners.emit(event.clone());
+        assert!(listeners.unsubscribe(first));
+        assert!(!listeners.unsubscribe(first));
+        listeners.emit(event.clone());
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![(1, event.clone()), (2, event.clone()), (2, event)]
+        );
+    }
+
+    #[test]
+    fn serialize_with_type_tag() {
+        let event = GameEvent::PlayerSubmitted {
+            round: 2,
+            player: "A".to_owned(),
+            num_waiting: 1,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "type": "playerSubmitted",
+                "round": 2,
+                "player": "A",
+                "numWaiting": 1,
+            })
+        );
+    }
+}
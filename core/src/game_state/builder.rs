@@ -1,13 +1,20 @@
-use std::{
-    collections::{HashMap, HashSet},
-    iter::FromIterator,
-};
+use std::collections::{HashMap, HashSet};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 use crate::{cards::CardStorage, error::GameCoreError, player::Player};
 
-use super::{GameState, PlayerName};
+use super::{
+    validation::validate_cards, DeckIssue, ExhaustionPolicy, GameState, JudgeStrategy, PlayerName,
+    ValidationReport,
+};
+
+/// Minimum number of players unless configured otherwise.
+pub const DEFAULT_MIN_PLAYERS: usize = 3;
+/// Maximum number of players unless configured otherwise.
+pub const DEFAULT_MAX_PLAYERS: usize = 20;
 
-#[derive(Default)]
 pub struct GameStateBuilder<PN = String>
 where
     PN: PlayerName,
@@ -15,6 +22,52 @@ where
     players: HashSet<PN>,
     questions: HashSet<String>,
     answers: HashSet<String>,
+
+    // Number of times each duplicated card was added, kept for `validate()`.
+    question_duplicates: HashMap<String, usize>,
+    answer_duplicates: HashMap<String, usize>,
+
+    exhaustion_policy: ExhaustionPolicy,
+    judge_strategy: JudgeStrategy,
+
+    min_players: usize,
+    max_players: usize,
+
+    seed: Option<u64>,
+}
+
+/// The outcome of `GameStateBuilder::check()`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct BuildCheck {
+    /// In the order `build()` would report them.
+    pub problems: Vec<GameCoreError>,
+    pub warnings: Vec<DeckIssue>,
+}
+
+impl<PN> Default for GameStateBuilder<PN>
+where
+    PN: PlayerName,
+{
+    fn default() -> Self {
+        Self {
+            players: Default::default(),
+            questions: Default::default(),
+            answers: Default::default(),
+            question_duplicates: Default::default(),
+            answer_duplicates: Default::default(),
+            exhaustion_policy: Default::default(),
+            judge_strategy: Default::default(),
+            min_players: DEFAULT_MIN_PLAYERS,
+            max_players: DEFAULT_MAX_PLAYERS,
+            seed: None,
+        }
+    }
+}
+
+impl BuildCheck {
+    pub fn is_ready(&self) -> bool {
+        self.problems.is_empty()
+    }
 }
 
 impl<PN> GameStateBuilder<PN>
@@ -25,6 +78,16 @@ where
         Default::default()
     }
 
+    /// With a seed, games built from the same players and cards shuffle, deal and pick Judges
+    /// the same way every time. Without one (the default), they are random.
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
     pub fn num_players(&self) -> usize {
         self.players.len()
     }
@@ -44,6 +107,12 @@ where
         if self.players.contains(&player_name) {
             Err(GameCoreError::PlayerAlreadyExists {
                 name: player_name.to_string(),
+                display_name: player_name.to_string(),
+            })
+        } else if self.players.len() >= self.max_players {
+            Err(GameCoreError::TooManyPlayers {
+                num_players: self.players.len(),
+                max_players: self.max_players,
             })
         } else {
             self.players.insert(player_name);
@@ -57,82 +126,190 @@ where
         if !self.players.remove(&player_name) {
             Err(GameCoreError::PlayerDoesNotExist {
                 name: player_name.to_string(),
+                display_name: player_name.to_string(),
             })
         } else {
             Ok(())
         }
     }
 
+    pub fn min_players(&self) -> usize {
+        self.min_players
+    }
+
+    pub fn max_players(&self) -> usize {
+        self.max_players
+    }
+
+    /// Players already added are kept even if there are now too many; `build()` reports it.
+    pub fn set_player_bounds(
+        &mut self,
+        min_players: usize,
+        max_players: usize,
+    ) -> Result<(), GameCoreError> {
+        if min_players < 2 || min_players > max_players {
+            return Err(GameCoreError::InvalidPlayerBounds {
+                min_players,
+                max_players,
+            });
+        }
+
+        self.min_players = min_players;
+        self.max_players = max_players;
+
+        Ok(())
+    }
+
+    pub fn set_exhaustion_policy(&mut self, exhaustion_policy: ExhaustionPolicy) {
+        self.exhaustion_policy = exhaustion_policy;
+    }
+
+    pub fn set_judge_strategy(&mut self, judge_strategy: JudgeStrategy) {
+        self.judge_strategy = judge_strategy;
+    }
+
     pub fn withdraw_all_players(&mut self) {
         self.players = HashSet::new();
     }
 
     pub fn remove_all_questions(&mut self) {
         self.questions = HashSet::new();
+        self.question_duplicates = HashMap::new();
     }
 
     pub fn remove_all_answers(&mut self) {
         self.answers = HashSet::new();
+        self.answer_duplicates = HashMap::new();
     }
 
+    /// A duplicated question is kept only once. `validate()` reports it.
     pub fn add_new_question(&mut self, question: impl Into<String>) {
-        self.questions.insert(question.into());
+        let question = question.into();
+
+        if self.questions.contains(&question) {
+            *self.question_duplicates.entry(question).or_insert(1) += 1;
+        } else {
+            self.questions.insert(question);
+        }
     }
 
     pub fn add_new_questions(&mut self, questions: impl IntoIterator<Item = impl Into<String>>) {
         questions.into_iter().for_each(|q| self.add_new_question(q));
     }
 
+    /// A duplicated answer is kept only once. `validate()` reports it.
     pub fn add_new_answer(&mut self, answer: impl Into<String>) {
-        self.answers.insert(answer.into());
+        let answer = answer.into();
+
+        if self.answers.contains(&answer) {
+            *self.answer_duplicates.entry(answer).or_insert(1) += 1;
+        } else {
+            self.answers.insert(answer);
+        }
     }
 
     pub fn add_new_answers(&mut self, answers: impl IntoIterator<Item = impl Into<String>>) {
         answers.into_iter().for_each(|a| self.add_new_answer(a));
     }
 
-    pub fn build(&self, hand_size: usize) -> Result<GameState<PN>, GameCoreError> {
-        let num_players = self.players.len();
-        if num_players < 3 {
-            return Err(GameCoreError::NotEnoughPlayers { num_players });
-        }
+    /// Report problems in the questions and answers that would be played with `hand_size`.
+    ///
+    /// Nothing is removed; the report only describes what `build()` would use.
+    pub fn validate(&self, hand_size: usize) -> ValidationReport {
+        validate_cards(
+            &self.questions,
+            &self.question_duplicates,
+            &self.answers,
+            &self.answer_duplicates,
+            hand_size,
+        )
+    }
 
-        if self.num_questions() == 0 {
-            return Err(GameCoreError::NoQuestionCards);
+    /// Report every problem that would stop `build()`, along with the deck issues found by
+    /// `validate()`. Nothing is built.
+    pub fn check(&self, hand_size: usize) -> BuildCheck {
+        BuildCheck {
+            problems: self.blocking_problems(hand_size),
+            warnings: self.validate(hand_size).issues,
         }
+    }
 
-        let num_answer_cards_in_storage = self.num_answer_cards_in_storage();
-        if num_players * hand_size > num_answer_cards_in_storage {
-            return Err(GameCoreError::InsufficientAnswerCardsToDeal {
-                num_players,
-                each_deal: hand_size,
-                num_answer_cards: num_answer_cards_in_storage,
-            });
+    pub fn build(&self, hand_size: usize) -> Result<GameState<PN>, GameCoreError> {
+        if let Some(problem) = self.blocking_problems(hand_size).into_iter().next() {
+            return Err(problem);
         }
 
-        let mut players = HashMap::from_iter(
-            self.players
-                .iter()
-                .map(|player_name| (player_name.clone(), Player::new())),
-        );
-        let mut question_card_storage = CardStorage::from_cards(self.questions.clone());
-        let mut answer_card_storage = CardStorage::from_cards(self.answers.clone());
+        let mut rng = match self.seed {
+            Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+            None => ChaCha8Rng::from_entropy(),
+        };
+
+        // Sorted before shuffling, since the order of a HashSet changes between runs.
+        let mut player_names: Vec<PN> = self.players.iter().cloned().collect();
+        player_names.sort_by_key(ToString::to_string);
+        let mut questions: Vec<String> = self.questions.iter().cloned().collect();
+        questions.sort();
+        let mut answers: Vec<String> = self.answers.iter().cloned().collect();
+        answers.sort();
+
+        let mut question_card_storage =
+            CardStorage::from_cards(questions, ChaCha8Rng::seed_from_u64(rng.gen()));
+        let mut answer_card_storage =
+            CardStorage::from_cards(answers, ChaCha8Rng::seed_from_u64(rng.gen()));
 
         answer_card_storage.shuffle_deck();
         question_card_storage.shuffle_deck();
 
-        for player in players.values_mut() {
+        let mut players = HashMap::new();
+        for player_name in player_names {
+            let mut player = Player::new();
             for _ in 0..hand_size {
                 player.add_card_to_hand(answer_card_storage.draw_card_from_deck().unwrap());
             }
+            players.insert(player_name, player);
         }
 
         Ok(GameState::new(
             players,
             question_card_storage,
             answer_card_storage,
+            self.exhaustion_policy,
+            self.judge_strategy,
+            rng,
         ))
     }
+
+    fn blocking_problems(&self, hand_size: usize) -> Vec<GameCoreError> {
+        let mut problems = vec![];
+
+        let num_players = self.players.len();
+        if num_players < self.min_players {
+            problems.push(GameCoreError::NotEnoughPlayers {
+                num_players,
+                min_players: self.min_players,
+            });
+        } else if num_players > self.max_players {
+            problems.push(GameCoreError::TooManyPlayers {
+                num_players,
+                max_players: self.max_players,
+            });
+        }
+
+        if self.num_questions() == 0 {
+            problems.push(GameCoreError::NoQuestionCards);
+        }
+
+        let num_answer_cards_in_storage = self.num_answer_cards_in_storage();
+        if num_players * hand_size > num_answer_cards_in_storage {
+            problems.push(GameCoreError::InsufficientAnswerCardsToDeal {
+                num_players,
+                each_deal: hand_size,
+                num_answer_cards: num_answer_cards_in_storage,
+            });
+        }
+
+        problems
+    }
 }
 
 #[cfg(test)]
@@ -176,7 +353,8 @@ mod tests {
         assert_eq!(
             game_state_builder.withdraw_player("B").err().unwrap(),
             GameCoreError::PlayerDoesNotExist {
-                name: "B".to_owned()
+                name: "B".to_owned(),
+                display_name: "B".to_owned(),
             }
         );
         assert_eq!(game_state_builder.num_players(), 1);
@@ -202,7 +380,8 @@ mod tests {
         assert_eq!(
             game_state_builder.add_new_player("A").err().unwrap(),
             GameCoreError::PlayerAlreadyExists {
-                name: "A".to_owned()
+                name: "A".to_owned(),
+                display_name: "A".to_owned()
             }
         );
     }
@@ -310,7 +489,10 @@ mod tests {
 
         assert_eq!(
             game_state_builder.build(10).err().unwrap(),
-            GameCoreError::NotEnoughPlayers { num_players: 2 }
+            GameCoreError::NotEnoughPlayers {
+                num_players: 2,
+                min_players: 3
+            }
         );
         assert_eq!(game_state_builder.num_answer_cards_in_storage(), 30);
     }
@@ -331,4 +513,107 @@ mod tests {
         );
         assert_eq!(game_state_builder.num_answer_cards_in_storage(), 30);
     }
+
+    #[test]
+    fn check_reports_all_problems() {
+        let mut game_state_builder: GameStateBuilder = GameStateBuilder::new();
+        game_state_builder.add_new_player("A").ok().unwrap();
+        game_state_builder.add_new_answer("");
+
+        let check = game_state_builder.check(10);
+
+        assert!(!check.is_ready());
+        assert_eq!(
+            check.problems,
+            vec![
+                GameCoreError::NotEnoughPlayers {
+                    num_players: 1,
+                    min_players: 3
+                },
+                GameCoreError::NoQuestionCards,
+                GameCoreError::InsufficientAnswerCardsToDeal {
+                    num_players: 1,
+                    each_deal: 10,
+                    num_answer_cards: 1
+                }
+            ]
+        );
+        assert_eq!(check.warnings.len(), 1);
+        assert_eq!(game_state_builder.num_players(), 1);
+    }
+
+    #[test]
+    fn check_ready() {
+        let mut game_state_builder: GameStateBuilder = GameStateBuilder::new();
+        game_state_builder.add_new_player("A").ok().unwrap();
+        game_state_builder.add_new_player("B").ok().unwrap();
+        game_state_builder.add_new_player("C").ok().unwrap();
+        for i in 0..30 {
+            game_state_builder.add_new_answer(i.to_string());
+        }
+        game_state_builder.add_new_question("Are you okay?");
+
+        assert_eq!(game_state_builder.check(10), BuildCheck::default());
+        assert!(game_state_builder.build(10).is_ok());
+    }
+
+    #[test]
+    fn add_player_beyond_max() {
+        let mut game_state_builder: GameStateBuilder = GameStateBuilder::new();
+        game_state_builder.set_player_bounds(2, 2).unwrap();
+        game_state_builder.add_new_player("A").ok().unwrap();
+        game_state_builder.add_new_player("B").ok().unwrap();
+
+        assert_eq!(
+            game_state_builder.add_new_player("C").err().unwrap(),
+            GameCoreError::TooManyPlayers {
+                num_players: 2,
+                max_players: 2
+            }
+        );
+        assert_eq!(game_state_builder.num_players(), 2);
+    }
+
+    #[test]
+    fn build_game_with_configured_bounds() {
+        let mut game_state_builder: GameStateBuilder = GameStateBuilder::new();
+        game_state_builder.add_new_player("A").ok().unwrap();
+        game_state_builder.add_new_player("B").ok().unwrap();
+        game_state_builder.add_new_player("C").ok().unwrap();
+        for i in 0..30 {
+            game_state_builder.add_new_answer(i.to_string());
+        }
+        game_state_builder.add_new_question("Are you okay?");
+
+        game_state_builder.set_player_bounds(4, 6).unwrap();
+        assert_eq!(
+            game_state_builder.build(10).err().unwrap(),
+            GameCoreError::NotEnoughPlayers {
+                num_players: 3,
+                min_players: 4
+            }
+        );
+
+        game_state_builder.set_player_bounds(2, 2).unwrap();
+        assert_eq!(
+            game_state_builder.build(10).err().unwrap(),
+            GameCoreError::TooManyPlayers {
+                num_players: 3,
+                max_players: 2
+            }
+        );
+
+        game_state_builder.set_player_bounds(2, 3).unwrap();
+        assert!(game_state_builder.build(10).is_ok());
+    }
+
+    #[test]
+    fn invalid_player_bounds() {
+        let mut game_state_builder: GameStateBuilder = GameStateBuilder::new();
+
+        assert!(game_state_builder.set_player_bounds(1, 5).is_err());
+        assert!(game_state_builder.set_player_bounds(5, 4).is_err());
+        assert_eq!(game_state_builder.min_players(), DEFAULT_MIN_PLAYERS);
+        assert_eq!(game_state_builder.max_players(), DEFAULT_MAX_PLAYERS);
+    }
 }
@@ -1,6 +1,12 @@
-use std::{collections::HashMap, fmt::Display, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::Hash,
+};
 
-use rand::{prelude::SliceRandom, thread_rng};
+use rand::{prelude::SliceRandom, Rng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     cards::{AnswerCard, CardStorage, QuestionCard},
@@ -10,9 +16,62 @@ use crate::{
 
 pub type AllSubmittedAnswers<PlayerName> = Option<Vec<(PlayerName, String)>>;
 
+/// Stable across games: the same card content always has the same ID.
+pub type CardId = String;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HandCard {
+    pub id: CardId,
+    pub content: String,
+}
+
+/// Numbers of cards left to draw and already played. Cards in hands and the current question
+/// are in neither.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeckSizes {
+    pub questions_in_deck: usize,
+    pub questions_discarded: usize,
+    pub answers_in_deck: usize,
+    pub answers_discarded: usize,
+}
+
 pub mod builder;
+pub mod validation;
 
 pub use builder::*;
+pub use validation::*;
+
+/// What happens when the Answer deck cannot refill every hand.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExhaustionPolicy {
+    /// Refuse a redraw, or the last submission of a round, that needs more cards than are left
+    /// with `GameCoreError::AnswerDeckExhausted`, without any side effects. Since that round can
+    /// never be completed, a refused submission also ends the game.
+    /// Played cards are never reshuffled into the deck.
+    Error,
+    /// Deal what is left; hands may stay short. Played cards are never reshuffled into the deck.
+    ShortHands,
+    /// Reshuffle the played cards into the deck as soon as it runs out, then deal short hands
+    /// if even that is not enough.
+    #[default]
+    Recycle,
+    /// Deal what is left and mark the game as over; played cards are never reshuffled.
+    EndGame,
+}
+
+/// How the Judge of the next round is chosen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JudgeStrategy {
+    /// Every player takes a turn, in the order of `ordered_players()`.
+    #[default]
+    RoundRobin,
+    /// Anyone but the current Judge, picked at random.
+    Random,
+}
 
 pub trait PlayerName: Clone + Default + Display + Eq + Hash + PartialEq {}
 impl PlayerName for String {}
@@ -30,11 +89,16 @@ where
     num_players: usize,
     max_hand_size: usize,
     ordered_players: Vec<PN>,
+    exhaustion_policy: ExhaustionPolicy,
+    judge_strategy: JudgeStrategy,
 
     // Variables
     current_judge: Option<usize>,
     current_question: Option<QuestionCard>,
     submitted_answers_display: HashMap<PN, String>,
+    submitted_card_ids: HashMap<PN, Vec<CardId>>,
+    is_over: bool,
+    rng: ChaCha8Rng,
 }
 
 impl<PN> GameState<PN>
@@ -51,10 +115,19 @@ where
             if !self.players.contains_key(player_name) {
                 return Err(GameCoreError::PlayerDoesNotExist {
                     name: player_name.to_string(),
+                    display_name: player_name.to_string(),
                 });
             }
         }
 
+        let num_discarded_cards = player_names
+            .iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(|player_name| self.players[player_name].hand_size())
+            .sum();
+        self.ensure_answer_cards_to_refill(num_discarded_cards)?;
+
         for player_name in player_names {
             let player = self.players.get_mut(player_name).unwrap();
             let indices = (0..player.hand_size()).collect::<Vec<_>>();
@@ -71,12 +144,25 @@ where
         Ok(())
     }
 
+    /// Only `ExhaustionPolicy::EndGame` and `ExhaustionPolicy::Error` end a game, when the
+    /// Answer deck runs out. Under `Error`, the submission that found out failed.
+    pub fn is_over(&self) -> bool {
+        self.is_over
+    }
+
     /// Change the Judge to the next player and return the player's name
     pub fn next_judge(&mut self) -> PN {
-        self.current_judge = if let Some(current_judge) = self.current_judge {
-            Some((current_judge + 1) % self.num_players)
-        } else {
-            Some(0)
+        self.current_judge = match (self.judge_strategy, self.current_judge) {
+            (JudgeStrategy::RoundRobin, Some(current_judge)) => {
+                Some((current_judge + 1) % self.num_players)
+            }
+            (JudgeStrategy::RoundRobin, None) => Some(0),
+            (JudgeStrategy::Random, Some(current_judge)) => {
+                // Skip over the current Judge.
+                let offset = self.rng.gen_range(1..self.num_players);
+                Some((current_judge + offset) % self.num_players)
+            }
+            (JudgeStrategy::Random, None) => Some(self.rng.gen_range(0..self.num_players)),
         };
 
         self.current_judge_name().unwrap()
@@ -86,6 +172,7 @@ where
         if let Some(current_card) = self.current_question.take() {
             self.question_card_storage.discard_card(current_card);
         }
+        self.submitted_card_ids.clear();
 
         self.current_question = Some(
             if let Some(next_card) = self.question_card_storage.draw_card_from_deck() {
@@ -110,6 +197,58 @@ where
             .collect()
     }
 
+    /// Same as `report_hands()`, with the ID of each card.
+    pub fn report_hand_cards(&self) -> HashMap<PN, Vec<HandCard>> {
+        self.players
+            .iter()
+            .map(|(player_name, player)| (player_name.clone(), player.report_hand_cards()))
+            .collect()
+    }
+
+    pub fn deck_sizes(&self) -> DeckSizes {
+        DeckSizes {
+            questions_in_deck: self.question_card_storage.num_cards_in_deck(),
+            questions_discarded: self.question_card_storage.num_cards_in_discard_pile(),
+            answers_in_deck: self.answer_card_storage.num_cards_in_deck(),
+            answers_discarded: self.answer_card_storage.num_cards_in_discard_pile(),
+        }
+    }
+
+    /// Only the hand of `player_name`, e.g. to show it privately.
+    pub fn hand_cards_of(&self, player_name: &PN) -> Option<Vec<HandCard>> {
+        self.players
+            .get(player_name)
+            .map(|player| player.report_hand_cards())
+    }
+
+    pub fn current_question_id(&self) -> Option<CardId> {
+        self.current_question.as_ref().map(|q| q.id.clone())
+    }
+
+    /// IDs of the cards each player submitted for the current question, in the order played.
+    pub fn report_submitted_card_ids(&self) -> HashMap<PN, Vec<CardId>> {
+        self.submitted_card_ids.clone()
+    }
+
+    /// Same as `submit_answers()`, but the cards are picked by their IDs instead of their
+    /// positions in the hand, so a stale view of the hand cannot play the wrong card.
+    pub fn submit_answers_by_ids(
+        &mut self,
+        player_name: &PN,
+        card_ids: &[CardId],
+    ) -> Result<AllSubmittedAnswers<PN>, GameCoreError> {
+        let indices = if let Some(player) = self.players.get(player_name) {
+            player.indices_of(card_ids)?
+        } else {
+            return Err(GameCoreError::PlayerDoesNotExist {
+                name: player_name.to_string(),
+                display_name: player_name.to_string(),
+            });
+        };
+
+        self.submit_answers(player_name, &indices)
+    }
+
     /// The returned all submitted answers are already shuffled.
     pub fn submit_answers(
         &mut self,
@@ -131,50 +270,80 @@ where
         if *player_name == judge {
             return Err(GameCoreError::JudgeTryingToSubmitAnswers {
                 judge_name: judge.to_string(),
+                display_name: judge.to_string(),
             });
         }
 
         if self.submitted_answers_display.contains_key(player_name) {
             return Err(GameCoreError::PlayerAlreadySubmittedAnswers {
                 player_name: player_name.to_string(),
+                display_name: player_name.to_string(),
             });
         }
 
-        if let Some(player) = self.players.get_mut(player_name) {
-            let played_cards_content = player.play_cards_content(indices)?;
+        let played_cards_content = if let Some(player) = self.players.get(player_name) {
+            player.play_cards_content(indices)?
+        } else {
+            return Err(GameCoreError::PlayerDoesNotExist {
+                name: player_name.to_string(),
+                display_name: player_name.to_string(),
+            });
+        };
 
-            let combined_answer = question.combine_with_answers(&played_cards_content)?;
-            self.submitted_answers_display
-                .insert(player_name.clone(), combined_answer);
+        let combined_answer = question.combine_with_answers(&played_cards_content)?;
 
-            let played_cards = player.remove_cards(indices)?;
+        let is_last = self.submitted_answers_display.len() + 2 == self.num_players;
+        if is_last {
+            if let Err(err) = self.ensure_answer_cards_to_refill(indices.len()) {
+                self.is_over = true;
+                return Err(err);
+            }
+        }
 
-            played_cards
-                .into_iter()
-                .for_each(|c| self.answer_card_storage.discard_card(c));
+        self.submitted_answers_display
+            .insert(player_name.clone(), combined_answer);
 
-            if self.submitted_answers_display.len() == self.num_players - 1 {
-                self.refill_player_hands();
+        let player = self.players.get_mut(player_name).unwrap();
+        let played_cards = player.remove_cards(indices)?;
+        self.submitted_card_ids.insert(
+            player_name.clone(),
+            played_cards.iter().map(|c| c.id.clone()).collect(),
+        );
 
-                let mut submitted_answers: Vec<(PN, String)> =
-                    std::mem::take(&mut self.submitted_answers_display)
-                        .into_iter()
-                        .collect();
+        played_cards
+            .into_iter()
+            .for_each(|c| self.answer_card_storage.discard_card(c));
 
-                submitted_answers.shuffle(&mut rand::thread_rng());
+        if self.submitted_answers_display.len() == self.num_players - 1 {
+            self.refill_player_hands();
 
-                return Ok(Some(submitted_answers));
-            }
-        } else {
-            return Err(GameCoreError::PlayerDoesNotExist {
-                name: player_name.to_string(),
-            });
+            let mut submitted_answers: Vec<(PN, String)> =
+                std::mem::take(&mut self.submitted_answers_display)
+                    .into_iter()
+                    .collect();
+
+            // Sorted first, so the same seed always gives the same order.
+            submitted_answers.sort_by_key(|(player_name, _)| player_name.to_string());
+            submitted_answers.shuffle(&mut self.rng);
+
+            return Ok(Some(submitted_answers));
         }
 
         Ok(None)
     }
 
     pub fn increment_awesome_points(&mut self, player_name: &PN) -> Result<i32, GameCoreError> {
+        self.check_chosen_player(player_name)?;
+
+        Ok(self
+            .players
+            .get_mut(player_name)
+            .unwrap()
+            .increment_awesome_points())
+    }
+
+    /// Return Err() if the Judge cannot choose `player_name` in the current round.
+    pub fn check_chosen_player(&self, player_name: &PN) -> Result<(), GameCoreError> {
         let judge = if let Some(judge) = self.current_judge_name() {
             judge
         } else {
@@ -185,21 +354,44 @@ where
             return Err(GameCoreError::JudgeCannotBeChosen);
         }
 
+        if !self.players.contains_key(player_name) {
+            return Err(GameCoreError::PlayerDoesNotExist {
+                name: player_name.to_string(),
+                display_name: player_name.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Give any player, the Judge included, `points` Awesome Points; `points` may be negative.
+    pub fn add_awesome_points(
+        &mut self,
+        player_name: &PN,
+        points: i32,
+    ) -> Result<i32, GameCoreError> {
         if let Some(player) = self.players.get_mut(player_name) {
-            Ok(player.increment_awesome_points())
+            Ok(player.add_awesome_points(points))
         } else {
             Err(GameCoreError::PlayerDoesNotExist {
                 name: player_name.to_string(),
+                display_name: player_name.to_string(),
             })
         }
     }
 
     /// In descending order of awesome points.
+    /// Ties are in the order of `ordered_players()`.
     pub fn report_awesome_point_ranking(&self) -> Vec<(PN, i32)> {
         let mut ranking: Vec<(PN, i32)> = self
-            .players
+            .ordered_players
             .iter()
-            .map(|(player_name, player)| (player_name.clone(), player.awesome_points()))
+            .map(|player_name| {
+                (
+                    player_name.clone(),
+                    self.players[player_name].awesome_points(),
+                )
+            })
             .collect();
 
         ranking.sort_by_key(|(_, ap)| -(*ap));
@@ -210,15 +402,19 @@ where
     /// Requirements:
     /// - The player names (keys of `players`) must not change throughout the game.
     /// - `players` are expected to have a filled hand already, each having the same hand size.
-    /// - `players` has at least 3 entries.
+    /// - `players` has at least 2 entries.
     /// - `question_card_storage` must not be empty.
     pub(super) fn new(
         players: HashMap<PN, Player>,
         question_card_storage: CardStorage<QuestionCard>,
         answer_card_storage: CardStorage<AnswerCard>,
+        exhaustion_policy: ExhaustionPolicy,
+        judge_strategy: JudgeStrategy,
+        mut rng: ChaCha8Rng,
     ) -> Self {
-        let mut ordered_players: Vec<PN> = players.keys().into_iter().cloned().collect();
-        ordered_players.shuffle(&mut thread_rng());
+        let mut ordered_players: Vec<PN> = players.keys().cloned().collect();
+        ordered_players.sort_by_key(ToString::to_string);
+        ordered_players.shuffle(&mut rng);
 
         let max_hand_size = players.values().next().unwrap().hand_size();
 
@@ -230,10 +426,15 @@ where
             num_players: ordered_players.len(),
             max_hand_size,
             ordered_players,
+            exhaustion_policy,
+            judge_strategy,
 
             current_judge: None,
             current_question: None,
             submitted_answers_display: HashMap::new(),
+            submitted_card_ids: HashMap::new(),
+            is_over: false,
+            rng,
         }
     }
 
@@ -242,10 +443,50 @@ where
             .map(|current_judge| self.ordered_players[current_judge].clone())
     }
 
+    /// Under `ExhaustionPolicy::Error`, fail if the deck cannot refill every hand after
+    /// `num_cards_to_remove` more cards leave the hands.
+    fn ensure_answer_cards_to_refill(
+        &self,
+        num_cards_to_remove: usize,
+    ) -> Result<(), GameCoreError> {
+        if self.exhaustion_policy != ExhaustionPolicy::Error {
+            return Ok(());
+        }
+
+        let needed = self
+            .players
+            .values()
+            .map(|player| self.max_hand_size - player.hand_size())
+            .sum::<usize>()
+            + num_cards_to_remove;
+        let available = self.answer_card_storage.num_cards_in_deck();
+
+        if needed > available {
+            Err(GameCoreError::AnswerDeckExhausted { needed, available })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// In the order of `ordered_players`, so the same seed always deals the same cards.
     fn refill_player_hands(&mut self) {
-        for player in self.players.values_mut() {
+        for player_name in self.ordered_players.iter() {
+            let player = self.players.get_mut(player_name).unwrap();
             while player.hand_size() < self.max_hand_size {
-                player.add_card_to_hand(self.answer_card_storage.draw_card_from_deck().unwrap());
+                let card = if self.exhaustion_policy == ExhaustionPolicy::Recycle {
+                    self.answer_card_storage.draw_card_from_deck()
+                } else {
+                    self.answer_card_storage.draw_card_without_recycling()
+                };
+
+                if let Some(card) = card {
+                    player.add_card_to_hand(card);
+                } else {
+                    if self.exhaustion_policy == ExhaustionPolicy::EndGame {
+                        self.is_over = true;
+                    }
+                    break;
+                }
             }
         }
     }
@@ -290,6 +531,26 @@ mod tests {
         assert_ne!(game_state.current_judge_name(), current_judge_name);
     }
 
+    #[test]
+    fn random_judge() {
+        let mut game_state_builder: GameStateBuilder = GameStateBuilder::new();
+        for player in ["A", "B", "C", "D"] {
+            game_state_builder.add_new_player(player).unwrap();
+        }
+        game_state_builder.add_new_question("Who?");
+        game_state_builder.add_new_answers((0..40).map(|i| i.to_string()));
+        game_state_builder.set_judge_strategy(JudgeStrategy::Random);
+        let mut game_state = game_state_builder.build(10).unwrap();
+
+        let mut last_judge = game_state.next_judge();
+        for _ in 0..20 {
+            let judge = game_state.next_judge();
+
+            assert_ne!(judge, last_judge);
+            last_judge = judge;
+        }
+    }
+
     #[test]
     fn does_not_have_a_question_at_start() {
         let game_state = get_built_game_state();
@@ -307,6 +568,27 @@ mod tests {
         assert_ne!(game_state.draw_next_question_card(), current_question);
     }
 
+    #[test]
+    fn deck_sizes() {
+        let mut game_state = get_built_game_state();
+
+        assert_eq!(
+            game_state.deck_sizes(),
+            DeckSizes {
+                questions_in_deck: 10,
+                questions_discarded: 0,
+                answers_in_deck: 0,
+                answers_discarded: 0,
+            }
+        );
+
+        game_state.draw_next_question_card();
+        game_state.draw_next_question_card();
+
+        assert_eq!(game_state.deck_sizes().questions_in_deck, 8);
+        assert_eq!(game_state.deck_sizes().questions_discarded, 1);
+    }
+
     #[test]
     fn auto_refill_question_cards() {
         let mut game_state = get_built_game_state();
@@ -355,7 +637,8 @@ mod tests {
         assert_eq!(
             result,
             GameCoreError::PlayerDoesNotExist {
-                name: "D".to_owned()
+                name: "D".to_owned(),
+                display_name: "D".to_owned(),
             }
         );
     }
@@ -423,7 +706,8 @@ mod tests {
         assert_eq!(
             game_state.submit_answers(&answer_submitter.to_owned(), &[7]),
             Err(GameCoreError::PlayerDoesNotExist {
-                name: answer_submitter.to_owned()
+                name: answer_submitter.to_owned(),
+                display_name: answer_submitter.to_owned(),
             })
         );
     }
@@ -438,6 +722,7 @@ mod tests {
             game_state.submit_answers(&game_state.current_judge_name().unwrap(), &[7]),
             Err(GameCoreError::JudgeTryingToSubmitAnswers {
                 judge_name: game_state.current_judge_name().unwrap(),
+                display_name: game_state.current_judge_name().unwrap(),
             })
         );
     }
@@ -457,7 +742,8 @@ mod tests {
         assert_eq!(
             game_state.submit_answers(&answer_submitter.to_owned(), &[7]),
             Err(GameCoreError::PlayerAlreadySubmittedAnswers {
-                player_name: answer_submitter.to_owned()
+                player_name: answer_submitter.to_owned(),
+                display_name: answer_submitter.to_owned()
             })
         );
     }
@@ -560,7 +846,8 @@ mod tests {
         assert_eq!(
             game_state.increment_awesome_points(&"D".to_owned()),
             Err(GameCoreError::PlayerDoesNotExist {
-                name: "D".to_owned()
+                name: "D".to_owned(),
+                display_name: "D".to_owned(),
             })
         )
     }
@@ -594,4 +881,190 @@ mod tests {
         assert_eq!(awesome_point_ranking[1].1, 0);
         assert_eq!(awesome_point_ranking[2].1, 0);
     }
+
+    #[test]
+    fn report_hand_cards() {
+        let game_state = get_built_game_state();
+
+        let hands = game_state.report_hands();
+        let hand_cards = game_state.report_hand_cards();
+
+        for (player_name, hand) in hands {
+            let contents = hand_cards[&player_name]
+                .iter()
+                .map(|card| card.content.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(contents, hand);
+        }
+    }
+
+    #[test]
+    fn submit_answers_by_ids() {
+        let mut game_state = get_built_game_state();
+        game_state.next_judge();
+        game_state.draw_next_question_card();
+        let current_judge_name = game_state.current_judge_name().unwrap();
+        let answer_submitter = if current_judge_name != "A" { "A" } else { "B" }.to_owned();
+        let chosen_card = game_state.report_hand_cards()[&answer_submitter][3].clone();
+
+        assert_eq!(
+            game_state
+                .submit_answers_by_ids(&answer_submitter, std::slice::from_ref(&chosen_card.id)),
+            Ok(None)
+        );
+        assert!(!game_state.report_hands()[&answer_submitter].contains(&chosen_card.content));
+        assert_eq!(
+            game_state.report_submitted_card_ids()[&answer_submitter],
+            vec![chosen_card.id]
+        );
+    }
+
+    #[test]
+    fn submit_answers_by_stale_ids() {
+        let mut game_state = get_built_game_state();
+        game_state.next_judge();
+        game_state.draw_next_question_card();
+        let current_judge_name = game_state.current_judge_name().unwrap();
+        let answer_submitter = if current_judge_name != "A" { "A" } else { "B" }.to_owned();
+        let stale_card_id = crate::cards::AnswerCard::new("not dealt").id;
+
+        assert_eq!(
+            game_state
+                .submit_answers_by_ids(&answer_submitter, std::slice::from_ref(&stale_card_id)),
+            Err(GameCoreError::CardNotInHand {
+                card_id: stale_card_id
+            })
+        );
+        assert!(game_state.report_submitted_card_ids().is_empty());
+    }
+
+    #[test]
+    fn submitted_card_ids_reset_on_next_question() {
+        let mut game_state = get_built_game_state();
+        game_state.next_judge();
+        game_state.draw_next_question_card();
+        let current_judge_name = game_state.current_judge_name().unwrap();
+        let answer_submitter = if current_judge_name != "A" { "A" } else { "B" };
+        game_state
+            .submit_answers(&answer_submitter.to_owned(), &[0])
+            .unwrap();
+
+        game_state.draw_next_question_card();
+
+        assert!(game_state.report_submitted_card_ids().is_empty());
+        assert!(game_state.current_question_id().unwrap().starts_with("q-"));
+    }
+
+    /// 3 players with 2 cards each, and only 1 Answer card left in the deck.
+    fn get_tiny_game_state(exhaustion_policy: ExhaustionPolicy) -> GameState {
+        let mut game_state_builder: GameStateBuilder = GameStateBuilder::new();
+        game_state_builder.add_new_player("A").ok().unwrap();
+        game_state_builder.add_new_player("B").ok().unwrap();
+        game_state_builder.add_new_player("C").ok().unwrap();
+        for i in 1..=7 {
+            game_state_builder.add_new_answer("A".to_owned() + &i.to_string());
+        }
+        game_state_builder.add_new_question("Q?");
+        game_state_builder.set_exhaustion_policy(exhaustion_policy);
+
+        game_state_builder.build(2).ok().unwrap()
+    }
+
+    /// Every non-judge player plays their first card. Return the result of the last submission.
+    fn play_a_round(
+        game_state: &mut GameState,
+    ) -> Result<AllSubmittedAnswers<String>, GameCoreError> {
+        let judge = game_state.next_judge();
+        game_state.draw_next_question_card();
+        let answer_submitters = ["A", "B", "C"]
+            .iter()
+            .map(|&name| name.to_owned())
+            .filter(|name| *name != judge)
+            .collect::<Vec<_>>();
+
+        game_state.submit_answers(&answer_submitters[0], &[0])?;
+        game_state.submit_answers(&answer_submitters[1], &[0])
+    }
+
+    fn num_cards_in_hands(game_state: &GameState) -> usize {
+        game_state.report_hands().values().map(Vec::len).sum()
+    }
+
+    #[test]
+    fn exhaustion_policy_error_on_the_last_submission() {
+        let mut game_state = get_tiny_game_state(ExhaustionPolicy::Error);
+
+        assert_eq!(
+            play_a_round(&mut game_state),
+            Err(GameCoreError::AnswerDeckExhausted {
+                needed: 2,
+                available: 1
+            })
+        );
+
+        assert!(game_state.is_over());
+        assert_eq!(num_cards_in_hands(&game_state), 5);
+    }
+
+    #[test]
+    fn exhaustion_policy_error_on_redraw() {
+        let mut game_state = get_tiny_game_state(ExhaustionPolicy::Error);
+
+        assert_eq!(
+            game_state.redraw_hands(&["A".to_owned()]),
+            Err(GameCoreError::AnswerDeckExhausted {
+                needed: 2,
+                available: 1
+            })
+        );
+        assert_eq!(num_cards_in_hands(&game_state), 6);
+    }
+
+    #[test]
+    fn exhaustion_policy_short_hands() {
+        let mut game_state = get_tiny_game_state(ExhaustionPolicy::ShortHands);
+
+        assert!(play_a_round(&mut game_state).unwrap().is_some());
+        assert_eq!(num_cards_in_hands(&game_state), 5);
+
+        assert!(play_a_round(&mut game_state).unwrap().is_some());
+        assert_eq!(num_cards_in_hands(&game_state), 3);
+        assert!(!game_state.is_over());
+    }
+
+    #[test]
+    fn exhaustion_policy_recycle() {
+        let mut game_state = get_tiny_game_state(ExhaustionPolicy::Recycle);
+
+        for _ in 0..10 {
+            assert!(play_a_round(&mut game_state).unwrap().is_some());
+            assert_eq!(num_cards_in_hands(&game_state), 6);
+        }
+    }
+
+    #[test]
+    fn exhaustion_policy_end_game() {
+        let mut game_state = get_tiny_game_state(ExhaustionPolicy::EndGame);
+
+        assert!(play_a_round(&mut game_state).unwrap().is_some());
+
+        assert!(game_state.is_over());
+        assert_eq!(num_cards_in_hands(&game_state), 5);
+    }
+
+    #[test]
+    fn exhaustion_until_hands_are_empty() {
+        let mut game_state = get_tiny_game_state(ExhaustionPolicy::ShortHands);
+
+        while num_cards_in_hands(&game_state) > 0 {
+            if play_a_round(&mut game_state).is_err() {
+                break;
+            }
+        }
+
+        assert!(
+            play_a_round(&mut game_state).is_err(),
+            "A player with an empty hand cannot answer, but nothing should panic."
+        );
+    }
 }
@@ -0,0 +1,441 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Display,
+};
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::cards::QuestionCard;
+
+/// Questions longer than this (in characters) are reported as unusually long.
+pub const QUESTION_LENGTH_LIMIT: usize = 200;
+/// Answers longer than this (in characters) are reported as unusually long.
+pub const ANSWER_LENGTH_LIMIT: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    Info,
+    Warning,
+    /// The card cannot be played as intended.
+    Error,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CardKind {
+    Question,
+    Answer,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum DeckProblem {
+    /// The card was added `times` times but is kept only once.
+    ExactDuplicate {
+        times: usize,
+    },
+    /// The card differs from `of` only in case, spacing or punctuation.
+    NearDuplicate {
+        of: String,
+    },
+    Empty,
+    #[serde(rename_all = "camelCase")]
+    TooManyBlanks {
+        num_blanks: usize,
+        hand_size: usize,
+    },
+    TooLong {
+        length: usize,
+        limit: usize,
+    },
+    /// Underscores that are probably not meant as blanks, e.g. in an Answer card or inside a word.
+    SuspiciousUnderscore,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeckIssue {
+    pub severity: Severity,
+    pub card_kind: CardKind,
+    pub card: String,
+    pub problem: DeckProblem,
+}
+
+/// Issues are sorted by severity (most severe first), then by card.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    pub issues: Vec<DeckIssue>,
+}
+
+impl DeckProblem {
+    pub fn severity(&self) -> Severity {
+        match self {
+            DeckProblem::Empty | DeckProblem::TooManyBlanks { .. } => Severity::Error,
+            DeckProblem::ExactDuplicate { .. }
+            | DeckProblem::NearDuplicate { .. }
+            | DeckProblem::SuspiciousUnderscore => Severity::Warning,
+            DeckProblem::TooLong { .. } => Severity::Info,
+        }
+    }
+}
+
+impl ValidationReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.count(Severity::Error) > 0
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+
+        write!(f, "{}", severity)
+    }
+}
+
+impl Display for DeckIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let card_kind = match self.card_kind {
+            CardKind::Question => "Question",
+            CardKind::Answer => "Answer",
+        };
+
+        let problem = match &self.problem {
+            DeckProblem::ExactDuplicate { times } => format!("added {} times", times),
+            DeckProblem::NearDuplicate { of } => format!("nearly the same as \"{}\"", of),
+            DeckProblem::Empty => "empty".to_owned(),
+            DeckProblem::TooManyBlanks {
+                num_blanks,
+                hand_size,
+            } => format!(
+                "has {} blanks but a hand only holds {} cards",
+                num_blanks, hand_size
+            ),
+            DeckProblem::TooLong { length, limit } => {
+                format!("{} characters long (limit: {})", length, limit)
+            }
+            DeckProblem::SuspiciousUnderscore => "has underscores that are not blanks".to_owned(),
+        };
+
+        write!(
+            f,
+            "[{}] {} \"{}\": {}",
+            self.severity, card_kind, self.card, problem
+        )
+    }
+}
+
+/// Check the given cards and produce a report.
+///
+/// `question_duplicates` and `answer_duplicates` map a card to the number of times it was added.
+pub(super) fn validate_cards(
+    questions: &HashSet<String>,
+    question_duplicates: &HashMap<String, usize>,
+    answers: &HashSet<String>,
+    answer_duplicates: &HashMap<String, usize>,
+    hand_size: usize,
+) -> ValidationReport {
+    let mut issues = vec![];
+    let mut report = |card_kind: CardKind, card: &str, problem: DeckProblem| {
+        issues.push(DeckIssue {
+            severity: problem.severity(),
+            card_kind,
+            card: card.to_owned(),
+            problem,
+        })
+    };
+
+    let underscore_in_word = Regex::new("[[:alnum:]]_|_[[:alnum:]]").unwrap();
+
+    for (card_kind, cards, duplicates) in [
+        (CardKind::Question, questions, question_duplicates),
+        (CardKind::Answer, answers, answer_duplicates),
+    ] {
+        for (card, &times) in duplicates.iter() {
+            report(card_kind, card, DeckProblem::ExactDuplicate { times });
+        }
+
+        for (card, of) in near_duplicates(cards) {
+            report(
+                card_kind,
+                card,
+                DeckProblem::NearDuplicate { of: of.clone() },
+            );
+        }
+
+        let limit = match card_kind {
+            CardKind::Question => QUESTION_LENGTH_LIMIT,
+            CardKind::Answer => ANSWER_LENGTH_LIMIT,
+        };
+
+        for card in cards.iter() {
+            if card.trim().is_empty() {
+                report(card_kind, card, DeckProblem::Empty);
+                continue;
+            }
+
+            let length = card.chars().count();
+            if length > limit {
+                report(card_kind, card, DeckProblem::TooLong { length, limit });
+            }
+
+            let suspicious_underscore = match card_kind {
+                CardKind::Question => underscore_in_word.is_match(card),
+                CardKind::Answer => card.contains('_'),
+            };
+            if suspicious_underscore {
+                report(card_kind, card, DeckProblem::SuspiciousUnderscore);
+            }
+
+            if let CardKind::Question = card_kind {
+                let num_blanks = QuestionCard::new(card.clone()).num_blanks();
+                if num_blanks > hand_size {
+                    report(
+                        card_kind,
+                        card,
+                        DeckProblem::TooManyBlanks {
+                            num_blanks,
+                            hand_size,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    issues.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then(a.card_kind.cmp(&b.card_kind))
+            .then(a.card.cmp(&b.card))
+    });
+
+    ValidationReport { issues }
+}
+
+/// Pairs of (card, the card it nearly duplicates).
+fn near_duplicates(cards: &HashSet<String>) -> Vec<(&String, &String)> {
+    let mut groups: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+    for card in cards.iter().filter(|c| !c.trim().is_empty()) {
+        // Cards of only punctuation or emoji normalize to nothing, so they are compared as written.
+        let key = match normalize(card) {
+            normalized if normalized.is_empty() => card.trim().to_owned(),
+            normalized => normalized,
+        };
+        groups.entry(key).or_default().push(card);
+    }
+
+    groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .flat_map(|mut group| {
+            group.sort();
+            let first = group[0];
+            group
+                .into_iter()
+                .skip(1)
+                .map(move |card| (card, first))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Lowercase letters and digits, with every run of underscores counted as one blank.
+fn normalize(card: &str) -> String {
+    let mut normalized = String::new();
+
+    for c in card.chars() {
+        if c == '_' {
+            if !normalized.ends_with('_') {
+                normalized.push('_');
+            }
+        } else if c.is_alphanumeric() {
+            normalized.extend(c.to_lowercase());
+        }
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_state::GameStateBuilder;
+
+    use super::*;
+
+    fn problems(report: &ValidationReport) -> Vec<(&str, &DeckProblem)> {
+        report
+            .issues
+            .iter()
+            .map(|issue| (issue.card.as_str(), &issue.problem))
+            .collect()
+    }
+
+    #[test]
+    fn clean_deck() {
+        let mut builder: GameStateBuilder = GameStateBuilder::new();
+        builder.add_new_questions(["Who am I?", "I only believe in __."]);
+        builder.add_new_answers(["Zombies", "A tennis ball"]);
+
+        assert!(builder.validate(10).is_clean());
+    }
+
+    #[test]
+    fn exact_duplicates() {
+        let mut builder: GameStateBuilder = GameStateBuilder::new();
+        builder.add_new_questions(["Who am I?", "Who am I?", "Who am I?"]);
+        builder.add_new_answers(["Zombies", "Zombies"]);
+
+        let report = builder.validate(10);
+
+        assert_eq!(
+            problems(&report),
+            vec![
+                ("Who am I?", &DeckProblem::ExactDuplicate { times: 3 }),
+                ("Zombies", &DeckProblem::ExactDuplicate { times: 2 }),
+            ]
+        );
+        assert_eq!(report.count(Severity::Warning), 2);
+    }
+
+    #[test]
+    fn near_duplicates() {
+        let mut builder: GameStateBuilder = GameStateBuilder::new();
+        builder.add_new_answers(["Zombies!", "zombies", "Tennis ball", "?", "!", "🎉", "🎈"]);
+
+        let report = builder.validate(10);
+
+        assert_eq!(
+            problems(&report),
+            vec![(
+                "zombies",
+                &DeckProblem::NearDuplicate {
+                    of: "Zombies!".to_owned()
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn empty_cards() {
+        let mut builder: GameStateBuilder = GameStateBuilder::new();
+        builder.add_new_question("");
+        builder.add_new_answers(["   ", "\t"]);
+
+        let report = builder.validate(10);
+
+        assert_eq!(report.count(Severity::Error), 3);
+        assert!(report
+            .issues
+            .iter()
+            .all(|issue| issue.problem == DeckProblem::Empty));
+    }
+
+    #[test]
+    fn too_many_blanks() {
+        let mut builder: GameStateBuilder = GameStateBuilder::new();
+        builder.add_new_questions(["_,_,_.", "_ and _."]);
+
+        let report = builder.validate(2);
+
+        assert_eq!(
+            problems(&report),
+            vec![(
+                "_,_,_.",
+                &DeckProblem::TooManyBlanks {
+                    num_blanks: 3,
+                    hand_size: 2
+                }
+            )]
+        );
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn too_long() {
+        let mut builder: GameStateBuilder = GameStateBuilder::new();
+        let long_answer = "a".repeat(ANSWER_LENGTH_LIMIT + 1);
+        builder.add_new_answer(long_answer.clone());
+
+        let report = builder.validate(10);
+
+        assert_eq!(
+            problems(&report),
+            vec![(
+                long_answer.as_str(),
+                &DeckProblem::TooLong {
+                    length: ANSWER_LENGTH_LIMIT + 1,
+                    limit: ANSWER_LENGTH_LIMIT
+                }
+            )]
+        );
+        assert_eq!(report.issues[0].severity, Severity::Info);
+    }
+
+    #[test]
+    fn suspicious_underscores() {
+        let mut builder: GameStateBuilder = GameStateBuilder::new();
+        builder.add_new_questions(["Call my_function with _.", "I like __, and _."]);
+        builder.add_new_answer("snake_case");
+
+        let report = builder.validate(10);
+
+        assert_eq!(
+            problems(&report),
+            vec![
+                (
+                    "Call my_function with _.",
+                    &DeckProblem::SuspiciousUnderscore
+                ),
+                ("snake_case", &DeckProblem::SuspiciousUnderscore),
+            ]
+        );
+    }
+
+    #[test]
+    fn sorted_by_severity() {
+        let mut builder: GameStateBuilder = GameStateBuilder::new();
+        builder.add_new_answers(["a_b", ""]);
+
+        let report = builder.validate(10);
+
+        assert_eq!(report.issues[0].severity, Severity::Error);
+        assert_eq!(report.issues[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn display_issue() {
+        let issue = DeckIssue {
+            severity: Severity::Error,
+            card_kind: CardKind::Question,
+            card: "_,_,_.".to_owned(),
+            problem: DeckProblem::TooManyBlanks {
+                num_blanks: 3,
+                hand_size: 2,
+            },
+        };
+
+        assert_eq!(
+            issue.to_string(),
+            "[error] Question \"_,_,_.\": has 3 blanks but a hand only holds 2 cards"
+        );
+    }
+}
@@ -0,0 +1,553 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Languages with a message catalogue. Messages missing from a catalogue fall back to English.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+    Cantonese,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::English, Locale::Spanish, Locale::Cantonese];
+
+    /// e.g. "en", "es", "yue"
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+            Locale::Cantonese => "yue",
+        }
+    }
+
+    /// Accept a language code with an optional region or encoding, ignoring case,
+    /// e.g. "es", "es-ES", "en_GB.UTF-8". "zh-HK" and "zh-MO" are read as Cantonese.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.trim().to_lowercase().replace('_', "-");
+        let code = code.split('.').next().unwrap_or_default();
+
+        match code {
+            "zh-hk" | "zh-mo" => return Some(Locale::Cantonese),
+            _ => {}
+        }
+
+        match code.split('-').next().unwrap_or_default() {
+            "en" => Some(Locale::English),
+            "es" => Some(Locale::Spanish),
+            "yue" => Some(Locale::Cantonese),
+            _ => None,
+        }
+    }
+}
+
+/// A catalogue key with the arguments to fill in, for frontends that translate messages
+/// themselves. Serialized as { key: string, args: object }.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Message {
+    pub key: String,
+    pub args: Map<String, Value>,
+}
+
+impl Message {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            args: Map::new(),
+        }
+    }
+
+    pub fn arg(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.args.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn localize(&self, locale: Locale) -> String {
+        translate(locale, &self.key, &self.args)
+    }
+}
+
+/// Look up `key` in the catalogue of `locale`, then of English, and replace every `{name}` in it
+/// with `args[name]`, in one pass so values are never substituted into. Unknown keys are returned
+/// as they are.
+pub fn translate(locale: Locale, key: &str, args: &Map<String, Value>) -> String {
+    let template = catalogue(locale, key)
+        .or_else(|| catalogue(Locale::English, key))
+        .unwrap_or(key);
+
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        text.push_str(&rest[..open]);
+        rest = &rest[open..];
+
+        let value = rest
+            .find('}')
+            .and_then(|close| Some((close, args.get(&rest[1..close])?)));
+        match value {
+            Some((close, value)) => {
+                match value {
+                    Value::String(value) => text.push_str(value),
+                    value => text.push_str(&value.to_string()),
+                }
+                rest = &rest[close + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+
+    text
+}
+
+fn catalogue(locale: Locale, key: &str) -> Option<&'static str> {
+    let entries = match locale {
+        Locale::English => ENGLISH,
+        Locale::Spanish => SPANISH,
+        Locale::Cantonese => CANTONESE,
+    };
+
+    entries
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, template)| *template)
+}
+
+const ENGLISH: &[(&str, &str)] = &[
+        ("error.custom", "(custom) {reason}"),
+        ("error.questionBlanksAndNumAnswersMismatch", "Mismatch in number of question blanks ({numBlanks}) and number of answers ({numAnswers})."),
+        ("error.playerChoosingCardOutOfHandBound", "Player chose a card index ({chosenInd}) >= hand size ({handBound})."),
+        ("error.playerChoosingTheSameCardMultipleTimes", "Player chose the same card index ({chosenInd}) multiple times."),
+        ("error.judgeTryingToSubmitAnswers", "The Judge ({player}) cannot submit answers."),
+        ("error.playerAlreadySubmittedAnswers", "Player {player} already submitted answers."),
+        ("error.insufficientAnswerCardsToDeal", "Cannot deal {eachDeal} cards to {numPlayers} players when there are only {numAnswerCards} cards in total."),
+        ("error.noQuestionCards", "There are no question cards."),
+        ("error.noActiveQuestionCard", "There is no active question card."),
+        ("error.noActiveJudge", "There is no active judge."),
+        ("error.notEnoughPlayers", "There must be at least {minPlayers} players. (Now: {numPlayers})"),
+        ("error.tooManyPlayers", "There can be at most {maxPlayers} players. (Now: {numPlayers})"),
+        ("error.invalidPlayerBounds", "The player bounds must satisfy 2 <= minimum ({minPlayers}) <= maximum ({maxPlayers})."),
+        ("error.playerAlreadyExists", "A Player with the name {player} already exists."),
+        ("error.playerDoesNotExist", "Player with name {player} does not exist."),
+        ("error.gameNotStarted", "The game is not started."),
+        ("error.gameAlreadyInProgress", "The game is already in progress."),
+        ("error.gameEnded", "The game has ended."),
+        ("error.handSizeCannotBeZero", "Hand size cannot be zero."),
+        ("error.judgeCannotBeChosen", "The Judge cannot be chosen."),
+        ("error.invalidDeck", "The deck cannot be imported: {reason}"),
+        ("error.cardNotInHand", "Card {cardId} is not in the player's hand."),
+        ("error.answerDeckExhausted", "{needed} Answer cards are needed but only {available} are left in the deck."),
+        ("error.invalidConfig", "The game config is invalid: {reason}"),
+        ("error.redrawsNotAllowed", "Redrawing hands is not allowed in this game."),
+        ("error.sessionAlreadyExists", "Session {sessionId} already exists."),
+        ("error.sessionDoesNotExist", "Session {sessionId} does not exist."),
+        ("error.notSessionOwner", "Only the owner of session {sessionId} can do this, not {playerName}."),
+        ("error.ownerAlreadyHasLobby", "{owner} is already preparing a game (session {sessionId})."),
+        ("error.notALobby", "Session {sessionId} is not being prepared."),
+        ("error.notAGame", "Session {sessionId} has no running game."),
+
+        ("cli.preparation", "Preparation stage!"),
+        ("cli.help.add", "Add a Player: /add `Player Name`"),
+        ("cli.help.start", "Start the game: /start"),
+        ("cli.help.validate", "Check the decks: /validate"),
+        ("cli.help.handSize", "Set hand size: /handsize `Number >= 1`"),
+        ("cli.help.winTarget", "Set win target: /wintarget `Number >= 1`"),
+        ("cli.help.countBase", "Set count base: /countbase `0 or 1`"),
+        ("cli.help.privacy", "Hide each hand from the other players: /privacy `on or off`"),
+        ("cli.help.preset", "Use a preset: /preset `{presets}`"),
+        ("cli.help.config", "Load or save the game config: /loadconfig `path`, /saveconfig `path`"),
+        ("cli.help.locale", "Change the language: /locale `{locales}`"),
+        ("cli.help.cards", "Add cards: /questions `path`, /answers `path`, /pack `directory or .json/.csv/.tsv deck`; remove them all: /clearcards"),
+        ("cli.playerJoined", "{player} joins the game!"),
+        ("cli.playerLeft", "{player} leaves the game!"),
+        ("cli.usingPreset", "Using the {preset} preset."),
+        ("cli.noSuchPreset", "There is no preset named {preset}."),
+        ("cli.usingLocale", "The language is now English."),
+        ("cli.noSuchLocale", "There is no language with the code {locale}."),
+        ("cli.configLoaded", "Loaded the game config from {path}."),
+        ("cli.configSaved", "Saved the game config to {path}."),
+        ("cli.judgeForRound", "The Judge for this round is {judge}!"),
+        ("cli.yourHand", "{player}, Your hand is:"),
+        ("cli.answerThis", "Answer this: {question}"),
+        ("cli.submitAnswers", "Please submit your answers, {player}"),
+        ("cli.answersCollected", "The following creative answers were collected:"),
+        ("cli.chooseFavorite", "Choose your favorite, Judge {judge}!"),
+        ("cli.winner", "🎉 Congratulations, {player}, You Have Won! 🎉"),
+        ("cli.deckRanOut", "The Answer deck ran out! {player} leads with {points} Awesome Points."),
+        ("cli.noProblems", "No problems found in the decks."),
+        ("cli.reportSummary", "{errors} error(s), {warnings} warning(s), {notes} note(s)"),
+        ("cli.usage", "Usage: cli-app [flags] | cli-app tui [flags] | cli-app host `Your Name` [address] [flags] | cli-app join address `Your Name` | cli-app script [path] [flags]\nThe default address is {address}. A script is read from the path, or from stdin without one, and played without delays."),
+        ("cli.hosting", "Hosting a game at {address}. Others join with: cli-app join {address} `Their Name`"),
+        ("cli.joinOverNetwork", "Players join over the network: cli-app join {address} `Their Name`"),
+        ("cli.gameInProgress", "The game has already started. Try again when it is over."),
+        ("cli.waitingForHost", "Waiting for the host to start the game..."),
+        ("cli.waitingForOthers", "Waiting for the other players..."),
+        ("cli.playerDisconnected", "{player} left, so the game is over."),
+        ("cli.hostLeft", "The host left the game."),
+        ("cli.privacyOn", "Privacy mode is on: each hand is shown only once the device is passed on."),
+        ("cli.privacyOff", "Privacy mode is off."),
+        ("cli.passTo", "Pass the device to {player}, then press Enter."),
+        ("cli.tui.unsupported", "The full-screen mode needs a terminal, so the game is played line by line."),
+        ("cli.tui.header", "Round {round} · Judge: {judge}"),
+        ("cli.tui.question", "Question"),
+        ("cli.tui.hand", "{player}'s hand"),
+        ("cli.tui.answers", "Answers"),
+        ("cli.tui.scores", "Awesome Points"),
+        ("cli.tui.pickHelp", "↑↓ move · Enter pick or submit · Space pick or put back · Backspace put back the last · r redraw · k kick · q end the game"),
+        ("cli.tui.handTooShort", "{blanks} cards are needed but your hand has {cards}. Press r to redraw it or q to end the game."),
+        ("cli.tui.revealing", "The answers are coming in..."),
+        ("cli.tui.judgeHelp", "{judge}, choose your favorite: ↑↓ move · Enter choose · k kick · q end the game"),
+        ("cli.tui.kick", "Kick a player"),
+        ("cli.tui.kickHelp", "↑↓ move · Enter kick them and end the game · Backspace go back"),
+        ("cli.tui.backHelp", "Press any key to go back."),
+        ("cli.tui.roundWinner", "{player} wins the round! Press Enter to continue."),
+        ("cli.gameEnded", "The game was ended early."),
+        ("cli.gameEndedBy", "{player} ended the game."),
+        ("cli.hostOnly", "Only the host can kick players."),
+        ("cli.inputEnded", "The input ended before the game did."),
+        ("cli.commandsHint", "Type /help at any prompt to see the commands."),
+        ("cli.unknownCommand", "There is no such command. Type /help to see them."),
+        ("cli.command.scores", "/scores: show the Awesome Points"),
+        ("cli.command.redraw", "/redraw: swap your hand for new cards, if this game allows it"),
+        ("cli.command.hand", "/hand: show your hand again"),
+        ("cli.command.question", "/question: show the question again"),
+        ("cli.command.kick", "/kick `player`: end the game and remove the player from it. Over the network, only the host can."),
+        ("cli.command.quit", "/quit: end the game and go back to the preparation. A guest leaves instead, which also ends the game."),
+        ("cli.command.help", "/help: show these commands"),
+        ("cli.flags", "Flags:"),
+        ("cli.flag.questions", "  --questions `path`: add questions from a file, or from every file in a directory"),
+        ("cli.flag.answers", "  --answers `path`: add answers from a file, or from every file in a directory"),
+        ("cli.flag.pack", "  --pack `path`: add a directory with questions and answers, or with such packs"),
+        ("cli.flag.handSize", "  --hand-size `Number >= 1`"),
+        ("cli.flag.winTarget", "  --win-target `Number >= 1`"),
+        ("cli.flag.countBase", "  --count-base `0 or 1`"),
+        ("cli.flag.seed", "  --seed `Number`: shuffle the same way every time; 0 in a script by default"),
+        ("cli.flag.help", "  --help: show this. A .json file is an array of cards; any other file has one card per line."),
+        ("cli.unknownFlag", "There is no flag {flag}."),
+        ("cli.flagNeedsValue", "{flag} needs a value."),
+        ("cli.badFlagValue", "{value} is not a valid value for {flag}."),
+        ("cli.cardsLoaded", "Added {questions} questions and {answers} answers from {path}."),
+        ("cli.cardsNotLoaded", "Cannot load the cards: {reason}"),
+        ("cli.cardsCleared", "Removed all the questions and answers."),
+        ("cli.invalidInput", "Invalid input"),
+        ("cli.emptyInput", "Empty input"),
+];
+
+const SPANISH: &[(&str, &str)] = &[
+        ("error.custom", "(personalizado) {reason}"),
+        ("error.questionBlanksAndNumAnswersMismatch", "La pregunta tiene {numBlanks} espacio(s) en blanco, pero se dieron {numAnswers} respuesta(s)."),
+        ("error.playerChoosingCardOutOfHandBound", "El jugador eligió la carta número {chosenInd}, pero la mano solo tiene {handBound}."),
+        ("error.playerChoosingTheSameCardMultipleTimes", "El jugador eligió la carta número {chosenInd} más de una vez."),
+        ("error.judgeTryingToSubmitAnswers", "El Juez ({player}) no puede enviar respuestas."),
+        ("error.playerAlreadySubmittedAnswers", "{player} ya envió sus respuestas."),
+        ("error.insufficientAnswerCardsToDeal", "No se pueden repartir {eachDeal} cartas a {numPlayers} jugadores con solo {numAnswerCards} cartas en total."),
+        ("error.noQuestionCards", "No hay cartas de pregunta."),
+        ("error.noActiveQuestionCard", "No hay ninguna pregunta en juego."),
+        ("error.noActiveJudge", "No hay ningún Juez en esta ronda."),
+        ("error.notEnoughPlayers", "Se necesitan al menos {minPlayers} jugadores. (Ahora: {numPlayers})"),
+        ("error.tooManyPlayers", "Puede haber como máximo {maxPlayers} jugadores. (Ahora: {numPlayers})"),
+        ("error.invalidPlayerBounds", "Los límites de jugadores deben cumplir 2 <= mínimo ({minPlayers}) <= máximo ({maxPlayers})."),
+        ("error.playerAlreadyExists", "Ya existe un jugador con el nombre {player}."),
+        ("error.playerDoesNotExist", "No existe ningún jugador con el nombre {player}."),
+        ("error.gameNotStarted", "La partida no ha comenzado."),
+        ("error.gameAlreadyInProgress", "La partida ya está en curso."),
+        ("error.gameEnded", "La partida ha terminado."),
+        ("error.handSizeCannotBeZero", "El tamaño de la mano no puede ser cero."),
+        ("error.judgeCannotBeChosen", "No se puede elegir al Juez."),
+        ("error.invalidDeck", "No se puede importar la baraja: {reason}"),
+        ("error.cardNotInHand", "La carta {cardId} no está en la mano del jugador."),
+        ("error.answerDeckExhausted", "Se necesitan {needed} cartas de respuesta, pero solo quedan {available} en la baraja."),
+        ("error.invalidConfig", "La configuración de la partida no es válida: {reason}"),
+        ("error.redrawsNotAllowed", "En esta partida no se permite cambiar la mano."),
+        ("error.sessionAlreadyExists", "La sesión {sessionId} ya existe."),
+        ("error.sessionDoesNotExist", "La sesión {sessionId} no existe."),
+        ("error.notSessionOwner", "Solo el dueño de la sesión {sessionId} puede hacer esto, no {playerName}."),
+        ("error.ownerAlreadyHasLobby", "{owner} ya está preparando una partida (sesión {sessionId})."),
+        ("error.notALobby", "La sesión {sessionId} no está en preparación."),
+        ("error.notAGame", "La sesión {sessionId} no tiene ninguna partida en curso."),
+
+        ("cli.preparation", "¡Fase de preparación!"),
+        ("cli.help.add", "Añadir un jugador: /add `Nombre`"),
+        ("cli.help.start", "Empezar la partida: /start"),
+        ("cli.help.validate", "Revisar las barajas: /validate"),
+        ("cli.help.handSize", "Tamaño de la mano: /handsize `Número >= 1`"),
+        ("cli.help.winTarget", "Puntos para ganar: /wintarget `Número >= 1`"),
+        ("cli.help.countBase", "Empezar a contar desde: /countbase `0 o 1`"),
+        ("cli.help.privacy", "Ocultar cada mano a los demás jugadores: /privacy `on u off`"),
+        ("cli.help.preset", "Usar un modo predefinido: /preset `{presets}`"),
+        ("cli.help.config", "Cargar o guardar la configuración: /loadconfig `ruta`, /saveconfig `ruta`"),
+        ("cli.help.locale", "Cambiar el idioma: /locale `{locales}`"),
+        ("cli.help.cards", "Añadir cartas: /questions `ruta`, /answers `ruta`, /pack `carpeta o baraja .json/.csv/.tsv`; quitarlas todas: /clearcards"),
+        ("cli.playerJoined", "¡{player} se une a la partida!"),
+        ("cli.playerLeft", "¡{player} deja la partida!"),
+        ("cli.usingPreset", "Usando el modo {preset}."),
+        ("cli.noSuchPreset", "No hay ningún modo llamado {preset}."),
+        ("cli.usingLocale", "Ahora el idioma es español."),
+        ("cli.noSuchLocale", "No hay ningún idioma con el código {locale}."),
+        ("cli.configLoaded", "Configuración cargada desde {path}."),
+        ("cli.configSaved", "Configuración guardada en {path}."),
+        ("cli.judgeForRound", "¡El Juez de esta ronda es {judge}!"),
+        ("cli.yourHand", "{player}, esta es tu mano:"),
+        ("cli.answerThis", "Responde a esto: {question}"),
+        ("cli.submitAnswers", "Envía tus respuestas, {player}"),
+        ("cli.answersCollected", "Estas son las respuestas más creativas:"),
+        ("cli.chooseFavorite", "¡Elige tu favorita, Juez {judge}!"),
+        ("cli.winner", "🎉 ¡Felicidades, {player}, has ganado! 🎉"),
+        ("cli.deckRanOut", "¡Se acabaron las cartas de respuesta! {player} va en cabeza con {points} Puntos Geniales."),
+        ("cli.noProblems", "No se encontraron problemas en las barajas."),
+        ("cli.reportSummary", "{errors} error(es), {warnings} advertencia(s), {notes} nota(s)"),
+        ("cli.usage", "Uso: cli-app [opciones] | cli-app tui [opciones] | cli-app host `Tu nombre` [dirección] [opciones] | cli-app join dirección `Tu nombre` | cli-app script [ruta] [opciones]\nLa dirección por defecto es {address}. Un guion se lee de la ruta, o de stdin si no hay, y se juega sin pausas."),
+        ("cli.hosting", "Partida abierta en {address}. Los demás se unen con: cli-app join {address} `Su nombre`"),
+        ("cli.joinOverNetwork", "Los jugadores se unen por la red: cli-app join {address} `Su nombre`"),
+        ("cli.gameInProgress", "La partida ya ha comenzado. Vuelve a intentarlo cuando termine."),
+        ("cli.waitingForHost", "Esperando a que el anfitrión empiece la partida..."),
+        ("cli.waitingForOthers", "Esperando a los demás jugadores..."),
+        ("cli.playerDisconnected", "{player} se fue, así que la partida terminó."),
+        ("cli.hostLeft", "El anfitrión dejó la partida."),
+        ("cli.privacyOn", "El modo privado está activado: cada mano se muestra solo después de pasar el dispositivo."),
+        ("cli.privacyOff", "El modo privado está desactivado."),
+        ("cli.passTo", "Pasa el dispositivo a {player} y pulsa Enter."),
+        ("cli.tui.unsupported", "El modo de pantalla completa necesita una terminal, así que se juega línea por línea."),
+        ("cli.tui.header", "Ronda {round} · Juez: {judge}"),
+        ("cli.tui.question", "Pregunta"),
+        ("cli.tui.hand", "Mano de {player}"),
+        ("cli.tui.answers", "Respuestas"),
+        ("cli.tui.scores", "Puntos"),
+        ("cli.tui.pickHelp", "↑↓ mover · Enter elegir o enviar · Espacio elegir o devolver · Retroceso devolver la última · r cambiar la mano · k expulsar · q terminar la partida"),
+        ("cli.tui.handTooShort", "Se necesitan {blanks} cartas, pero tu mano tiene {cards}. Pulsa r para cambiarla o q para terminar la partida."),
+        ("cli.tui.revealing", "Llegan las respuestas..."),
+        ("cli.tui.judgeHelp", "{judge}, elige tu favorita: ↑↓ mover · Enter elegir · k expulsar · q terminar la partida"),
+        ("cli.tui.kick", "Expulsar a un jugador"),
+        ("cli.tui.kickHelp", "↑↓ mover · Enter expulsar y terminar la partida · Retroceso volver"),
+        ("cli.tui.backHelp", "Pulsa cualquier tecla para volver."),
+        ("cli.tui.roundWinner", "¡{player} gana la ronda! Pulsa Enter para continuar."),
+        ("cli.gameEnded", "La partida terminó antes de tiempo."),
+        ("cli.gameEndedBy", "{player} terminó la partida."),
+        ("cli.hostOnly", "Solo el anfitrión puede expulsar jugadores."),
+        ("cli.inputEnded", "La entrada terminó antes que la partida."),
+        ("cli.commandsHint", "Escribe /help en cualquier momento para ver los comandos."),
+        ("cli.unknownCommand", "Ese comando no existe. Escribe /help para verlos."),
+        ("cli.command.scores", "/scores: ver los puntos"),
+        ("cli.command.redraw", "/redraw: cambiar tu mano por cartas nuevas, si esta partida lo permite"),
+        ("cli.command.hand", "/hand: volver a ver tu mano"),
+        ("cli.command.question", "/question: volver a ver la pregunta"),
+        ("cli.command.kick", "/kick `jugador`: terminar la partida y quitar al jugador. En red, solo puede el anfitrión."),
+        ("cli.command.quit", "/quit: terminar la partida y volver a la preparación. Un invitado se va, lo que también termina la partida."),
+        ("cli.command.help", "/help: ver estos comandos"),
+        ("cli.flags", "Opciones:"),
+        ("cli.flag.questions", "  --questions `ruta`: añadir preguntas de un archivo, o de todos los de un directorio"),
+        ("cli.flag.answers", "  --answers `ruta`: añadir respuestas de un archivo, o de todos los de un directorio"),
+        ("cli.flag.pack", "  --pack `ruta`: añadir un directorio con preguntas y respuestas, o con paquetes así"),
+        ("cli.flag.handSize", "  --hand-size `Número >= 1`"),
+        ("cli.flag.winTarget", "  --win-target `Número >= 1`"),
+        ("cli.flag.countBase", "  --count-base `0 o 1`"),
+        ("cli.flag.seed", "  --seed `Número`: barajar siempre igual; 0 por defecto en un guion"),
+        ("cli.flag.help", "  --help: ver esto. Un archivo .json es una lista de cartas; cualquier otro tiene una carta por línea."),
+        ("cli.unknownFlag", "No existe la opción {flag}."),
+        ("cli.flagNeedsValue", "{flag} necesita un valor."),
+        ("cli.badFlagValue", "{value} no es un valor válido para {flag}."),
+        ("cli.cardsLoaded", "Se añadieron {questions} preguntas y {answers} respuestas de {path}."),
+        ("cli.cardsNotLoaded", "No se pudieron cargar las cartas: {reason}"),
+        ("cli.cardsCleared", "Se quitaron todas las preguntas y respuestas."),
+        ("cli.invalidInput", "Entrada no válida"),
+        ("cli.emptyInput", "Entrada vacía"),
+];
+
+const CANTONESE: &[(&str, &str)] = &[
+        ("error.custom", "（自訂）{reason}"),
+        ("error.questionBlanksAndNumAnswersMismatch", "問題有 {numBlanks} 個空格，但係交咗 {numAnswers} 個答案。"),
+        ("error.playerChoosingCardOutOfHandBound", "揀咗第 {chosenInd} 張卡，但係手上得 {handBound} 張。"),
+        ("error.playerChoosingTheSameCardMultipleTimes", "第 {chosenInd} 張卡揀咗唔止一次。"),
+        ("error.judgeTryingToSubmitAnswers", "評判（{player}）唔可以交答案。"),
+        ("error.playerAlreadySubmittedAnswers", "{player} 已經交咗答案。"),
+        ("error.insufficientAnswerCardsToDeal", "總共得 {numAnswerCards} 張卡，唔夠每位派 {eachDeal} 張畀 {numPlayers} 位玩家。"),
+        ("error.noQuestionCards", "冇問題卡。"),
+        ("error.noActiveQuestionCard", "而家冇問題喺度。"),
+        ("error.noActiveJudge", "而家冇評判。"),
+        ("error.notEnoughPlayers", "最少要有 {minPlayers} 位玩家。（而家：{numPlayers}）"),
+        ("error.tooManyPlayers", "最多只可以有 {maxPlayers} 位玩家。（而家：{numPlayers}）"),
+        ("error.invalidPlayerBounds", "玩家人數上下限要符合 2 <= 最少（{minPlayers}）<= 最多（{maxPlayers}）。"),
+        ("error.playerAlreadyExists", "已經有位叫 {player} 嘅玩家。"),
+        ("error.playerDoesNotExist", "冇玩家叫 {player}。"),
+        ("error.gameNotStarted", "遊戲仲未開始。"),
+        ("error.gameAlreadyInProgress", "遊戲已經開始咗。"),
+        ("error.gameEnded", "遊戲已經完咗。"),
+        ("error.handSizeCannotBeZero", "手牌數量唔可以係零。"),
+        ("error.judgeCannotBeChosen", "唔可以揀評判。"),
+        ("error.invalidDeck", "匯入唔到呢副牌：{reason}"),
+        ("error.cardNotInHand", "{cardId} 呢張卡唔喺玩家手上。"),
+        ("error.answerDeckExhausted", "要 {needed} 張答案卡，但係牌堆得返 {available} 張。"),
+        ("error.invalidConfig", "遊戲設定唔啱：{reason}"),
+        ("error.redrawsNotAllowed", "呢局唔可以換手牌。"),
+        ("error.sessionAlreadyExists", "已經有 {sessionId} 呢個場次。"),
+        ("error.sessionDoesNotExist", "冇 {sessionId} 呢個場次。"),
+        ("error.notSessionOwner", "只有場次 {sessionId} 嘅主持人先可以咁做，{playerName} 唔得。"),
+        ("error.ownerAlreadyHasLobby", "{owner} 已經準備緊一局遊戲（場次 {sessionId}）。"),
+        ("error.notALobby", "場次 {sessionId} 唔係準備緊。"),
+        ("error.notAGame", "場次 {sessionId} 冇進行緊嘅遊戲。"),
+
+        ("cli.preparation", "準備階段！"),
+        ("cli.help.add", "加入玩家：/add `名`"),
+        ("cli.help.start", "開始遊戲：/start"),
+        ("cli.help.validate", "檢查牌堆：/validate"),
+        ("cli.help.handSize", "設定手牌數量：/handsize `數字 >= 1`"),
+        ("cli.help.winTarget", "設定勝出分數：/wintarget `數字 >= 1`"),
+        ("cli.help.countBase", "由幾開始數：/countbase `0 或 1`"),
+        ("cli.help.privacy", "唔俾其他玩家睇到手牌：/privacy `on 或 off`"),
+        ("cli.help.preset", "用預設模式：/preset `{presets}`"),
+        ("cli.help.config", "載入或者儲存遊戲設定：/loadconfig `路徑`，/saveconfig `路徑`"),
+        ("cli.help.locale", "轉語言：/locale `{locales}`"),
+        ("cli.help.cards", "加卡：/questions `路徑`，/answers `路徑`，/pack `資料夾或者 .json/.csv/.tsv 牌組`；全部清走：/clearcards"),
+        ("cli.playerJoined", "{player} 加入咗遊戲！"),
+        ("cli.playerLeft", "{player} 離開咗遊戲！"),
+        ("cli.usingPreset", "而家用緊 {preset} 模式。"),
+        ("cli.noSuchPreset", "冇叫 {preset} 嘅模式。"),
+        ("cli.usingLocale", "而家用廣東話。"),
+        ("cli.noSuchLocale", "冇 {locale} 呢個語言代碼。"),
+        ("cli.configLoaded", "已經由 {path} 載入遊戲設定。"),
+        ("cli.configSaved", "已經將遊戲設定儲存到 {path}。"),
+        ("cli.judgeForRound", "呢輪嘅評判係 {judge}！"),
+        ("cli.yourHand", "{player}，你手上有："),
+        ("cli.answerThis", "答呢條：{question}"),
+        ("cli.submitAnswers", "{player}，請交答案"),
+        ("cli.answersCollected", "收到以下嘅創意答案："),
+        ("cli.chooseFavorite", "評判 {judge}，揀你最鍾意嘅！"),
+        ("cli.winner", "🎉 恭喜 {player}，你贏咗！🎉"),
+        ("cli.deckRanOut", "答案卡用晒！{player} 以 {points} 分領先。"),
+        ("cli.noProblems", "牌堆冇問題。"),
+        ("cli.reportSummary", "{errors} 個錯誤，{warnings} 個警告，{notes} 個備註"),
+        ("cli.usage", "用法：cli-app [選項] | cli-app tui [選項] | cli-app host `你個名` [地址] [選項] | cli-app join 地址 `你個名` | cli-app script [路徑] [選項]\n預設地址係 {address}。劇本由路徑讀，冇路徑就由 stdin 讀，玩嘅時候唔會停。"),
+        ("cli.hosting", "喺 {address} 開咗局。其他人可以咁加入：cli-app join {address} `佢哋個名`"),
+        ("cli.joinOverNetwork", "玩家要經網絡加入：cli-app join {address} `佢哋個名`"),
+        ("cli.gameInProgress", "遊戲已經開始咗，等佢完咗再試下。"),
+        ("cli.waitingForHost", "等緊主持開始遊戲……"),
+        ("cli.waitingForOthers", "等緊其他玩家……"),
+        ("cli.playerDisconnected", "{player} 走咗，所以遊戲完咗。"),
+        ("cli.hostLeft", "主持離開咗遊戲。"),
+        ("cli.privacyOn", "開咗私隱模式：部機交到下一個玩家手上先會顯示佢嘅手牌。"),
+        ("cli.privacyOff", "熄咗私隱模式。"),
+        ("cli.passTo", "將部機交俾 {player}，然後撳 Enter。"),
+        ("cli.tui.unsupported", "全螢幕模式要喺終端機度用，所以會逐行玩。"),
+        ("cli.tui.header", "第 {round} 輪 · 評判：{judge}"),
+        ("cli.tui.question", "問題"),
+        ("cli.tui.hand", "{player} 嘅手牌"),
+        ("cli.tui.answers", "答案"),
+        ("cli.tui.scores", "分數"),
+        ("cli.tui.pickHelp", "↑↓ 移動 · Enter 揀或者交 · 空格 揀或者放返 · Backspace 放返最後嗰張 · r 換牌 · k 踢人 · q 結束遊戲"),
+        ("cli.tui.handTooShort", "要 {blanks} 張卡，但係你手上得 {cards} 張。撳 r 換牌，或者撳 q 結束遊戲。"),
+        ("cli.tui.revealing", "答案嚟緊……"),
+        ("cli.tui.judgeHelp", "{judge}，揀你最鍾意嘅：↑↓ 移動 · Enter 揀 · k 踢人 · q 結束遊戲"),
+        ("cli.tui.kick", "踢走玩家"),
+        ("cli.tui.kickHelp", "↑↓ 移動 · Enter 踢走佢並結束遊戲 · Backspace 返去"),
+        ("cli.tui.backHelp", "撳任何掣返去。"),
+        ("cli.tui.roundWinner", "{player} 贏咗呢輪！撳 Enter 繼續。"),
+        ("cli.gameEnded", "遊戲提早結束咗。"),
+        ("cli.gameEndedBy", "{player} 結束咗遊戲。"),
+        ("cli.hostOnly", "得主持人先可以踢人。"),
+        ("cli.inputEnded", "遊戲未完輸入就完咗。"),
+        ("cli.commandsHint", "喺任何時候打 /help 就可以睇到指令。"),
+        ("cli.unknownCommand", "冇呢個指令。打 /help 睇下有咩指令。"),
+        ("cli.command.scores", "/scores：睇分數"),
+        ("cli.command.redraw", "/redraw：換過一手新牌（如果呢局容許）"),
+        ("cli.command.hand", "/hand：再睇一次手牌"),
+        ("cli.command.question", "/question：再睇一次問題"),
+        ("cli.command.kick", "/kick `玩家`：結束遊戲並踢走嗰個玩家。網上玩嘅話，得主持人先可以咁做。"),
+        ("cli.command.quit", "/quit：結束遊戲，返去準備階段。客人用嘅話就係離開，遊戲一樣會結束。"),
+        ("cli.command.help", "/help：睇呢啲指令"),
+        ("cli.flags", "選項："),
+        ("cli.flag.questions", "  --questions `路徑`：由一個檔案，或者一個資料夾入面所有檔案加問題"),
+        ("cli.flag.answers", "  --answers `路徑`：由一個檔案，或者一個資料夾入面所有檔案加答案"),
+        ("cli.flag.pack", "  --pack `路徑`：加一個有問題同答案嘅資料夾，或者有呢啲卡包嘅資料夾"),
+        ("cli.flag.handSize", "  --hand-size `數字 >= 1`"),
+        ("cli.flag.winTarget", "  --win-target `數字 >= 1`"),
+        ("cli.flag.countBase", "  --count-base `0 或 1`"),
+        ("cli.flag.seed", "  --seed `數字`：每次都洗得一樣；劇本預設用 0"),
+        ("cli.flag.help", "  --help：睇呢個。.json 檔係一串卡；其他檔案每行一張卡。"),
+        ("cli.unknownFlag", "冇 {flag} 呢個選項。"),
+        ("cli.flagNeedsValue", "{flag} 要有個值。"),
+        ("cli.badFlagValue", "{value} 唔係 {flag} 嘅有效值。"),
+        ("cli.cardsLoaded", "由 {path} 加咗 {questions} 條問題同 {answers} 個答案。"),
+        ("cli.cardsNotLoaded", "載入唔到啲卡：{reason}"),
+        ("cli.cardsCleared", "清走晒所有問題同答案。"),
+        ("cli.invalidInput", "輸入唔啱"),
+        ("cli.emptyInput", "冇輸入嘢"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locale_codes() {
+        for locale in Locale::ALL {
+            assert_eq!(Locale::from_code(locale.code()), Some(locale));
+        }
+        assert_eq!(Locale::from_code("es-MX"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_code("en_GB.UTF-8"), Some(Locale::English));
+        assert_eq!(Locale::from_code("zh-HK"), Some(Locale::Cantonese));
+        assert_eq!(Locale::from_code("zh-CN"), None);
+        assert_eq!(Locale::from_code("C.UTF-8"), None);
+    }
+
+    #[test]
+    fn fill_in_arguments() {
+        let message = Message::new("cli.deckRanOut")
+            .arg("player", "Alex")
+            .arg("points", 3);
+
+        assert_eq!(
+            message.localize(Locale::English),
+            "The Answer deck ran out! Alex leads with 3 Awesome Points."
+        );
+        assert_eq!(
+            message.localize(Locale::Cantonese),
+            "答案卡用晒！Alex 以 3 分領先。"
+        );
+    }
+
+    #[test]
+    fn fall_back_to_english_then_the_key() {
+        assert_eq!(
+            translate(Locale::Spanish, "cli.emptyInput", &Map::new()),
+            "Entrada vacía"
+        );
+        assert_eq!(
+            translate(Locale::Spanish, "test.onlyTheKey", &Map::new()),
+            "test.onlyTheKey"
+        );
+    }
+
+    #[test]
+    fn leave_substituted_values_alone() {
+        let message = Message::new("cli.deckRanOut")
+            .arg("player", "{points}")
+            .arg("points", 3);
+
+        assert_eq!(
+            message.localize(Locale::English),
+            "The Answer deck ran out! {points} leads with 3 Awesome Points."
+        );
+        assert_eq!(
+            translate(Locale::English, "test.{unknown}", &Map::new()),
+            "test.{unknown}"
+        );
+    }
+
+    #[test]
+    fn every_locale_covers_english() {
+        for locale in Locale::ALL {
+            for (key, _) in ENGLISH {
+                assert!(
+                    catalogue(locale, key).is_some(),
+                    "{:?} lacks {}",
+                    locale,
+                    key
+                );
+            }
+        }
+    }
+}
@@ -1,10 +1,18 @@
+pub mod config;
+pub mod decks;
 pub mod drivers;
+pub mod events;
 pub mod game_state;
+pub mod i18n;
+pub mod rules;
+pub mod sessions;
 
 mod cards;
 mod error;
 mod player;
 
+pub use error::GameCoreError;
+
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
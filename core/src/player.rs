@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::{cards::AnswerCard, error::GameCoreError};
+use crate::{cards::AnswerCard, error::GameCoreError, game_state::HandCard};
 
 #[derive(Clone, Debug)]
 pub(crate) struct Player {
@@ -26,6 +26,12 @@ impl Player {
         self.awesome_points
     }
 
+    /// `points` may be negative. The value after the change is returned.
+    pub(crate) fn add_awesome_points(&mut self, points: i32) -> i32 {
+        self.awesome_points += points;
+        self.awesome_points
+    }
+
     pub(crate) fn hand_size(&self) -> usize {
         self.hand.len()
     }
@@ -38,6 +44,31 @@ impl Player {
         self.hand.iter().map(|card| card.content.clone()).collect()
     }
 
+    pub(crate) fn report_hand_cards(&self) -> Vec<HandCard> {
+        self.hand
+            .iter()
+            .map(|card| HandCard {
+                id: card.id.clone(),
+                content: card.content.clone(),
+            })
+            .collect()
+    }
+
+    /// ZERO-based indices of the cards with `card_ids` in the hand, in the same order.
+    pub(crate) fn indices_of(&self, card_ids: &[String]) -> Result<Vec<usize>, GameCoreError> {
+        card_ids
+            .iter()
+            .map(|card_id| {
+                self.hand
+                    .iter()
+                    .position(|card| card.id == *card_id)
+                    .ok_or_else(|| GameCoreError::CardNotInHand {
+                        card_id: card_id.clone(),
+                    })
+            })
+            .collect()
+    }
+
     /// `indices` are ZERO-based indices of the hand.
     pub(crate) fn play_cards_content(
         &self,
@@ -121,6 +152,14 @@ mod tests {
         assert_eq!(player.increment_awesome_points(), 2);
     }
 
+    #[test]
+    fn add_awesome_points() {
+        let mut player = Player::new();
+
+        assert_eq!(player.add_awesome_points(3), 3);
+        assert_eq!(player.add_awesome_points(-5), -2);
+    }
+
     #[test]
     fn add_card_to_hand() {
         let mut player = Player::new();
@@ -272,4 +311,44 @@ mod tests {
             assert_eq!(card_content, i.to_string());
         }
     }
+
+    #[test]
+    fn report_hand_cards() {
+        let mut player = Player::new();
+        player.add_card_to_hand(AnswerCard::new("42"));
+
+        let hand = player.report_hand_cards();
+
+        assert_eq!(hand.len(), 1);
+        assert_eq!(hand[0].id, AnswerCard::new("42").id);
+        assert_eq!(hand[0].content, "42");
+    }
+
+    #[test]
+    fn indices_of_card_ids() {
+        let mut player = Player::new();
+        for i in 0..10 {
+            player.add_card_to_hand(AnswerCard::new(i.to_string()));
+        }
+        let card_ids = [AnswerCard::new("7").id, AnswerCard::new("2").id];
+
+        assert_eq!(player.indices_of(&card_ids).unwrap(), vec![7, 2]);
+    }
+
+    #[test]
+    fn indices_of_card_not_in_hand() {
+        let mut player = Player::new();
+        player.add_card_to_hand(AnswerCard::new("0"));
+        let missing_id = AnswerCard::new("1").id;
+
+        assert_eq!(
+            player
+                .indices_of(std::slice::from_ref(&missing_id))
+                .err()
+                .unwrap(),
+            GameCoreError::CardNotInHand {
+                card_id: missing_id
+            }
+        );
+    }
 }
@@ -0,0 +1,115 @@
+use crate::error::GameCoreError;
+
+/// A house rule, run by `GenericDriver` at each step of a round.
+///
+/// Every hook does nothing by default. Hooks that return Err() stop the action they validate,
+/// without any side effects.
+pub trait Rule: RuleClone + Send {
+    fn name(&self) -> &str;
+
+    /// After the Judge and the question are chosen, before the hands are shown.
+    fn on_round_start(&mut self, _round: &RoundStart, _effects: &mut RuleEffects) {}
+
+    /// Before a submission is accepted.
+    fn validate_submission(&mut self, _submission: &Submission) -> Result<(), GameCoreError> {
+        Ok(())
+    }
+
+    /// After a submission is accepted.
+    fn after_submission(&mut self, _submission: &Submission, _effects: &mut RuleEffects) {}
+
+    /// When every player has submitted; `submissions` may be reordered or edited before they
+    /// are shown to the Judge.
+    fn on_submissions_closed(&mut self, _submissions: &mut Vec<(String, String)>) {}
+
+    /// Before the Judge's choice is accepted.
+    fn validate_judging(&mut self, _judging: &Judging) -> Result<(), GameCoreError> {
+        Ok(())
+    }
+
+    /// After the Judge's choice is accepted, before the ranking is reported.
+    fn on_round_end(&mut self, _judging: &Judging, _effects: &mut RuleEffects) {}
+}
+
+/// Lets a builder hand a fresh copy of its rules to every game it builds.
+pub trait RuleClone {
+    fn clone_box(&self) -> Box<dyn Rule>;
+}
+
+impl<T> RuleClone for T
+where
+    T: Rule + Clone + 'static,
+{
+    fn clone_box(&self) -> Box<dyn Rule> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Rule> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+pub struct RoundStart<'a> {
+    /// ONE-based.
+    pub round: usize,
+    pub judge: &'a str,
+    pub question: &'a str,
+    pub players: &'a [String],
+}
+
+pub struct Submission<'a> {
+    pub round: usize,
+    pub player: &'a str,
+    /// Contents of the submitted cards, in the order played.
+    pub answers: &'a [String],
+}
+
+pub struct Judging<'a> {
+    pub round: usize,
+    pub judge: &'a str,
+    pub chosen_player: &'a str,
+    /// (player, combined answer), as shown to the Judge.
+    pub submissions: &'a [(String, String)],
+}
+
+/// Changes requested by hooks, applied by the driver once every rule has run.
+///
+/// Effects naming unknown players are ignored, and so are redraws the Answer deck cannot cover.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RuleEffects {
+    pub(crate) awards: Vec<(String, i32)>,
+    pub(crate) redraws: Vec<String>,
+}
+
+impl RuleEffects {
+    /// Give (or, with negative `points`, take) Awesome Points.
+    pub fn award(&mut self, player: impl Into<String>, points: i32) {
+        self.awards.push((player.into(), points));
+    }
+
+    /// Discard the player's hand and deal a new one, even if redraws are not allowed.
+    pub fn redraw_hand(&mut self, player: impl Into<String>) {
+        self.redraws.push(player.into());
+    }
+}
+
+/// The standard game: the chosen player earns one Awesome Point.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AwesomePointForWinner;
+
+impl Rule for AwesomePointForWinner {
+    fn name(&self) -> &str {
+        "Awesome Point for the winner"
+    }
+
+    fn on_round_end(&mut self, judging: &Judging, effects: &mut RuleEffects) {
+        effects.award(judging.chosen_player, 1);
+    }
+}
+
+/// The rules a builder starts with.
+pub fn default_rules() -> Vec<Box<dyn Rule>> {
+    vec![Box::new(AwesomePointForWinner)]
+}
@@ -0,0 +1,448 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{
+    drivers::{
+        generic::{GenericDriver, GenericDriverBuilder},
+        GameCoreDriver,
+    },
+    error::GameCoreError,
+};
+
+/// Chosen by the frontend, e.g. a channel ID. Opaque to the registry.
+pub type SessionId = String;
+
+/// What the registry needs from a game being prepared.
+pub trait SessionLobby: Default {
+    type Game: SessionGame;
+
+    fn players(&self) -> Vec<String>;
+
+    fn build(&self) -> Result<Self::Game, GameCoreError>;
+}
+
+/// What the registry needs from a running game.
+pub trait SessionGame {
+    fn players(&self) -> Vec<String>;
+
+    /// True once the game can be cleaned up.
+    fn is_finished(&self) -> bool;
+
+    /// Called when the session is removed.
+    fn end(self);
+}
+
+impl SessionLobby for GenericDriverBuilder {
+    type Game = GenericDriver;
+
+    fn players(&self) -> Vec<String> {
+        GenericDriverBuilder::players(self)
+    }
+
+    fn build(&self) -> Result<GenericDriver, GameCoreError> {
+        GenericDriverBuilder::build(self)
+    }
+}
+
+impl SessionGame for GenericDriver {
+    fn players(&self) -> Vec<String> {
+        self.ordered_players()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.is_game_over() || self.winner().is_some()
+    }
+
+    fn end(self) {
+        self.end_game();
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionStage {
+    Lobby,
+    Game,
+}
+
+enum Stage<L: SessionLobby> {
+    Lobby(L),
+    Game(L::Game),
+}
+
+struct Session<L: SessionLobby> {
+    owner: String,
+    stage: Stage<L>,
+}
+
+/// Every lobby and game of a frontend, keyed by session ID.
+///
+/// A lobby belongs to the player who created it: only they can close it or start its game.
+/// Each owner has at most one lobby at a time.
+pub struct SessionRegistry<L: SessionLobby = GenericDriverBuilder> {
+    sessions: HashMap<SessionId, Session<L>>,
+}
+
+impl<L: SessionLobby> Default for SessionRegistry<L> {
+    fn default() -> Self {
+        Self {
+            sessions: HashMap::new(),
+        }
+    }
+}
+
+impl<L: SessionLobby> SessionRegistry<L> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn create_lobby(
+        &mut self,
+        session_id: impl Into<SessionId>,
+        owner: impl Into<String>,
+    ) -> Result<&mut L, GameCoreError> {
+        let session_id = session_id.into();
+        let owner = owner.into();
+
+        if self.sessions.contains_key(&session_id) {
+            return Err(GameCoreError::SessionAlreadyExists { session_id });
+        }
+        if let Some(lobby_id) = self.lobby_of_owner(&owner) {
+            return Err(GameCoreError::OwnerAlreadyHasLobby {
+                owner,
+                session_id: lobby_id.to_owned(),
+            });
+        }
+
+        let session = self.sessions.entry(session_id).or_insert(Session {
+            owner,
+            stage: Stage::Lobby(L::default()),
+        });
+
+        match &mut session.stage {
+            Stage::Lobby(lobby) => Ok(lobby),
+            Stage::Game(_) => unreachable!(),
+        }
+    }
+
+    pub fn lobby(&self, session_id: &str) -> Result<&L, GameCoreError> {
+        match &self.session(session_id)?.stage {
+            Stage::Lobby(lobby) => Ok(lobby),
+            Stage::Game(_) => Err(not_a_lobby(session_id)),
+        }
+    }
+
+    pub fn lobby_mut(&mut self, session_id: &str) -> Result<&mut L, GameCoreError> {
+        match &mut self.session_mut(session_id)?.stage {
+            Stage::Lobby(lobby) => Ok(lobby),
+            Stage::Game(_) => Err(not_a_lobby(session_id)),
+        }
+    }
+
+    pub fn lobby_of_owner(&self, owner: &str) -> Option<&str> {
+        self.sessions
+            .iter()
+            .find(|(_, session)| session.owner == owner && matches!(session.stage, Stage::Lobby(_)))
+            .map(|(session_id, _)| session_id.as_str())
+    }
+
+    pub fn close_lobby(&mut self, session_id: &str, requester: &str) -> Result<(), GameCoreError> {
+        self.lobby(session_id)?;
+        self.check_owner(session_id, requester)?;
+
+        self.sessions.remove(session_id);
+
+        Ok(())
+    }
+
+    /// Build the lobby's game and register it as `game_id`, which may be the lobby's own ID or a
+    /// new one, e.g. for a thread created for the game.
+    ///
+    /// The lobby stays open if the game cannot be built.
+    pub fn start_game(
+        &mut self,
+        lobby_id: &str,
+        requester: &str,
+        game_id: impl Into<SessionId>,
+    ) -> Result<&mut L::Game, GameCoreError> {
+        let game_id = game_id.into();
+        let lobby = self.lobby(lobby_id)?;
+        self.check_owner(lobby_id, requester)?;
+
+        if game_id != lobby_id && self.sessions.contains_key(&game_id) {
+            return Err(GameCoreError::SessionAlreadyExists {
+                session_id: game_id,
+            });
+        }
+
+        let game = lobby.build()?;
+        let owner = self.sessions.remove(lobby_id).unwrap().owner;
+        let session = self.sessions.entry(game_id).or_insert(Session {
+            owner,
+            stage: Stage::Game(game),
+        });
+
+        match &mut session.stage {
+            Stage::Game(game) => Ok(game),
+            Stage::Lobby(_) => unreachable!(),
+        }
+    }
+
+    pub fn game(&self, session_id: &str) -> Result<&L::Game, GameCoreError> {
+        match &self.session(session_id)?.stage {
+            Stage::Game(game) => Ok(game),
+            Stage::Lobby(_) => Err(not_a_game(session_id)),
+        }
+    }
+
+    pub fn game_mut(&mut self, session_id: &str) -> Result<&mut L::Game, GameCoreError> {
+        match &mut self.session_mut(session_id)?.stage {
+            Stage::Game(game) => Ok(game),
+            Stage::Lobby(_) => Err(not_a_game(session_id)),
+        }
+    }
+
+    /// Remove the game, e.g. when its channel is deleted, whoever asks.
+    pub fn end_game(&mut self, session_id: &str) -> Result<(), GameCoreError> {
+        self.game(session_id)?;
+
+        if let Some(Session {
+            stage: Stage::Game(game),
+            ..
+        }) = self.sessions.remove(session_id)
+        {
+            game.end();
+        }
+
+        Ok(())
+    }
+
+    /// End every finished game. Return their session IDs, sorted.
+    pub fn remove_finished_games(&mut self) -> Vec<SessionId> {
+        let mut finished: Vec<_> = self
+            .sessions
+            .iter()
+            .filter(
+                |(_, session)| matches!(&session.stage, Stage::Game(game) if game.is_finished()),
+            )
+            .map(|(session_id, _)| session_id.clone())
+            .collect();
+        finished.sort();
+
+        for session_id in finished.iter() {
+            self.end_game(session_id).unwrap();
+        }
+
+        finished
+    }
+
+    pub fn owner(&self, session_id: &str) -> Result<&str, GameCoreError> {
+        Ok(&self.session(session_id)?.owner)
+    }
+
+    pub fn stage(&self, session_id: &str) -> Option<SessionStage> {
+        self.sessions
+            .get(session_id)
+            .map(|session| match session.stage {
+                Stage::Lobby(_) => SessionStage::Lobby,
+                Stage::Game(_) => SessionStage::Game,
+            })
+    }
+
+    /// Sorted.
+    pub fn session_ids(&self) -> Vec<SessionId> {
+        let mut session_ids: Vec<_> = self.sessions.keys().cloned().collect();
+        session_ids.sort();
+
+        session_ids
+    }
+
+    /// Lobbies and games the player is in, sorted by session ID.
+    pub fn sessions_of_player(&self, player_name: &str) -> Vec<SessionId> {
+        let mut session_ids: Vec<_> = self
+            .sessions
+            .iter()
+            .filter(|(_, session)| {
+                let players = match &session.stage {
+                    Stage::Lobby(lobby) => lobby.players(),
+                    Stage::Game(game) => game.players(),
+                };
+
+                players.iter().any(|player| player == player_name)
+            })
+            .map(|(session_id, _)| session_id.clone())
+            .collect();
+        session_ids.sort();
+
+        session_ids
+    }
+
+    fn session(&self, session_id: &str) -> Result<&Session<L>, GameCoreError> {
+        self.sessions
+            .get(session_id)
+            .ok_or_else(|| GameCoreError::SessionDoesNotExist {
+                session_id: session_id.to_owned(),
+            })
+    }
+
+    fn session_mut(&mut self, session_id: &str) -> Result<&mut Session<L>, GameCoreError> {
+        self.sessions
+            .get_mut(session_id)
+            .ok_or_else(|| GameCoreError::SessionDoesNotExist {
+                session_id: session_id.to_owned(),
+            })
+    }
+
+    fn check_owner(&self, session_id: &str, requester: &str) -> Result<(), GameCoreError> {
+        if self.owner(session_id)? == requester {
+            Ok(())
+        } else {
+            Err(GameCoreError::NotSessionOwner {
+                session_id: session_id.to_owned(),
+                player_name: requester.to_owned(),
+            })
+        }
+    }
+}
+
+fn not_a_lobby(session_id: &str) -> GameCoreError {
+    GameCoreError::NotALobby {
+        session_id: session_id.to_owned(),
+    }
+}
+
+fn not_a_game(session_id: &str) -> GameCoreError {
+    GameCoreError::NotAGame {
+        session_id: session_id.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill_lobby(lobby: &mut GenericDriverBuilder, players: &[&str]) {
+        for player in players {
+            lobby.add_player(*player).unwrap();
+        }
+        lobby.add_new_questions((0..5).map(|i| format!("Why {}, _?", i)).collect::<Vec<_>>());
+        lobby.add_new_answers((0..60).map(|i| i.to_string()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn lobby_ownership() {
+        let mut registry: SessionRegistry = SessionRegistry::new();
+        registry.create_lobby("lobby-1", "alex").unwrap();
+
+        assert_eq!(
+            registry.create_lobby("lobby-1", "sam").err(),
+            Some(GameCoreError::SessionAlreadyExists {
+                session_id: "lobby-1".to_owned()
+            })
+        );
+        assert_eq!(
+            registry.create_lobby("lobby-2", "alex").err(),
+            Some(GameCoreError::OwnerAlreadyHasLobby {
+                owner: "alex".to_owned(),
+                session_id: "lobby-1".to_owned()
+            })
+        );
+        assert_eq!(registry.lobby_of_owner("alex"), Some("lobby-1"));
+        assert_eq!(
+            registry.close_lobby("lobby-1", "sam"),
+            Err(GameCoreError::NotSessionOwner {
+                session_id: "lobby-1".to_owned(),
+                player_name: "sam".to_owned()
+            })
+        );
+
+        registry.close_lobby("lobby-1", "alex").unwrap();
+        assert_eq!(registry.stage("lobby-1"), None);
+        registry.create_lobby("lobby-2", "alex").unwrap();
+    }
+
+    #[test]
+    fn lobby_to_game() {
+        let mut registry: SessionRegistry = SessionRegistry::new();
+        registry.create_lobby("lobby", "alex").unwrap();
+        registry.create_lobby("busy", "kim").unwrap();
+
+        assert!(registry.start_game("lobby", "alex", "thread").is_err());
+        assert_eq!(registry.stage("lobby"), Some(SessionStage::Lobby));
+
+        fill_lobby(
+            registry.lobby_mut("lobby").unwrap(),
+            &["alex", "sam", "kim"],
+        );
+        assert!(registry.start_game("lobby", "sam", "thread").is_err());
+        assert_eq!(
+            registry.start_game("lobby", "alex", "busy").err(),
+            Some(GameCoreError::SessionAlreadyExists {
+                session_id: "busy".to_owned()
+            })
+        );
+        registry
+            .start_game("lobby", "alex", "thread")
+            .unwrap()
+            .start_round();
+
+        assert_eq!(registry.stage("lobby"), None);
+        assert_eq!(registry.stage("thread"), Some(SessionStage::Game));
+        assert_eq!(registry.owner("thread"), Ok("alex"));
+        assert_eq!(registry.game("thread").unwrap().round(), 1);
+        assert!(matches!(
+            registry.lobby("thread"),
+            Err(GameCoreError::NotALobby { .. })
+        ));
+        assert!(matches!(
+            registry.game("busy"),
+            Err(GameCoreError::NotAGame { .. })
+        ));
+        assert_eq!(registry.lobby_of_owner("alex"), None);
+        assert_eq!(registry.session_ids(), vec!["busy", "thread"]);
+    }
+
+    #[test]
+    fn lookup_by_player() {
+        let mut registry: SessionRegistry = SessionRegistry::new();
+        fill_lobby(
+            registry.create_lobby("a", "alex").unwrap(),
+            &["alex", "sam", "kim"],
+        );
+        fill_lobby(registry.create_lobby("b", "robin").unwrap(), &["sam"]);
+        registry.start_game("a", "alex", "a").unwrap();
+
+        assert_eq!(registry.sessions_of_player("sam"), vec!["a", "b"]);
+        assert_eq!(registry.sessions_of_player("kim"), vec!["a"]);
+        assert!(registry.sessions_of_player("nobody").is_empty());
+    }
+
+    #[test]
+    fn clean_up_finished_games() {
+        let mut registry: SessionRegistry = SessionRegistry::new();
+        for (session_id, owner) in [("a", "alex"), ("b", "sam")] {
+            let lobby = registry.create_lobby(session_id, owner).unwrap();
+            fill_lobby(lobby, &["alex", "sam", "kim"]);
+            lobby.set_win_target(1).unwrap();
+            registry.start_game(session_id, owner, session_id).unwrap();
+        }
+
+        let game = registry.game_mut("b").unwrap();
+        let judge = game.start_round().judge;
+        let players: Vec<_> = game
+            .ordered_players()
+            .into_iter()
+            .filter(|player| *player != judge)
+            .collect();
+        for player in players.iter() {
+            game.submit_answers(player, vec![0usize]).unwrap();
+        }
+        game.end_round(&players[0]).unwrap();
+
+        assert_eq!(registry.remove_finished_games(), vec!["b"]);
+        assert_eq!(registry.session_ids(), vec!["a"]);
+        registry.end_game("a").unwrap();
+        assert!(registry.end_game("a").is_err());
+    }
+}
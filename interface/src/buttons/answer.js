@@ -20,7 +20,10 @@ module.exports = {
             metadata.playerAnswerInteractions[userMention] = interaction;
         }
 
-        const hand = metadata.playerHands[userMention];
+        const hand = interaction.client.gameInstanceManager
+            .getDriver(channelId)
+            .viewFor(userMention)
+            .hand.map((card) => card.content);
         const handInteraction = metadata.playerHandInteractions[userMention];
         const currentSelectionIndices = metadata.playerSelections[userMention];
 
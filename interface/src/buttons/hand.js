@@ -54,22 +54,25 @@ module.exports = {
         const user = interaction.user;
         const userMention = user.toString();
 
-        const metadata =
-            interaction.client.gameInstanceManager.getDriverMetadata(channelId);
+        const gameInstanceManager = interaction.client.gameInstanceManager;
+        const metadata = gameInstanceManager.getDriverMetadata(channelId);
 
-        if (userMention === metadata.judge) {
-            throw "You are the Judge! Sit back and relax!";
+        let view;
+        try {
+            view = gameInstanceManager.getDriver(channelId).viewFor(userMention);
+        } catch (_) {
+            throw "You are not part of this game!";
         }
 
-        if (!(userMention in metadata.playerHands)) {
-            throw "You are not part of this game!";
+        if (view.isJudge) {
+            throw "You are the Judge! Sit back and relax!";
         }
 
-        if (metadata.successfullySubmittedPlayers.has(userMention)) {
+        if (view.submittedCardIds !== null) {
             throw "You have already submitted an answer!";
         }
 
-        const hand = metadata.playerHands[userMention];
+        const hand = view.hand.map((card) => card.content);
         metadata.playerSelections[userMention] = [];
         metadata.playerHandInteractions[userMention] = interaction;
 
@@ -23,7 +23,7 @@ module.exports = {
             return;
         }
 
-        builder.addPlayer(userMention);
+        builder.addPlayer(userMention, user.username);
 
         await interaction.reply({
             content: "Joined successfully!",
@@ -21,7 +21,10 @@ module.exports = {
             metadata.redrawPlayers.delete(userMention);
         }
 
-        const hand = metadata.playerHands[userMention];
+        const hand = interaction.client.gameInstanceManager
+            .getDriver(channelId)
+            .viewFor(userMention)
+            .hand.map((card) => card.content);
         const handInteraction =
             metadata.playerHandInteractions[userMention];
         const currentSelectionIndices =
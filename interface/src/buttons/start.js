@@ -34,7 +34,7 @@ function formatQuestion(question) {
 
 async function startRound(driver, channel, metadata) {
     const players = driver.orderedPlayers();
-    const { judge, question, playerHands } = driver.startRound();
+    const { judge, question } = driver.startRound();
 
     await channel.send(formatPlayers(players, judge));
 
@@ -43,7 +43,6 @@ async function startRound(driver, channel, metadata) {
     const numBlanks = question.split("_").length - 1;
 
     metadata.judge = judge;
-    metadata.playerHands = playerHands;
     metadata.numBlanks = numBlanks;
     metadata.playerSelections = {};
     metadata.roundStartMessage = roundStartMessage;
@@ -51,7 +50,6 @@ async function startRound(driver, channel, metadata) {
     metadata.redrawPlayers = new Set();
     metadata.playerAnswerInteractions = {};
     metadata.playerSubmitInteractions = {};
-    metadata.successfullySubmittedPlayers = new Set();
     metadata.submitResult = [];
 }
 
@@ -94,8 +94,6 @@ module.exports = {
         };
         await interaction.channel.send(option);
 
-        metadata.successfullySubmittedPlayers.add(userMention);
-
         if (submitResult !== null) {
             // Redraw for players who opted in
             const redrawPlayers = Array.from(metadata.redrawPlayers);
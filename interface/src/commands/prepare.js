@@ -43,7 +43,7 @@ module.exports = {
 
         const builder = gameInstanceManager.createBuilder(ownerId);
         const metadata = gameInstanceManager.getBuilderMetadata(ownerId);
-        builder.addPlayer(ownerMention);
+        builder.addPlayer(ownerMention, owner.username);
         builder.addNewQuestions(AssetLoader.LoadQuestionsJson("default"));
         builder.addNewAnswers(AssetLoader.LoadAnswersJson("default"));
 
@@ -9,8 +9,8 @@ module.exports = {
         ),
     async execute(interaction) {
         const userId = interaction.user.id;
-        const metadata =
-            interaction.client.gameInstanceManager.getBuilderMetadata(userId);
+        const builder =
+            interaction.client.gameInstanceManager.getBuilder(userId);
 
         const winTarget = interaction.options.getInteger("value");
 
@@ -18,7 +18,7 @@ module.exports = {
             throw `Cannot set win target to ${winTarget}. It has to be at least 1.`;
         }
 
-        metadata.winTarget = winTarget;
+        builder.setWinTarget(winTarget);
 
         await interaction.reply(`Successfully set win target to ${winTarget}`);
     },
@@ -7,4 +7,20 @@ class LogDisplayError extends Error {
     }
 }
 
-module.exports = { LogDisplayError };
+/**
+ * Whether `error` was thrown by the game core, optionally with the given code
+ * (e.g. "notEnoughPlayers").
+ *
+ * @param {unknown} error
+ * @param {import("bot-against-humanity-core").GameCoreErrorCode} [code]
+ * @returns {error is import("bot-against-humanity-core").GameCoreError}
+ */
+function isGameCoreError(error, code = undefined) {
+    return (
+        error instanceof Error &&
+        error.name === "GameCoreError" &&
+        (code === undefined || error.code === code)
+    );
+}
+
+module.exports = { LogDisplayError, isGameCoreError };
@@ -1,5 +1,5 @@
-const { WasmDriverBuilder } = require("bot-against-humanity-core");
-const { LogDisplayError } = require("./error");
+const { WasmSessionRegistry } = require("bot-against-humanity-core");
+const { LogDisplayError, isGameCoreError } = require("./error");
 
 const errors = {
     noRegisteredUsername: (userId) =>
@@ -32,95 +32,131 @@ const errors = {
         ),
 };
 
+/**
+ * Rethrow registry errors as the messages players are used to.
+ *
+ * @param {() => T} action
+ * @param {Record<string, () => Error>} replacements by `GameCoreError` code
+ * @returns {T}
+ * @template T
+ */
+function withFriendlyErrors(action, replacements) {
+    try {
+        return action();
+    } catch (error) {
+        if (isGameCoreError(error) && error.code in replacements) {
+            throw replacements[error.code]();
+        }
+        throw error;
+    }
+}
+
+/**
+ * Thin wrapper around the core's session registry: lobbies are keyed by their
+ * owner's user ID, games by their channel ID. Only Discord-specific metadata
+ * is kept here.
+ */
 class GameInstanceManager {
     constructor() {
-        this.ownerIdToBuilder = new Map();
-        this.channelIdToDriver = new Map();
-        this.channelIdToSubmittedAnswers = new Map();
+        this.sessions = new WasmSessionRegistry();
+        this.ownerIdToBuilderMetadata = new Map();
+        this.channelIdToDriverMetadata = new Map();
     }
 
     createBuilder(ownerId) {
-        if (this.ownerIdToBuilder.has(ownerId)) {
-            throw errors.gameInstanceAlreadyBeingBuilt();
-        }
-
-        const builder = new WasmDriverBuilder();
+        const builder = withFriendlyErrors(
+            () => this.sessions.createLobby(ownerId, ownerId),
+            {
+                sessionAlreadyExists: () =>
+                    errors.gameInstanceAlreadyBeingBuilt(ownerId),
+                ownerAlreadyHasLobby: () =>
+                    errors.gameInstanceAlreadyBeingBuilt(ownerId),
+            },
+        );
+        // Round-robin Judges with the bot's big hands.
+        builder.usePreset("Classic");
         builder.setHandSize(15);
 
-        this.ownerIdToBuilder.set(ownerId, {
-            builder,
-            metadata: { winTarget: 5 },
-        });
-        return this.ownerIdToBuilder.get(ownerId).builder;
+        this.ownerIdToBuilderMetadata.set(ownerId, {});
+        return builder;
     }
 
     getBuilder(ownerId) {
-        if (!this.ownerIdToBuilder.has(ownerId)) {
-            throw errors.noGameInstanceBeingBuilt();
-        }
-
-        return this.ownerIdToBuilder.get(ownerId).builder;
+        return withFriendlyErrors(() => this.sessions.lobby(ownerId), {
+            sessionDoesNotExist: () => errors.noGameInstanceBeingBuilt(ownerId),
+            notALobby: () => errors.noGameInstanceBeingBuilt(ownerId),
+        });
     }
 
     getBuilderMetadata(ownerId) {
-        if (!this.ownerIdToBuilder.has(ownerId)) {
-            throw errors.noGameInstanceBeingBuilt();
-        }
+        this.getBuilder(ownerId);
 
-        return this.ownerIdToBuilder.get(ownerId).metadata;
+        return this.ownerIdToBuilderMetadata.get(ownerId);
     }
 
     removeBuilder(ownerId) {
-        if (!this.ownerIdToBuilder.has(ownerId)) {
-            throw errors.noGameInstanceBeingBuilt();
-        }
+        withFriendlyErrors(() => this.sessions.closeLobby(ownerId, ownerId), {
+            sessionDoesNotExist: () => errors.noGameInstanceBeingBuilt(ownerId),
+            notALobby: () => errors.noGameInstanceBeingBuilt(ownerId),
+        });
 
-        this.ownerIdToBuilder.delete(ownerId);
+        this.ownerIdToBuilderMetadata.delete(ownerId);
     }
 
     buildDriver(ownerId, channelId) {
-        if (!this.ownerIdToBuilder.has(ownerId)) {
-            throw errors.noGameInstanceBeingBuilt();
-        }
-
-        if (this.channelIdToDriver.has(channelId)) {
-            throw errors.threadAlreadyHasGameInstance();
-        }
-
-        const builderMetadata = this.getBuilderMetadata(ownerId);
-        const driver = this.getBuilder(ownerId).build();
-        this.ownerIdToBuilder.delete(ownerId);
-
-        this.channelIdToDriver.set(channelId, {
-            driver,
-            metadata: { winTarget: builderMetadata.winTarget },
+        const driver = withFriendlyErrors(
+            () => this.sessions.startGame(ownerId, ownerId, channelId),
+            {
+                sessionDoesNotExist: () =>
+                    errors.noGameInstanceBeingBuilt(ownerId),
+                notALobby: () => errors.noGameInstanceBeingBuilt(ownerId),
+                sessionAlreadyExists: () =>
+                    errors.threadAlreadyHasGameInstance(channelId),
+            },
+        );
+        this.ownerIdToBuilderMetadata.delete(ownerId);
+
+        this.channelIdToDriverMetadata.set(channelId, {
+            winTarget: driver.config().winTarget,
         });
 
         return driver;
     }
 
     getDriver(channelId) {
-        if (!this.channelIdToDriver.has(channelId)) {
-            throw errors.noRunningGameInstance(channelId);
-        }
-
-        return this.channelIdToDriver.get(channelId).driver;
+        return withFriendlyErrors(() => this.sessions.game(channelId), {
+            sessionDoesNotExist: () => errors.noRunningGameInstance(channelId),
+            notAGame: () => errors.noRunningGameInstance(channelId),
+        });
     }
 
     getDriverMetadata(channelId) {
-        if (!this.channelIdToDriver.has(channelId)) {
-            throw errors.noRunningGameInstance(channelId);
-        }
+        this.getDriver(channelId);
 
-        return this.channelIdToDriver.get(channelId).metadata;
+        return this.channelIdToDriverMetadata.get(channelId);
     }
 
     removeDriver(channelId) {
-        if (!this.channelIdToDriver.has(channelId)) {
-            throw errors.noRunningGameInstance(channelId);
-        }
+        withFriendlyErrors(() => this.sessions.endGame(channelId), {
+            sessionDoesNotExist: () => errors.noRunningGameInstance(channelId),
+            notAGame: () => errors.noRunningGameInstance(channelId),
+        });
+
+        this.channelIdToDriverMetadata.delete(channelId);
+    }
 
-        this.channelIdToDriver.delete(channelId);
+    /**
+     * End every game that is over or has a winner.
+     *
+     * @returns {string[]} their channel IDs
+     */
+    removeFinishedDrivers() {
+        const channelIds = this.sessions.removeFinishedGames();
+        channelIds.forEach((channelId) =>
+            this.channelIdToDriverMetadata.delete(channelId),
+        );
+
+        return channelIds;
     }
 }
 
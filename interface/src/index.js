@@ -4,7 +4,8 @@ const path = require("node:path");
 const { Client, Collection, Intents } = require("discord.js");
 const { BOT_TOKEN: token } = process.env;
 const GameInstanceManager = require("./game_instance_manager");
-const { LogDisplayError } = require("./error");
+const { LogDisplayError, isGameCoreError } = require("./error");
+const { translate } = require("bot-against-humanity-core");
 
 async function handleError(error, interaction) {
     const options = { content: "Error", ephemeral: true };
@@ -16,6 +17,14 @@ async function handleError(error, interaction) {
     if (error instanceof LogDisplayError) {
         console.error(error);
         options.content = error.displayMsg;
+    } else if (isGameCoreError(error)) {
+        console.error(`[${error.code}]`, error.details);
+        // Unknown locales fall back to English
+        options.content = translate(
+            interaction.guildLocale ?? "en",
+            error.key,
+            error.args,
+        );
     } else if (error instanceof Error) {
         console.error(error);
         options.content = `Error: ${error.message}`;
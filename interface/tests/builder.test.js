@@ -1,4 +1,7 @@
-const { WasmDriverBuilder } = require("bot-against-humanity-core");
+const {
+    WasmDriverBuilder,
+    translate,
+} = require("bot-against-humanity-core");
 const {
     generateMockAnswers,
     generateMockPlayers,
@@ -36,6 +39,39 @@ describe("WasmDriverBuilder", () => {
             expect(() => builder.addPlayer("A")).not.toThrow();
             expect(() => builder.addPlayer("A")).toThrow();
         });
+
+        test("reports a duplicate player with a code and details.", () => {
+            builder.addPlayer("1234", "Alex");
+
+            let error;
+            try {
+                builder.addPlayer("1234", "Sam");
+            } catch (e) {
+                error = e;
+            }
+
+            expect(error).toBeInstanceOf(Error);
+            expect(error.name).toBe("GameCoreError");
+            expect(error.code).toBe("playerAlreadyExists");
+            expect(error.details).toEqual({ name: "1234", displayName: "Alex" });
+        });
+
+        test("reports errors as localisable message keys.", () => {
+            builder.addPlayer("1234", "Alex");
+
+            let error;
+            try {
+                builder.addPlayer("1234");
+            } catch (e) {
+                error = e;
+            }
+
+            expect(error.key).toBe("error.playerAlreadyExists");
+            expect(translate("es-ES", error.key, error.args)).toBe(
+                "Ya existe un jugador con el nombre Alex [1234].",
+            );
+            expect(translate("fr", error.key, error.args)).toBe(error.message);
+        });
     });
 
     describe("removing players", () => {
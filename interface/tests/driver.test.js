@@ -37,17 +37,13 @@ describe("WasmDriver", () => {
     });
 
     test("can run a game.", () => {
-        const { judge, question, playerHands } = driver.startRound();
+        const { judge, question } = driver.startRound();
 
         expect(players).toContain(judge);
         expect(questions).toContain(question.split(" ")[0]);
 
-        expect(Object.keys(playerHands).length).toBe(NUM_PLAYERS);
-        expect(Object.keys(playerHands)).toEqual(
-            expect.arrayContaining(players),
-        );
-
-        for (const hand of Object.values(playerHands)) {
+        for (const player of players) {
+            const hand = driver.viewFor(player).hand.map((card) => card.content);
             expect(hand.length).toBe(HAND_SIZE);
             expect(answers).toEqual(expect.arrayContaining(hand));
         }
@@ -104,4 +100,27 @@ describe("WasmDriver", () => {
 
         expect(() => driver.endGame()).not.toThrow();
     });
+
+    test("notifies listeners.", () => {
+        const events = [];
+        const listenerId = driver.subscribe((event) => events.push(event));
+
+        const { judge } = driver.startRound();
+        const nonJudgePlayers = players.filter((p) => p !== judge);
+        nonJudgePlayers.forEach((p) => driver.submitAnswers(p, [0]));
+        driver.endRound(nonJudgePlayers[0]);
+
+        expect(events.map((e) => e.type)).toEqual([
+            "roundStarted",
+            ...nonJudgePlayers.map(() => "playerSubmitted"),
+            "submissionsClosed",
+            "judgeChose",
+            "scoresChanged",
+        ]);
+        expect(events[0]).toMatchObject({ round: 1, judge });
+
+        expect(driver.unsubscribe(listenerId)).toBe(true);
+        driver.endGame();
+        expect(events[events.length - 1].type).toBe("scoresChanged");
+    });
 });
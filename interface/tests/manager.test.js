@@ -36,7 +36,9 @@ describe("GameInstanceManager", () => {
 
         expect(builder2).toBeInstanceOf(WasmDriverBuilder);
 
-        expect(builder1).toBe(builder2); // Same object
+        // Handles to the same lobby
+        builder1.addPlayer("P");
+        expect(() => builder2.addPlayer("P")).toThrow();
     });
 
     test("can remove a builder", () => {
@@ -50,7 +52,9 @@ describe("GameInstanceManager", () => {
         const builderA = gameInstanceManager.createBuilder("A");
         const builderB = gameInstanceManager.createBuilder("B");
 
-        expect(builderA).not.toBe(builderB); // Different objects
+        // Different lobbies
+        builderA.addPlayer("P");
+        expect(() => builderB.addPlayer("P")).not.toThrow();
     });
 
     test("cannot build a driver before the requirements are met.", () => {
@@ -94,7 +98,9 @@ describe("GameInstanceManager", () => {
             const driver1 = gameInstanceManager.buildDriver("A", "channelA");
             const driver2 = gameInstanceManager.getDriver("channelA");
 
-            expect(driver1).toBe(driver2);
+            // Handles to the same game
+            driver1.startRound();
+            expect(driver2.publicView().round).toBe(1);
         });
 
         test("cannot get a driver before building it.", () => {
@@ -109,6 +115,15 @@ describe("GameInstanceManager", () => {
             ).not.toThrow();
         });
 
+        test("does not remove drivers of running games as finished.", () => {
+            gameInstanceManager.buildDriver("A", "channelA");
+
+            expect(gameInstanceManager.removeFinishedDrivers()).toEqual([]);
+            expect(() =>
+                gameInstanceManager.getDriver("channelA"),
+            ).not.toThrow();
+        });
+
         test("cannot remove a driver before building it.", () => {
             expect(() =>
                 gameInstanceManager.removeDriver("channelA"),
@@ -0,0 +1,142 @@
+//! Drives one game at a time over JSON-RPC 2.0 on stdin and stdout, one message per line, so
+//! bots and test harnesses in any language can play real games as a subprocess.
+//!
+//! - Methods are `builder.<name>` and `driver.<name>`, named like the methods of the WASM
+//!   driver, with named parameters, e.g.
+//!   `{"jsonrpc":"2.0","id":1,"method":"builder.addPlayer","params":{"player":"A"}}`.
+//! - `builder.build` replaces the game being played; `driver.endGame` ends it.
+//! - What happens in the game is sent as `event` notifications (`params` is a `GameEvent`),
+//!   before the response to the call that caused it.
+//! - A `GameCoreError` is an error with code -32000 and `data` `{ code, details }`.
+//!
+//! With `builder.setSeed`, the same input always gives the same output.
+
+mod methods;
+mod rpc;
+
+use std::io::{self, BufRead, Write};
+
+use methods::Game;
+
+fn main() -> io::Result<()> {
+    let mut game = Game::default();
+    let mut stdout = io::stdout().lock();
+
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        for output in rpc::handle_line(&mut game, &line) {
+            writeln!(stdout, "{}", output)?;
+        }
+        stdout.flush()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::Path};
+
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// Run `transcripts/<name>.in.jsonl` and compare the output with `<name>.out.jsonl`.
+    /// Set `UPDATE_TRANSCRIPTS=1` to rewrite the expected output instead.
+    fn check_transcript(name: &str) {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("transcripts");
+        let input = fs::read_to_string(directory.join(format!("{}.in.jsonl", name))).unwrap();
+
+        let mut game = Game::default();
+        let output: String = input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .flat_map(|line| rpc::handle_line(&mut game, line))
+            .map(|line| line + "\n")
+            .collect();
+
+        let expected_path = directory.join(format!("{}.out.jsonl", name));
+        if env::var_os("UPDATE_TRANSCRIPTS").is_some() {
+            fs::write(&expected_path, &output).unwrap();
+        }
+        let expected = fs::read_to_string(expected_path).unwrap();
+
+        for (i, (line, expected_line)) in output.lines().zip(expected.lines()).enumerate() {
+            assert_eq!(line, expected_line, "line {} of {} differs", i + 1, name);
+        }
+        assert_eq!(output.lines().count(), expected.lines().count());
+    }
+
+    fn call(game: &mut Game, request: Value) -> Vec<Value> {
+        rpc::handle_line(game, &request.to_string())
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn seeded_game() {
+        check_transcript("seeded_game");
+    }
+
+    #[test]
+    fn protocol_errors() {
+        let mut game = Game::default();
+
+        let output = rpc::handle_line(&mut game, "{");
+        assert_eq!(output.len(), 1);
+        let response: Value = serde_json::from_str(&output[0]).unwrap();
+        assert_eq!(response["error"]["code"], -32700);
+        assert_eq!(response["id"], Value::Null);
+
+        let response = &call(&mut game, json!({ "id": 1, "method": "driver.round" }))[0];
+        assert_eq!(response["error"]["code"], -32600);
+
+        let response = &call(
+            &mut game,
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "builder.fly" }),
+        )[0];
+        assert_eq!(response["error"]["code"], -32601);
+
+        let response = &call(
+            &mut game,
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "builder.setHandSize", "params": {} }),
+        )[0];
+        assert_eq!(response["error"]["code"], -32602);
+
+        let response = &call(
+            &mut game,
+            json!({ "jsonrpc": "2.0", "id": "a", "method": "driver.round" }),
+        )[0];
+        assert_eq!(response["id"], "a");
+        assert_eq!(response["error"]["code"], -32000);
+        assert_eq!(response["error"]["data"]["code"], "gameNotStarted");
+
+        // Notifications get no response.
+        assert!(call(
+            &mut game,
+            json!({ "jsonrpc": "2.0", "method": "builder.addPlayer", "params": { "player": "A" } }),
+        )
+        .is_empty());
+
+        let responses = &call(
+            &mut game,
+            json!([
+                { "jsonrpc": "2.0", "id": 4, "method": "builder.players" },
+                { "jsonrpc": "2.0", "method": "builder.removeAllPlayers" },
+                { "jsonrpc": "2.0", "id": 5, "method": "builder.players" },
+            ]),
+        )[0];
+        assert_eq!(
+            responses,
+            &json!([
+                { "jsonrpc": "2.0", "id": 4, "result": ["A"] },
+                { "jsonrpc": "2.0", "id": 5, "result": [] },
+            ])
+        );
+    }
+}
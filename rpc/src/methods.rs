@@ -0,0 +1,237 @@
+use std::sync::{Arc, Mutex};
+
+use bot_against_humanity_core::{
+    config::{GameConfig, PRESET_NAMES},
+    decks::{parse_csv, parse_json_against_humanity, parse_tsv},
+    drivers::{
+        generic::{GenericDriver, GenericDriverBuilder},
+        GameCoreDriver,
+    },
+    events::GameEvent,
+    game_state::{ExhaustionPolicy, JudgeStrategy},
+    GameCoreError,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+use crate::rpc::RpcError;
+
+/// The builder of the next game and the game being played, if any.
+#[derive(Default)]
+pub struct Game {
+    builder: GenericDriverBuilder,
+    driver: Option<GenericDriver>,
+    /// Filled by the driver's listener, emptied by `take_events()`.
+    events: Arc<Mutex<Vec<GameEvent>>>,
+}
+
+impl Game {
+    /// Events emitted since the last call.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut *self.events.lock().unwrap())
+    }
+
+    /// `method` is `builder.<name>` or `driver.<name>`, with the names of the WASM driver.
+    pub fn call(&mut self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        match method.split_once('.') {
+            Some(("builder", name)) => self.call_builder(name, params),
+            Some(("driver", name)) => self.call_driver(name, params),
+            _ => Err(RpcError::method_not_found(method)),
+        }
+    }
+
+    fn call_builder(&mut self, name: &str, params: &Value) -> Result<Value, RpcError> {
+        let builder = &mut self.builder;
+
+        match name {
+            "presetNames" => ok(PRESET_NAMES),
+            "usePreset" => {
+                let name: String = arg(params, "name")?;
+                let config =
+                    GameConfig::preset(&name).ok_or_else(|| GameCoreError::InvalidConfig {
+                        reason: format!("There is no preset named {}.", name),
+                    })?;
+                builder.set_config(config)?;
+                done()
+            }
+            "config" => ok(builder.config()),
+            "setConfig" => {
+                builder.set_config(arg(params, "config")?)?;
+                done()
+            }
+            "setHandSize" => {
+                builder.set_hand_size(arg(params, "handSize")?)?;
+                done()
+            }
+            "setWinTarget" => {
+                builder.set_win_target(arg(params, "winTarget")?)?;
+                done()
+            }
+            "setJudgeStrategy" => {
+                let strategy: JudgeStrategy = arg(params, "strategy")?;
+                builder.set_judge_strategy(strategy);
+                done()
+            }
+            "setAllowRedraws" => {
+                builder.set_allow_redraws(arg(params, "allowRedraws")?);
+                done()
+            }
+            "setPlayerBounds" => {
+                builder
+                    .set_player_bounds(arg(params, "minPlayers")?, arg(params, "maxPlayers")?)?;
+                done()
+            }
+            "setExhaustionPolicy" => {
+                let policy: ExhaustionPolicy = arg(params, "policy")?;
+                builder.set_exhaustion_policy(policy);
+                done()
+            }
+            "setSeed" => {
+                builder.set_seed(arg(params, "seed")?);
+                done()
+            }
+            "addPlayer" => {
+                let player: String = arg(params, "player")?;
+                match arg::<Option<String>>(params, "displayName")? {
+                    Some(display_name) => builder.add_player_with_name(player, display_name)?,
+                    None => builder.add_player(player)?,
+                }
+                done()
+            }
+            "renamePlayer" => {
+                let player: String = arg(params, "player")?;
+                builder.rename_player(&player, arg::<String>(params, "displayName")?)?;
+                done()
+            }
+            "removePlayer" => {
+                builder.remove_player(arg::<String>(params, "player")?)?;
+                done()
+            }
+            "removeAllPlayers" => {
+                builder.remove_all_players();
+                done()
+            }
+            "players" => ok(builder.players()),
+            "displayName" => ok(builder.display_name(&arg::<String>(params, "player")?)),
+            "addNewQuestions" => {
+                builder.add_new_questions(arg::<Vec<String>>(params, "questions")?);
+                done()
+            }
+            "clearAllQuestions" => {
+                builder.clear_all_questions();
+                done()
+            }
+            "addNewAnswers" => {
+                builder.add_new_answers(arg::<Vec<String>>(params, "answers")?);
+                done()
+            }
+            "clearAllAnswers" => {
+                builder.clear_all_answers();
+                done()
+            }
+            "importDeck" => {
+                let text: String = arg(params, "text")?;
+                let import = match arg::<String>(params, "format")?.as_str() {
+                    "jsonAgainstHumanity" => parse_json_against_humanity(&text)?,
+                    "csv" => parse_csv(&text)?,
+                    "tsv" => parse_tsv(&text)?,
+                    format => {
+                        return Err(RpcError::invalid_params(format!(
+                            "format: expected jsonAgainstHumanity, csv or tsv, not {}",
+                            format
+                        )))
+                    }
+                };
+                builder.add_deck(&import.deck);
+                ok(import.issues)
+            }
+            "ruleNames" => ok(builder.rule_names()),
+            "validate" => ok(builder.validate()),
+            "check" => {
+                let check = builder.check();
+                ok(json!({
+                    "ready": check.is_ready(),
+                    "problems": check.problems.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                    "warnings": check.warnings,
+                }))
+            }
+            "build" => {
+                let mut driver = builder.build()?;
+                if let Some(previous) = self.driver.take() {
+                    previous.end_game();
+                }
+                let events = self.events.clone();
+                driver.subscribe(move |event| events.lock().unwrap().push(event.clone()));
+                self.driver = Some(driver);
+                done()
+            }
+            _ => Err(RpcError::method_not_found(&format!("builder.{}", name))),
+        }
+    }
+
+    fn call_driver(&mut self, name: &str, params: &Value) -> Result<Value, RpcError> {
+        if name == "endGame" {
+            let driver = self.driver.take().ok_or(GameCoreError::GameNotStarted)?;
+            driver.end_game();
+            return done();
+        }
+        let driver = self.driver.as_mut().ok_or(GameCoreError::GameNotStarted)?;
+
+        match name {
+            "orderedPlayers" => ok(driver.ordered_players()),
+            "startRound" => ok(driver.start_round()),
+            "submitAnswers" => ok(driver.submit_answers(
+                arg::<String>(params, "player")?,
+                arg::<Vec<usize>>(params, "indices")?,
+            )?),
+            "submitAnswersByIds" => ok(driver.submit_answers_by_ids(
+                arg::<String>(params, "player")?,
+                arg::<Vec<String>>(params, "cardIds")?,
+            )?),
+            "redrawHands" => {
+                driver.redraw_hands(arg::<Vec<String>>(params, "players")?)?;
+                done()
+            }
+            "endRound" => ok(driver.end_round(arg::<String>(params, "chosenPlayer")?)?),
+            "round" => ok(driver.round()),
+            "currentJudge" => ok(driver.current_judge()),
+            "currentQuestion" => ok(driver.current_question()),
+            "submittedPlayers" => ok(driver.submitted_players()),
+            "pendingPlayers" => ok(driver.pending_players()),
+            "scores" => ok(driver.scores()),
+            "deckSizes" => ok(driver.deck_sizes()),
+            "winner" => ok(driver.winner()),
+            "publicView" => ok(driver.public_view()),
+            "viewFor" => ok(driver.view_for(arg::<String>(params, "player")?)?),
+            "displayName" => ok(driver.display_name(&arg::<String>(params, "player")?)),
+            "displayNames" => ok(driver.display_names()),
+            "renamePlayer" => {
+                let player: String = arg(params, "player")?;
+                driver.rename_player(&player, arg::<String>(params, "displayName")?)?;
+                done()
+            }
+            "config" => ok(driver.config()),
+            "isGameOver" => ok(driver.is_game_over()),
+            "submittedCardIds" => ok(driver.submitted_card_ids()),
+            "ruleNames" => ok(driver.rule_names()),
+            _ => Err(RpcError::method_not_found(&format!("driver.{}", name))),
+        }
+    }
+}
+
+fn done() -> Result<Value, RpcError> {
+    Ok(Value::Null)
+}
+
+/// Through `Value`, so maps come out with sorted keys and transcripts are stable.
+fn ok(result: impl Serialize) -> Result<Value, RpcError> {
+    Ok(serde_json::to_value(result).unwrap())
+}
+
+/// A named parameter. A missing one is read as null, so it can be optional.
+fn arg<T: DeserializeOwned>(params: &Value, name: &str) -> Result<T, RpcError> {
+    let value = params.get(name).cloned().unwrap_or(Value::Null);
+
+    serde_json::from_value(value)
+        .map_err(|err| RpcError::invalid_params(format!("{}: {}", name, err)))
+}
@@ -0,0 +1,118 @@
+use serde_json::{json, Value};
+
+use bot_against_humanity_core::GameCoreError;
+
+use crate::methods::Game;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// A `GameCoreError`; its code and details are in `data`.
+const GAME_ERROR: i64 = -32000;
+
+#[derive(Debug)]
+pub struct RpcError {
+    code: i64,
+    message: String,
+    data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn method_not_found(method: &str) -> Self {
+        Self {
+            code: METHOD_NOT_FOUND,
+            message: format!("There is no method named {}.", method),
+            data: None,
+        }
+    }
+
+    pub fn invalid_params(message: String) -> Self {
+        Self {
+            code: INVALID_PARAMS,
+            message,
+            data: None,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let mut error = json!({ "code": self.code, "message": self.message });
+        if let Some(data) = &self.data {
+            error["data"] = data.clone();
+        }
+
+        error
+    }
+}
+
+impl From<GameCoreError> for RpcError {
+    fn from(error: GameCoreError) -> Self {
+        Self {
+            code: GAME_ERROR,
+            message: error.to_string(),
+            data: Some(json!({ "code": error.code(), "details": error.details() })),
+        }
+    }
+}
+
+/// Return the lines to write for one line of input: the events the calls caused, as
+/// `event` notifications, then the response, if any.
+pub fn handle_line(game: &mut Game, line: &str) -> Vec<String> {
+    let mut output = vec![];
+
+    let response = match serde_json::from_str::<Value>(line) {
+        Ok(Value::Array(batch)) if !batch.is_empty() => {
+            let responses: Vec<Value> = batch
+                .iter()
+                .filter_map(|request| handle_request(game, request, &mut output))
+                .collect();
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        Ok(request) => handle_request(game, &request, &mut output),
+        Err(err) => Some(error_response(
+            Value::Null,
+            RpcError {
+                code: PARSE_ERROR,
+                message: err.to_string(),
+                data: None,
+            },
+        )),
+    };
+
+    output.extend(response.map(|response| response.to_string()));
+    output
+}
+
+/// Return None for notifications, which get no response.
+fn handle_request(game: &mut Game, request: &Value, output: &mut Vec<String>) -> Option<Value> {
+    let id = request.get("id").cloned();
+    let method = request.get("method").and_then(Value::as_str);
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match (request.get("jsonrpc").and_then(Value::as_str), method) {
+        (Some("2.0"), Some(method)) => game.call(method, &params),
+        _ => Err(RpcError {
+            code: INVALID_REQUEST,
+            message: "A request needs \"jsonrpc\": \"2.0\" and a method.".to_owned(),
+            data: None,
+        }),
+    };
+
+    for event in game.take_events() {
+        output.push(json!({ "jsonrpc": "2.0", "method": "event", "params": event }).to_string());
+    }
+
+    let id = match (id, &result) {
+        (Some(id), _) => id,
+        (None, Err(err)) if err.code == INVALID_REQUEST => Value::Null,
+        (None, _) => return None,
+    };
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => error_response(id, err),
+    })
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": error.to_json() })
+}
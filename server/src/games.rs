@@ -0,0 +1,491 @@
+use std::{
+    collections::HashMap,
+    net::{Shutdown, TcpStream},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bot_against_humanity_core::{
+    drivers::{
+        generic::GenericDriverBuilder,
+        views::{PlayerView, PublicView},
+        GameCoreDriver,
+    },
+    events::GameEvent,
+    sessions::{SessionGame, SessionId, SessionRegistry, SessionStage},
+    GameCoreError,
+};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::websocket::{self, Opcode};
+
+/// A failed request: its HTTP status, and a body shaped like the `GameCoreError`s thrown to
+/// JavaScript.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: u16,
+    pub code: String,
+    pub message: String,
+    pub details: Map<String, Value>,
+}
+
+impl ApiError {
+    pub fn new(status: u16, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code: code.to_owned(),
+            message: message.into(),
+            details: Map::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "code": self.code,
+            "message": self.message,
+            "details": self.details,
+        })
+    }
+}
+
+impl From<GameCoreError> for ApiError {
+    fn from(error: GameCoreError) -> Self {
+        let status = match error {
+            GameCoreError::SessionDoesNotExist { .. } => 404,
+            GameCoreError::NotSessionOwner { .. } => 403,
+            _ => 400,
+        };
+
+        Self {
+            status,
+            code: error.code(),
+            message: error.to_string(),
+            details: error.details(),
+        }
+    }
+}
+
+pub type ApiResult = Result<Value, ApiError>;
+
+pub type SubscriberId = u64;
+
+/// Updates are written while every request waits on the lock, so a subscriber who does not read
+/// them for this long is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Sent to a subscribed player after every change to their session.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum Update<'a> {
+    #[serde(rename_all = "camelCase")]
+    Lobby {
+        owner: &'a str,
+        players: Vec<String>,
+        /// { player ID: display name }
+        display_names: HashMap<String, String>,
+    },
+    /// `events` happened since the previous update.
+    Game {
+        events: &'a [GameEvent],
+        view: Box<View>,
+    },
+    /// The lobby was closed or the game ended; the socket is closed next.
+    Closed { events: &'a [GameEvent] },
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum View {
+    Player(PlayerView),
+    Public(PublicView),
+}
+
+struct Subscriber {
+    id: SubscriberId,
+    session_id: SessionId,
+    player: String,
+    socket: TcpStream,
+}
+
+/// Every lobby and game hosted by the server, and the sockets of the players following them.
+///
+/// Each player is given a token on joining, which must come with every request made as them.
+#[derive(Default)]
+pub struct Games {
+    sessions: SessionRegistry,
+    /// (session, player) to the player's token.
+    tokens: HashMap<(SessionId, String), String>,
+    next_session_id: u64,
+    next_subscriber_id: SubscriberId,
+    /// Filled by each game's listener, emptied by `broadcast()`.
+    pending_events: HashMap<SessionId, Arc<Mutex<Vec<GameEvent>>>>,
+    /// (player, combined answer) in the order shown to the Judge, once everyone submitted.
+    submissions: HashMap<SessionId, Vec<(String, String)>>,
+    subscribers: Vec<Subscriber>,
+}
+
+impl Games {
+    /// The owner joins the new lobby. Return `{ "sessionId": string, "token": string }`.
+    pub fn create_session(&mut self, owner: &str, display_name: Option<String>) -> ApiResult {
+        let session_id = (self.next_session_id + 1).to_string();
+        let lobby = self.sessions.create_lobby(session_id.as_str(), owner)?;
+        add_player(lobby, owner, display_name)?;
+        self.next_session_id += 1;
+        let token = self.new_token(&session_id, owner);
+
+        Ok(json!({ "sessionId": session_id, "token": token }))
+    }
+
+    pub fn join(
+        &mut self,
+        session_id: &str,
+        player: &str,
+        display_name: Option<String>,
+    ) -> ApiResult {
+        add_player(self.sessions.lobby_mut(session_id)?, player, display_name)?;
+        let token = self.new_token(session_id, player);
+        self.broadcast(session_id);
+
+        Ok(json!({ "token": token }))
+    }
+
+    pub fn leave(&mut self, session_id: &str, player: &str) -> ApiResult {
+        self.sessions.lobby_mut(session_id)?.remove_player(player)?;
+        self.tokens
+            .remove(&(session_id.to_owned(), player.to_owned()));
+        self.broadcast(session_id);
+
+        Ok(json!({}))
+    }
+
+    /// Only the owner can add cards.
+    pub fn add_cards(
+        &mut self,
+        session_id: &str,
+        requester: &str,
+        questions: Vec<String>,
+        answers: Vec<String>,
+    ) -> ApiResult {
+        self.check_owner(session_id, requester)?;
+        let lobby = self.sessions.lobby_mut(session_id)?;
+        lobby.add_new_questions(questions);
+        lobby.add_new_answers(answers);
+
+        Ok(json!({}))
+    }
+
+    pub fn close(&mut self, session_id: &str, requester: &str) -> ApiResult {
+        self.sessions.close_lobby(session_id, requester)?;
+        self.close_subscriptions(session_id, &[]);
+        self.tokens.retain(|(id, _), _| id != session_id);
+
+        Ok(json!({}))
+    }
+
+    /// Build the game and start its first round.
+    pub fn start(&mut self, session_id: &str, requester: &str) -> ApiResult {
+        let game = self
+            .sessions
+            .start_game(session_id, requester, session_id)?;
+        let events = Arc::new(Mutex::new(vec![]));
+        {
+            let events = events.clone();
+            game.subscribe(move |event| events.lock().unwrap().push(event.clone()));
+        }
+        game.start_round();
+
+        self.pending_events.insert(session_id.to_owned(), events);
+        self.broadcast(session_id);
+
+        Ok(json!({}))
+    }
+
+    pub fn submit(&mut self, session_id: &str, player: &str, card_ids: Vec<String>) -> ApiResult {
+        let game = self.sessions.game_mut(session_id)?;
+        if let Some(submissions) = game.submit_answers_by_ids(player, card_ids)? {
+            self.submissions.insert(session_id.to_owned(), submissions);
+        }
+        self.broadcast(session_id);
+
+        Ok(json!({}))
+    }
+
+    /// `answer` indexes the answers of the public view. The next round starts right away, unless
+    /// the game is finished, in which case it ends.
+    pub fn judge(&mut self, session_id: &str, player: &str, answer: usize) -> ApiResult {
+        let game = self.sessions.game_mut(session_id)?;
+        if game.current_judge().as_deref() != Some(player) {
+            return Err(ApiError::new(
+                403,
+                "notTheJudge",
+                format!("{} is not the Judge of this round.", player),
+            ));
+        }
+        let chosen_player = self
+            .submissions
+            .get(session_id)
+            .and_then(|submissions| submissions.get(answer))
+            .map(|(player, _)| player.clone())
+            .ok_or_else(|| {
+                ApiError::new(
+                    400,
+                    "noSuchAnswer",
+                    format!("There is no answer {} to choose.", answer),
+                )
+            })?;
+
+        game.end_round(chosen_player)?;
+        self.submissions.remove(session_id);
+
+        if game.is_finished() {
+            self.sessions.end_game(session_id)?;
+            let events = self.take_events(session_id);
+            self.pending_events.remove(session_id);
+            self.close_subscriptions(session_id, &events);
+            self.tokens.retain(|(id, _), _| id != session_id);
+        } else {
+            game.start_round();
+            self.broadcast(session_id);
+        }
+
+        Ok(json!({}))
+    }
+
+    /// What `player` may see, or the public view of a game if there is no player.
+    pub fn view(&self, session_id: &str, player: Option<&str>) -> ApiResult {
+        let update = match (self.sessions.stage(session_id), player) {
+            (Some(SessionStage::Game), None) => Update::Game {
+                events: &[],
+                view: Box::new(View::Public(self.sessions.game(session_id)?.public_view())),
+            },
+            _ => self.update_for(session_id, player.unwrap_or_default(), &[])?,
+        };
+
+        Ok(serde_json::to_value(update).unwrap())
+    }
+
+    /// Fail unless `token` is the one `player` was given on joining the session.
+    pub fn authenticate(
+        &self,
+        session_id: &str,
+        player: &str,
+        token: &str,
+    ) -> Result<(), ApiError> {
+        self.sessions.owner(session_id)?;
+        match self.tokens.get(&(session_id.to_owned(), player.to_owned())) {
+            Some(expected) if expected == token => Ok(()),
+            _ => Err(ApiError::new(
+                401,
+                "invalidToken",
+                format!("That is not the token of {} in this session.", player),
+            )),
+        }
+    }
+
+    /// `socket` must have completed the WebSocket handshake. It is sent the current state right
+    /// away.
+    pub fn subscribe(&mut self, session_id: &str, player: &str, socket: TcpStream) -> SubscriberId {
+        self.next_subscriber_id += 1;
+        let mut subscriber = Subscriber {
+            id: self.next_subscriber_id,
+            session_id: session_id.to_owned(),
+            player: player.to_owned(),
+            socket,
+        };
+
+        if subscriber
+            .socket
+            .set_write_timeout(Some(WRITE_TIMEOUT))
+            .is_ok()
+            && self.send_update(&mut subscriber, &[])
+        {
+            self.subscribers.push(subscriber);
+        }
+
+        self.next_subscriber_id
+    }
+
+    /// Close the socket, unless the server already did.
+    pub fn unsubscribe(&mut self, subscriber_id: SubscriberId) {
+        if let Some(i) = self.subscribers.iter().position(|s| s.id == subscriber_id) {
+            let mut subscriber = self.subscribers.remove(i);
+            close_socket(&mut subscriber.socket);
+        }
+    }
+
+    /// Written here so it cannot interleave with an update.
+    pub fn pong(&mut self, subscriber_id: SubscriberId, payload: &[u8]) {
+        if let Some(i) = self.subscribers.iter().position(|s| s.id == subscriber_id) {
+            let socket = &mut self.subscribers[i].socket;
+            if websocket::write_frame(socket, Opcode::Pong, payload).is_err() {
+                drop_socket(&mut self.subscribers.remove(i).socket);
+            }
+        }
+    }
+
+    fn new_token(&mut self, session_id: &str, player: &str) -> String {
+        let token = format!("{:032x}", rand::random::<u128>());
+        self.tokens
+            .insert((session_id.to_owned(), player.to_owned()), token.clone());
+
+        token
+    }
+
+    fn check_owner(&self, session_id: &str, requester: &str) -> Result<(), GameCoreError> {
+        if self.sessions.owner(session_id)? != requester {
+            return Err(GameCoreError::NotSessionOwner {
+                session_id: session_id.to_owned(),
+                player_name: requester.to_owned(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn take_events(&self, session_id: &str) -> Vec<GameEvent> {
+        self.pending_events
+            .get(session_id)
+            .map(|events| std::mem::take(&mut *events.lock().unwrap()))
+            .unwrap_or_default()
+            .into_iter()
+            // It would tell the Judge who wrote what; the answers are in the views anyway.
+            .filter(|event| !matches!(event, GameEvent::SubmissionsClosed { .. }))
+            .collect()
+    }
+
+    /// Send the session's subscribers their update, and drop those who cannot receive it.
+    fn broadcast(&mut self, session_id: &str) {
+        let events = self.take_events(session_id);
+        let mut subscribers = std::mem::take(&mut self.subscribers);
+
+        subscribers.retain_mut(|subscriber| {
+            subscriber.session_id != session_id || self.send_update(subscriber, &events)
+        });
+        self.subscribers = subscribers;
+    }
+
+    fn close_subscriptions(&mut self, session_id: &str, events: &[GameEvent]) {
+        let text = serde_json::to_string(&Update::Closed { events }).unwrap();
+
+        self.subscribers.retain_mut(|subscriber| {
+            if subscriber.session_id != session_id {
+                return true;
+            }
+            let _ = websocket::write_text(&mut subscriber.socket, &text);
+            close_socket(&mut subscriber.socket);
+            false
+        });
+    }
+
+    /// Return false, after closing the socket, if the update cannot be sent.
+    fn send_update(&self, subscriber: &mut Subscriber, events: &[GameEvent]) -> bool {
+        let update = match self.update_for(&subscriber.session_id, &subscriber.player, events) {
+            Ok(update) => update,
+            Err(_) => {
+                close_socket(&mut subscriber.socket);
+                return false;
+            }
+        };
+        let text = serde_json::to_string(&update).unwrap();
+
+        if websocket::write_text(&mut subscriber.socket, &text).is_err() {
+            drop_socket(&mut subscriber.socket);
+            return false;
+        }
+
+        true
+    }
+
+    fn update_for<'a>(
+        &'a self,
+        session_id: &str,
+        player: &str,
+        events: &'a [GameEvent],
+    ) -> Result<Update<'a>, ApiError> {
+        match self.sessions.stage(session_id) {
+            Some(SessionStage::Lobby) => {
+                let lobby = self.sessions.lobby(session_id)?;
+                let players = lobby.players();
+                let display_names = players
+                    .iter()
+                    .filter_map(|p| Some((p.clone(), lobby.display_name(p)?)))
+                    .collect();
+
+                Ok(Update::Lobby {
+                    owner: self.sessions.owner(session_id)?,
+                    players,
+                    display_names,
+                })
+            }
+            Some(SessionStage::Game) => Ok(Update::Game {
+                events,
+                view: Box::new(View::Player(
+                    self.sessions.game(session_id)?.view_for(player)?,
+                )),
+            }),
+            None => Err(GameCoreError::SessionDoesNotExist {
+                session_id: session_id.to_owned(),
+            }
+            .into()),
+        }
+    }
+}
+
+fn add_player(
+    lobby: &mut GenericDriverBuilder,
+    player: &str,
+    display_name: Option<String>,
+) -> Result<(), GameCoreError> {
+    match display_name {
+        Some(display_name) => lobby.add_player_with_name(player, display_name),
+        None => lobby.add_player(player),
+    }
+}
+
+fn close_socket(socket: &mut TcpStream) {
+    let _ = websocket::write_frame(socket, Opcode::Close, &[]);
+    drop_socket(socket);
+}
+
+/// For a socket that failed or timed out: a Close frame would not get through either.
+fn drop_socket(socket: &mut TcpStream) {
+    let _ = socket.shutdown(Shutdown::Both);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{ErrorKind, Write},
+        net::TcpListener,
+        time::Instant,
+    };
+
+    use super::*;
+
+    #[test]
+    fn drop_a_subscriber_who_does_not_read() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let _client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut socket, _) = listener.accept().unwrap();
+
+        // Fill the buffers, as a client that stopped reading would.
+        socket.set_nonblocking(true).unwrap();
+        loop {
+            match socket.write(&[0; 65536]) {
+                Ok(_) => {}
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) => panic!("{}", err),
+            }
+        }
+        socket.set_nonblocking(false).unwrap();
+
+        let mut games = Games::default();
+        games.create_session("A", None).unwrap();
+        let started = Instant::now();
+        games.subscribe("1", "A", socket);
+
+        assert!(games.subscribers.is_empty());
+        assert!(started.elapsed() < WRITE_TIMEOUT * 2);
+    }
+}
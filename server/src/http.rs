@@ -0,0 +1,202 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+};
+
+use serde_json::Value;
+
+/// Larger bodies are rejected; a deck of a few thousand cards fits comfortably.
+const MAX_BODY_SIZE: usize = 1 << 20;
+
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    /// Without the query string, percent-decoded.
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// Keyed by lowercase name.
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Return Ok(None) if the connection was closed before a request line.
+    pub fn read_from(reader: &mut impl BufRead) -> io::Result<Option<Self>> {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line)? == 0 {
+            return Ok(None);
+        }
+
+        let mut parts = request_line.split_whitespace();
+        let (method, target) = match (parts.next(), parts.next()) {
+            (Some(method), Some(target)) => (method.to_owned(), target),
+            _ => return Err(invalid_data("malformed request line")),
+        };
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        let mut headers = HashMap::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_lowercase(), value.trim().to_owned());
+            }
+        }
+
+        let content_length = match headers.get("content-length") {
+            Some(length) => length
+                .parse()
+                .map_err(|_| invalid_data("malformed Content-Length"))?,
+            None => 0,
+        };
+        if content_length > MAX_BODY_SIZE {
+            return Err(invalid_data("body too large"));
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+
+        Ok(Some(Self {
+            method,
+            path: percent_decode(path),
+            query: parse_query(query),
+            headers,
+            body,
+        }))
+    }
+
+    /// Non-empty path segments, e.g. ["sessions", "1", "start"].
+    pub fn segments(&self) -> Vec<&str> {
+        self.path.split('/').filter(|s| !s.is_empty()).collect()
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+
+    /// True for a WebSocket opening handshake.
+    pub fn is_upgrade(&self) -> bool {
+        self.header("upgrade")
+            .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+    }
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    pub fn json(status: u16, body: Value) -> Self {
+        Self { status, body }
+    }
+
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let body = self.body.to_string();
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             Access-Control-Allow-Origin: *\r\n\
+             Connection: close\r\n\r\n{}",
+            self.status,
+            reason_phrase(self.status),
+            body.len(),
+            body
+        )?;
+        writer.flush()
+    }
+}
+
+pub fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        101 => "Switching Protocols",
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        _ => "Internal Server Error",
+    }
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect()
+}
+
+/// Also turns '+' into a space, as in form-encoded query strings.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len()
+                && bytes[i + 1].is_ascii_hexdigit()
+                && bytes[i + 2].is_ascii_hexdigit() =>
+            {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap();
+                decoded.push(u8::from_str_radix(hex, 16).unwrap());
+                i += 3;
+                continue;
+            }
+            b'+' => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn read_a_request() {
+        let raw = "POST /sessions/a%20b/players?player=Mary+Jane&x HTTP/1.1\r\n\
+                   Host: localhost\r\n\
+                   Content-Length: 2\r\n\r\n{}";
+        let request = Request::read_from(&mut Cursor::new(raw)).unwrap().unwrap();
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.segments(), vec!["sessions", "a b", "players"]);
+        assert_eq!(request.query["player"], "Mary Jane");
+        assert_eq!(request.query["x"], "");
+        assert_eq!(request.header("host"), Some("localhost"));
+        assert_eq!(request.body, b"{}");
+        assert!(!request.is_upgrade());
+
+        assert!(Request::read_from(&mut Cursor::new("")).unwrap().is_none());
+        assert!(Request::read_from(&mut Cursor::new("nonsense\r\n\r\n")).is_err());
+    }
+
+    #[test]
+    fn percent_decoding() {
+        assert_eq!(percent_decode("%E4%BD%A0%E5%A5%BD"), "你好");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+}
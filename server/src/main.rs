@@ -0,0 +1,30 @@
+//! Hosts games over HTTP/JSON and pushes each player's updates over WebSockets, e.g. for LAN
+//! games and web clients. See `server` for the API.
+
+mod games;
+mod http;
+mod server;
+mod websocket;
+
+use std::{env, process};
+
+use server::Server;
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+fn main() {
+    let address = env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_ADDRESS.to_owned());
+
+    let server = match Server::bind(&address) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("Cannot listen on {}: {}", address, err);
+            process::exit(1);
+        }
+    };
+    println!("Listening on http://{}", server.local_addr().unwrap());
+
+    server.run();
+}
@@ -0,0 +1,637 @@
+//! The HTTP API. Requests and responses are JSON; errors are `{ code, message, details }`.
+//!
+//! Joining returns a `token` for the player, which every request made as them must carry.
+//!
+//! - `POST /sessions` `{ owner, displayName? }`: create a lobby, return `{ sessionId, token }`
+//! - `GET /sessions/:id?player=&token=`: the lobby, or the game as `player` sees it (the public
+//!   view without `player`)
+//! - `POST /sessions/:id/players` `{ player, displayName? }`: join a lobby, return `{ token }`
+//! - `POST /sessions/:id/leave` `{ player, token }`
+//! - `POST /sessions/:id/cards` `{ requester, token, questions?, answers? }`: owner only
+//! - `POST /sessions/:id/close` `{ requester, token }`: owner only
+//! - `POST /sessions/:id/start` `{ requester, token }`: owner only; the first round starts
+//! - `POST /sessions/:id/submit` `{ player, token, cardIds }`
+//! - `POST /sessions/:id/judge` `{ player, token, answer }`: `answer` indexes
+//!   `view.public.answers`
+//! - `GET /sessions/:id/updates?player=&token=`: a WebSocket pushing `player`'s updates as JSON
+//!   text
+
+use std::{
+    io::{self, BufReader},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{
+    games::{ApiError, ApiResult, Games},
+    http::{Request, Response},
+    websocket::{self, Opcode},
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PlayerBody {
+    #[serde(alias = "owner")]
+    player: String,
+    display_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LeaveBody {
+    player: String,
+    token: String,
+}
+
+#[derive(Deserialize)]
+struct RequesterBody {
+    requester: String,
+    token: String,
+}
+
+#[derive(Deserialize)]
+struct CardsBody {
+    requester: String,
+    token: String,
+    #[serde(default)]
+    questions: Vec<String>,
+    #[serde(default)]
+    answers: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubmitBody {
+    player: String,
+    token: String,
+    card_ids: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct JudgeBody {
+    player: String,
+    token: String,
+    answer: usize,
+}
+
+pub struct Server {
+    listener: TcpListener,
+    games: Arc<Mutex<Games>>,
+}
+
+impl Server {
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(address)?,
+            games: Default::default(),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serve each connection on its own thread, forever.
+    pub fn run(self) {
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    eprintln!("Cannot accept a connection: {}", err);
+                    continue;
+                }
+            };
+            let games = self.games.clone();
+
+            thread::spawn(move || {
+                if let Err(err) = handle_connection(stream, &games) {
+                    eprintln!("Connection failed: {}", err);
+                }
+            });
+        }
+    }
+}
+
+/// One request per connection, or one WebSocket.
+fn handle_connection(mut stream: TcpStream, games: &Mutex<Games>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let request = match Request::read_from(&mut reader)? {
+        Some(request) => request,
+        None => return Ok(()),
+    };
+
+    if request.is_upgrade() {
+        return serve_updates(&request, reader, stream, games);
+    }
+
+    let result = route(&request, &mut games.lock().unwrap());
+    let response = match result {
+        Ok(body) => Response::json(200, body),
+        Err(err) => Response::json(err.status, err.to_json()),
+    };
+
+    response.write_to(&mut stream)
+}
+
+fn route(request: &Request, games: &mut Games) -> ApiResult {
+    let player = request.query.get("player").map(String::as_str);
+    let token = request.query.get("token").map(String::as_str);
+
+    match (request.method.as_str(), request.segments().as_slice()) {
+        ("POST", ["sessions"]) => {
+            let body: PlayerBody = parse_body(request)?;
+            games.create_session(&body.player, body.display_name)
+        }
+        ("GET", ["sessions", id]) => {
+            if let Some(player) = player {
+                games.authenticate(id, player, token.unwrap_or_default())?;
+            }
+            games.view(id, player)
+        }
+        ("POST", ["sessions", id, "players"]) => {
+            let body: PlayerBody = parse_body(request)?;
+            games.join(id, &body.player, body.display_name)
+        }
+        ("POST", ["sessions", id, "leave"]) => {
+            let body: LeaveBody = parse_body(request)?;
+            games.authenticate(id, &body.player, &body.token)?;
+            games.leave(id, &body.player)
+        }
+        ("POST", ["sessions", id, "cards"]) => {
+            let body: CardsBody = parse_body(request)?;
+            games.authenticate(id, &body.requester, &body.token)?;
+            games.add_cards(id, &body.requester, body.questions, body.answers)
+        }
+        ("POST", ["sessions", id, "close"]) => {
+            let body: RequesterBody = parse_body(request)?;
+            games.authenticate(id, &body.requester, &body.token)?;
+            games.close(id, &body.requester)
+        }
+        ("POST", ["sessions", id, "start"]) => {
+            let body: RequesterBody = parse_body(request)?;
+            games.authenticate(id, &body.requester, &body.token)?;
+            games.start(id, &body.requester)
+        }
+        ("POST", ["sessions", id, "submit"]) => {
+            let body: SubmitBody = parse_body(request)?;
+            games.authenticate(id, &body.player, &body.token)?;
+            games.submit(id, &body.player, body.card_ids)
+        }
+        ("POST", ["sessions", id, "judge"]) => {
+            let body: JudgeBody = parse_body(request)?;
+            games.authenticate(id, &body.player, &body.token)?;
+            games.judge(id, &body.player, body.answer)
+        }
+        _ => Err(ApiError::new(
+            404,
+            "notFound",
+            format!("No route for {} {}.", request.method, request.path),
+        )),
+    }
+}
+
+fn parse_body<T: DeserializeOwned>(request: &Request) -> Result<T, ApiError> {
+    serde_json::from_slice(&request.body)
+        .map_err(|err| ApiError::new(400, "badRequest", format!("Invalid body: {}.", err)))
+}
+
+/// Hold the WebSocket open until the client or the server closes it.
+fn serve_updates(
+    request: &Request,
+    mut reader: BufReader<TcpStream>,
+    mut stream: TcpStream,
+    games: &Mutex<Games>,
+) -> io::Result<()> {
+    let target = match (request.segments().as_slice(), request.query.get("player")) {
+        (["sessions", id, "updates"], Some(player)) => Ok((id.to_string(), player.as_str())),
+        _ => Err(ApiError::new(
+            404,
+            "notFound",
+            "Updates are at /sessions/:id/updates?player=&token=",
+        )),
+    };
+    let token = request.query.get("token").map(String::as_str);
+    let key = request.header("sec-websocket-key");
+
+    let subscriber_id = {
+        let mut games = games.lock().unwrap();
+        let checked = target.and_then(|(session_id, player)| {
+            games.authenticate(&session_id, player, token.unwrap_or_default())?;
+            let key =
+                key.ok_or_else(|| ApiError::new(400, "badRequest", "Missing Sec-WebSocket-Key."))?;
+            Ok((session_id, player, key))
+        });
+        let (session_id, player, key) = match checked {
+            Ok(checked) => checked,
+            Err(err) => return Response::json(err.status, err.to_json()).write_to(&mut stream),
+        };
+
+        websocket::write_handshake(&mut stream, key)?;
+        games.subscribe(&session_id, player, stream)
+    };
+
+    // Clients only send control frames worth answering.
+    while let Ok(frame) = websocket::read_frame(&mut reader) {
+        match frame.opcode {
+            Opcode::Close => break,
+            Opcode::Ping => games.lock().unwrap().pong(subscriber_id, &frame.payload),
+            _ => {}
+        }
+    }
+    games.lock().unwrap().unsubscribe(subscriber_id);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        io::{BufRead, Read, Write},
+    };
+
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::websocket::Frame;
+
+    fn start_server() -> SocketAddr {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+
+        address
+    }
+
+    /// Return (status and reason, e.g. "200 OK", body).
+    fn send(address: SocketAddr, method: &str, path: &str, body: Value) -> (String, Value) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        let body = body.to_string();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..response.find("\r\n").unwrap()].to_owned();
+        let body = &response[response.find("\r\n\r\n").unwrap() + 4..];
+
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    fn post(address: SocketAddr, path: &str, body: Value) -> Value {
+        let (status, body) = send(address, "POST", path, body);
+        assert_eq!(status, "200 OK", "{}", body);
+
+        body
+    }
+
+    fn get(address: SocketAddr, path: &str) -> Value {
+        let (status, body) = send(address, "GET", path, Value::Null);
+        assert_eq!(status, "200 OK", "{}", body);
+
+        body
+    }
+
+    struct Updates {
+        reader: BufReader<TcpStream>,
+    }
+
+    impl Updates {
+        fn connect(address: SocketAddr, path: &str) -> Self {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write!(
+                stream,
+                "GET {} HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\n\
+                 Connection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                 Sec-WebSocket-Version: 13\r\n\r\n",
+                path
+            )
+            .unwrap();
+
+            let mut reader = BufReader::new(stream);
+            let mut status_line = String::new();
+            reader.read_line(&mut status_line).unwrap();
+            assert_eq!(status_line, "HTTP/1.1 101 Switching Protocols\r\n");
+            let mut line = String::new();
+            while line != "\r\n" {
+                line.clear();
+                reader.read_line(&mut line).unwrap();
+                if line.starts_with("Sec-WebSocket-Accept") {
+                    assert!(line.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+                }
+            }
+
+            Self { reader }
+        }
+
+        fn next(&mut self) -> Value {
+            let Frame { opcode, payload } = websocket::read_frame(&mut self.reader).unwrap();
+            assert_eq!(opcode, Opcode::Text);
+
+            serde_json::from_slice(&payload).unwrap()
+        }
+
+        fn next_frame_is_close(&mut self) -> bool {
+            websocket::read_frame(&mut self.reader).unwrap().opcode == Opcode::Close
+        }
+    }
+
+    /// Return the session ID and each player's token.
+    fn set_up_lobby(address: SocketAddr) -> (String, HashMap<&'static str, String>) {
+        let created = post(
+            address,
+            "/sessions",
+            json!({ "owner": "A", "displayName": "Ann" }),
+        );
+        let id = created["sessionId"].as_str().unwrap().to_owned();
+        let mut tokens = HashMap::new();
+        tokens.insert("A", created["token"].as_str().unwrap().to_owned());
+
+        for player in ["B", "C"] {
+            let joined = post(
+                address,
+                &format!("/sessions/{}/players", id),
+                json!({ "player": player }),
+            );
+            tokens.insert(player, joined["token"].as_str().unwrap().to_owned());
+        }
+        post(
+            address,
+            &format!("/sessions/{}/cards", id),
+            json!({
+                "requester": "A",
+                "token": tokens["A"],
+                "questions": ["Why _?"],
+                "answers": (0..40).map(|i| i.to_string()).collect::<Vec<_>>(),
+            }),
+        );
+
+        (id, tokens)
+    }
+
+    #[test]
+    fn play_a_round() {
+        let address = start_server();
+        let (id, tokens) = set_up_lobby(address);
+
+        let lobby = get(address, &format!("/sessions/{}", id));
+        assert_eq!(lobby["type"], "lobby");
+        assert_eq!(lobby["owner"], "A");
+        assert_eq!(lobby["players"], json!(["A", "B", "C"]));
+        assert_eq!(lobby["displayNames"]["A"], "Ann");
+
+        let mut updates = Updates::connect(
+            address,
+            &format!("/sessions/{}/updates?player=B&token={}", id, tokens["B"]),
+        );
+        assert_eq!(updates.next()["type"], "lobby");
+
+        post(
+            address,
+            &format!("/sessions/{}/start", id),
+            json!({ "requester": "A", "token": tokens["A"] }),
+        );
+        let update = updates.next();
+        assert_eq!(update["type"], "game");
+        assert_eq!(update["events"][0]["type"], "roundStarted");
+        assert_eq!(update["view"]["player"], "B");
+        assert_eq!(update["view"]["hand"].as_array().unwrap().len(), 10);
+
+        let judge = update["view"]["public"]["judge"]
+            .as_str()
+            .unwrap()
+            .to_owned();
+        let players: Vec<_> = ["A", "B", "C"]
+            .into_iter()
+            .filter(|player| *player != judge)
+            .collect();
+        for player in players.iter() {
+            let view = get(
+                address,
+                &format!(
+                    "/sessions/{}?player={}&token={}",
+                    id, player, tokens[player]
+                ),
+            );
+            let card_id = view["view"]["hand"][0]["id"].clone();
+            post(
+                address,
+                &format!("/sessions/{}/submit", id),
+                json!({ "player": player, "token": tokens[player], "cardIds": [card_id] }),
+            );
+        }
+        assert_eq!(updates.next()["events"][0]["type"], "playerSubmitted");
+        let update = updates.next();
+        // Who wrote what stays secret.
+        assert!(update["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|event| event["type"] != "submissionsClosed"));
+        assert_eq!(
+            update["view"]["public"]["answers"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let (status, error) = send(
+            address,
+            "POST",
+            &format!("/sessions/{}/judge", id),
+            json!({ "player": players[0], "token": tokens[players[0]], "answer": 0 }),
+        );
+        assert_eq!(status, "403 Forbidden");
+        assert_eq!(error["code"], "notTheJudge");
+
+        post(
+            address,
+            &format!("/sessions/{}/judge", id),
+            json!({ "player": judge, "token": tokens[judge.as_str()], "answer": 1 }),
+        );
+        let update = updates.next();
+        let event_types: Vec<_> = update["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| event["type"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            event_types,
+            vec!["judgeChose", "scoresChanged", "roundStarted"]
+        );
+        assert_eq!(update["view"]["public"]["round"], 2);
+
+        let public = get(address, &format!("/sessions/{}", id));
+        assert_eq!(public["view"]["round"], 2);
+        assert!(public["view"].get("hand").is_none());
+    }
+
+    #[test]
+    fn end_a_game_at_its_win_target() {
+        let address = start_server();
+        let (id, tokens) = set_up_lobby(address);
+        let mut updates = Updates::connect(
+            address,
+            &format!("/sessions/{}/updates?player=A&token={}", id, tokens["A"]),
+        );
+        updates.next();
+
+        let mut round = 0;
+        loop {
+            round += 1;
+            if round == 1 {
+                post(
+                    address,
+                    &format!("/sessions/{}/start", id),
+                    json!({ "requester": "A", "token": tokens["A"] }),
+                );
+            }
+            let update = updates.next();
+            if update["type"] == "closed" {
+                let events = update["events"].as_array().unwrap();
+                assert_eq!(events.last().unwrap()["type"], "gameOver");
+                assert!(updates.next_frame_is_close());
+                break;
+            }
+            assert!(round < 100, "the game never ended");
+
+            let judge = update["view"]["public"]["judge"]
+                .as_str()
+                .unwrap()
+                .to_owned();
+            for player in ["A", "B", "C"] {
+                if player == judge {
+                    continue;
+                }
+                let view = get(
+                    address,
+                    &format!(
+                        "/sessions/{}?player={}&token={}",
+                        id, player, tokens[player]
+                    ),
+                );
+                let card_id = view["view"]["hand"][0]["id"].clone();
+                post(
+                    address,
+                    &format!("/sessions/{}/submit", id),
+                    json!({ "player": player, "token": tokens[player], "cardIds": [card_id] }),
+                );
+                // The update for the submission.
+                updates.next();
+            }
+            post(
+                address,
+                &format!("/sessions/{}/judge", id),
+                json!({ "player": judge, "token": tokens[judge.as_str()], "answer": 0 }),
+            );
+        }
+
+        let (status, error) = send(address, "GET", &format!("/sessions/{}", id), Value::Null);
+        assert_eq!(status, "404 Not Found");
+        assert_eq!(error["code"], "sessionDoesNotExist");
+    }
+
+    #[test]
+    fn reject_bad_requests() {
+        let address = start_server();
+        let (id, tokens) = set_up_lobby(address);
+
+        let (status, error) = send(address, "POST", "/sessions", json!({ "owner": "A" }));
+        assert_eq!(
+            (status.as_str(), error["code"].as_str()),
+            ("400 Bad Request", Some("ownerAlreadyHasLobby"))
+        );
+
+        let (status, error) = send(
+            address,
+            "POST",
+            &format!("/sessions/{}/start", id),
+            json!({ "requester": "B", "token": tokens["B"] }),
+        );
+        assert_eq!(
+            (status.as_str(), error["code"].as_str()),
+            ("403 Forbidden", Some("notSessionOwner"))
+        );
+
+        let (status, error) = send(address, "POST", "/sessions/42/players", json!({}));
+        assert_eq!(
+            (status.as_str(), error["code"].as_str()),
+            ("400 Bad Request", Some("badRequest"))
+        );
+
+        let (status, error) = send(address, "DELETE", "/sessions", Value::Null);
+        assert_eq!(
+            (status.as_str(), error["code"].as_str()),
+            ("404 Not Found", Some("notFound"))
+        );
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "GET /sessions/{}/updates?player=Z&token={} HTTP/1.1\r\nUpgrade: websocket\r\n\
+             Sec-WebSocket-Key: x\r\n\r\n",
+            id, tokens["A"]
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(
+            response.starts_with("HTTP/1.1 401 Unauthorized\r\n"),
+            "{}",
+            response
+        );
+        assert!(response.contains("invalidToken"));
+    }
+
+    #[test]
+    fn reject_requests_without_the_player_token() {
+        let address = start_server();
+        let (id, tokens) = set_up_lobby(address);
+
+        let (status, error) = send(
+            address,
+            "POST",
+            &format!("/sessions/{}/start", id),
+            json!({ "requester": "A", "token": tokens["B"] }),
+        );
+        assert_eq!(
+            (status.as_str(), error["code"].as_str()),
+            ("401 Unauthorized", Some("invalidToken"))
+        );
+
+        let (status, error) = send(
+            address,
+            "GET",
+            &format!("/sessions/{}?player=B", id),
+            Value::Null,
+        );
+        assert_eq!(
+            (status.as_str(), error["code"].as_str()),
+            ("401 Unauthorized", Some("invalidToken"))
+        );
+
+        post(
+            address,
+            &format!("/sessions/{}/leave", id),
+            json!({ "player": "C", "token": tokens["C"] }),
+        );
+        let (status, _) = send(
+            address,
+            "GET",
+            &format!("/sessions/{}?player=C&token={}", id, tokens["C"]),
+            Value::Null,
+        );
+        assert_eq!(status, "401 Unauthorized");
+    }
+}
@@ -0,0 +1,194 @@
+//! Just enough of RFC 6455 to push JSON to browsers: the opening handshake, unfragmented text
+//! frames from the server, and reading the control frames clients send.
+
+use std::io::{self, Read, Write};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+const HANDSHAKE_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Frames from clients are only read to answer pings and closes, so they are small.
+const MAX_PAYLOAD_SIZE: u64 = 1 << 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Opcode {
+    Continuation,
+    Text,
+    Binary,
+    Close,
+    Ping,
+    Pong,
+}
+
+impl Opcode {
+    fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0x0 => Some(Self::Continuation),
+            0x1 => Some(Self::Text),
+            0x2 => Some(Self::Binary),
+            0x8 => Some(Self::Close),
+            0x9 => Some(Self::Ping),
+            0xA => Some(Self::Pong),
+            _ => None,
+        }
+    }
+
+    fn bits(self) -> u8 {
+        match self {
+            Self::Continuation => 0x0,
+            Self::Text => 0x1,
+            Self::Binary => 0x2,
+            Self::Close => 0x8,
+            Self::Ping => 0x9,
+            Self::Pong => 0xA,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub opcode: Opcode,
+    /// Unmasked.
+    pub payload: Vec<u8>,
+}
+
+/// The value of `Sec-WebSocket-Accept` for the client's `Sec-WebSocket-Key`.
+pub fn accept_key(key: &str) -> String {
+    let mut sha1 = sha1_smol::Sha1::new();
+    sha1.update(key.trim().as_bytes());
+    sha1.update(HANDSHAKE_GUID.as_bytes());
+
+    STANDARD.encode(sha1.digest().bytes())
+}
+
+pub fn write_handshake(writer: &mut impl Write, key: &str) -> io::Result<()> {
+    write!(
+        writer,
+        "HTTP/1.1 101 Switching Protocols\r\n\
+         Upgrade: websocket\r\n\
+         Connection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(key)
+    )?;
+    writer.flush()
+}
+
+/// Servers never mask their frames.
+pub fn write_frame(writer: &mut impl Write, opcode: Opcode, payload: &[u8]) -> io::Result<()> {
+    let mut header = vec![0x80 | opcode.bits()];
+    match payload.len() {
+        len if len < 126 => header.push(len as u8),
+        len if len <= u16::MAX as usize => {
+            header.push(126);
+            header.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            header.push(127);
+            header.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+
+    writer.write_all(&header)?;
+    writer.write_all(payload)?;
+    writer.flush()
+}
+
+pub fn write_text(writer: &mut impl Write, text: &str) -> io::Result<()> {
+    write_frame(writer, Opcode::Text, text.as_bytes())
+}
+
+pub fn read_frame(reader: &mut impl Read) -> io::Result<Frame> {
+    let mut header = [0; 2];
+    reader.read_exact(&mut header)?;
+
+    let opcode = Opcode::from_bits(header[0] & 0x0F)
+        .ok_or_else(|| invalid_data("unknown WebSocket opcode"))?;
+    let is_masked = header[1] & 0x80 != 0;
+    let len = match header[1] & 0x7F {
+        126 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_be_bytes(len) as u64
+        }
+        127 => {
+            let mut len = [0; 8];
+            reader.read_exact(&mut len)?;
+            u64::from_be_bytes(len)
+        }
+        len => len as u64,
+    };
+    if len > MAX_PAYLOAD_SIZE {
+        return Err(invalid_data("WebSocket frame too large"));
+    }
+
+    let mut mask = [0; 4];
+    if is_masked {
+        reader.read_exact(&mut mask)?;
+    }
+    let mut payload = vec![0; len as usize];
+    reader.read_exact(&mut payload)?;
+    if is_masked {
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+    }
+
+    Ok(Frame { opcode, payload })
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn accept_key_from_the_rfc() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn read_what_was_written() {
+        for len in [0, 125, 126, 1000, 70_000] {
+            let payload = vec![b'x'; len];
+            let mut written = vec![];
+            write_frame(&mut written, Opcode::Text, &payload).unwrap();
+
+            let frame = read_frame(&mut Cursor::new(&written));
+            if len as u64 > MAX_PAYLOAD_SIZE {
+                assert!(frame.is_err());
+            } else {
+                assert_eq!(
+                    frame.unwrap(),
+                    Frame {
+                        opcode: Opcode::Text,
+                        payload
+                    }
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unmask_client_frames() {
+        // A masked "Hello" from the RFC.
+        let raw = [
+            0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58,
+        ];
+
+        assert_eq!(
+            read_frame(&mut Cursor::new(raw)).unwrap(),
+            Frame {
+                opcode: Opcode::Text,
+                payload: b"Hello".to_vec()
+            }
+        );
+    }
+}